@@ -0,0 +1,188 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// This crate facilitates delegating narrow administrative permissions to accounts other than the owner of a contract.
+///
+/// The owner remains the admin of the roles and can:
+/// * `grant_role`: give a role to an account
+/// * `revoke_role`: take a role away from an account
+///
+/// Additionally, it exposes the following messages:
+/// * `has_role`: returns whether an account has a role
+/// * `renounce_role`: callable by an account holding a role, gives it up, e.g. when its key is compromised
+///
+/// In order to use it in your contract, implement the methods of the `AccessControl` trait: in most cases, you can call the corresponding methods on the `AccessControlData` object
+/// and emit the `RoleGranted`/`RoleRevoked` events of your contract. Then check the role of the caller in the messages it should be allowed to call.
+use ink::{
+    primitives::AccountId,
+    storage::{traits::ManualKey, Mapping},
+};
+use scale::{Decode, Encode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Role {
+    /// Can halt the contract, but not resume it
+    Pauser,
+    /// Can change the parameters the fees are calculated from
+    FeeManager,
+    /// Can update the price reported by the gas price oracle
+    PriceUpdater,
+}
+
+#[derive(Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AccessControlError {
+    /// The caller tried to grant or revoke a role but is not the admin of the roles
+    CallerNotAdmin(AccountId),
+    /// The account doesn't have the role it was supposed to give up or be deprived of
+    MissingRole(AccountId, Role),
+    /// Granting the role has to go through the timelock of the contract
+    TimelockRequired,
+}
+
+pub type AccessControlResult<T> = Result<T, AccessControlError>;
+
+/// The members of the roles, stored under the same key in every contract
+#[derive(Debug, Default)]
+#[ink::storage_item]
+pub struct AccessControlData {
+    members: Mapping<(Role, AccountId), (), ManualKey<0x524F4C45>>,
+}
+
+impl AccessControlData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+        self.members.contains((role, account))
+    }
+
+    /// `admin` is the account allowed to grant and revoke the roles, usually the owner of the contract
+    pub fn grant_role(
+        &mut self,
+        caller: AccountId,
+        admin: AccountId,
+        role: Role,
+        account: AccountId,
+    ) -> AccessControlResult<()> {
+        ensure_admin(caller, admin)?;
+        self.members.insert((role, account), &());
+        Ok(())
+    }
+
+    pub fn revoke_role(
+        &mut self,
+        caller: AccountId,
+        admin: AccountId,
+        role: Role,
+        account: AccountId,
+    ) -> AccessControlResult<()> {
+        ensure_admin(caller, admin)?;
+        self.take_role(role, account)
+    }
+
+    pub fn renounce_role(&mut self, caller: AccountId, role: Role) -> AccessControlResult<()> {
+        self.take_role(role, caller)
+    }
+
+    fn take_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()> {
+        self.members
+            .take((role, account))
+            .map(|_| ())
+            .ok_or(AccessControlError::MissingRole(account, role))
+    }
+}
+
+fn ensure_admin(caller: AccountId, admin: AccountId) -> AccessControlResult<()> {
+    if caller != admin {
+        Err(AccessControlError::CallerNotAdmin(caller))
+    } else {
+        Ok(())
+    }
+}
+
+/// Implement this trait to let the owner of your contract delegate some of its permissions.
+///
+/// An account can hold any number of roles, and a role can be held by any number of accounts.
+#[ink::trait_definition]
+pub trait AccessControl {
+    /// Returns whether `account` has `role`.
+    #[ink(message)]
+    fn has_role(&self, role: Role, account: AccountId) -> bool;
+
+    /// Gives `role` to `account`.
+    /// Can only be called by the admin of the roles.
+    #[ink(message)]
+    fn grant_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()>;
+
+    /// Takes `role` away from `account`.
+    /// Can only be called by the admin of the roles.
+    #[ink(message)]
+    fn revoke_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()>;
+
+    /// The caller gives up `role`.
+    #[ink(message)]
+    fn renounce_role(&mut self, role: Role) -> AccessControlResult<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accounts() -> (AccountId, AccountId) {
+        (AccountId::from([1; 32]), AccountId::from([2; 32]))
+    }
+
+    #[ink::test]
+    fn admin_grants_and_revokes_roles() {
+        let (admin, alice) = accounts();
+        let mut roles = AccessControlData::new();
+
+        assert_eq!(
+            roles.grant_role(alice, admin, Role::Pauser, alice),
+            Err(AccessControlError::CallerNotAdmin(alice))
+        );
+        assert!(!roles.has_role(Role::Pauser, alice));
+
+        assert_eq!(roles.grant_role(admin, admin, Role::Pauser, alice), Ok(()));
+        assert!(roles.has_role(Role::Pauser, alice));
+        assert!(!roles.has_role(Role::FeeManager, alice));
+        assert!(!roles.has_role(Role::Pauser, admin));
+
+        assert_eq!(
+            roles.revoke_role(alice, admin, Role::Pauser, alice),
+            Err(AccessControlError::CallerNotAdmin(alice))
+        );
+        assert_eq!(roles.revoke_role(admin, admin, Role::Pauser, alice), Ok(()));
+        assert!(!roles.has_role(Role::Pauser, alice));
+        assert_eq!(
+            roles.revoke_role(admin, admin, Role::Pauser, alice),
+            Err(AccessControlError::MissingRole(alice, Role::Pauser))
+        );
+    }
+
+    #[ink::test]
+    fn member_renounces_role() {
+        let (admin, alice) = accounts();
+        let mut roles = AccessControlData::new();
+        assert_eq!(
+            roles.grant_role(admin, admin, Role::FeeManager, alice),
+            Ok(())
+        );
+
+        assert_eq!(
+            roles.renounce_role(alice, Role::Pauser),
+            Err(AccessControlError::MissingRole(alice, Role::Pauser))
+        );
+        assert_eq!(roles.renounce_role(alice, Role::FeeManager), Ok(()));
+        assert!(!roles.has_role(Role::FeeManager, alice));
+        assert_eq!(
+            roles.renounce_role(alice, Role::FeeManager),
+            Err(AccessControlError::MissingRole(alice, Role::FeeManager))
+        );
+    }
+}
@@ -534,6 +534,104 @@ fn correct_receive_request(mut session: Session) {
     );
 }
 
+#[drink::test]
+fn receive_requests_reports_request_that_fails_to_execute(mut session: Session) {
+    mint_to_default_accounts(&mut session);
+
+    let most = most::setup(
+        &mut session,
+        guardian_accounts(),
+        DEFAULT_THRESHOLD,
+        POCKET_MONEY,
+        RELAY_GAS_USAGE,
+        MIN_GAS_PRICE,
+        MAX_GAS_PRICE,
+        DEFAULT_GAS_PRICE,
+        GAS_ORACLE_MAX_AGE,
+        ORACLE_CALL_GAS_LIMIT,
+        BASE_FEE_BUFFER_PERCENTAGE,
+        None,
+        owner(),
+        BOB,
+        DEFAULT_ETH_TRANSFER_GAS_USAGE,
+    );
+    let token = token::setup(&mut session, "TestToken".to_string(), bob(), BOB);
+
+    let token_address: ink_primitives::AccountId = token.into();
+    most::add_pair(
+        &mut session,
+        &most,
+        *token_address.as_ref(),
+        REMOTE_TOKEN,
+        true,
+        OWNER,
+    )
+    .expect("Add pair should succeed");
+
+    most::set_halted(&mut session, &most, false, OWNER).expect("Unhalt should succeed");
+
+    let committee_id: u128 = 0;
+    let amount: u128 = 100;
+    // the bridge holds enough tokens for only one of the requests
+    token::transfer(&mut session, &token, most.into(), amount + 50, BOB)
+        .expect("Transfer should succeed");
+
+    let requests: Vec<most::RequestData> = [1, 2]
+        .into_iter()
+        .map(|nonce| most::RequestData {
+            request_hash: hash_request_data(committee_id, token_address, amount, alice(), nonce),
+            committee_id,
+            dest_token_address: *token_address.as_ref(),
+            amount,
+            dest_receiver_address: *alice().as_ref(),
+            request_nonce: nonce,
+        })
+        .collect();
+
+    let alice_balance_before = token::balance_of(&mut session, &token, alice());
+
+    GUARDIANS
+        .iter()
+        .take(DEFAULT_THRESHOLD as usize - 1)
+        .for_each(|guardian| {
+            let result =
+                most::receive_requests(&mut session, &most, requests.clone(), guardian.clone());
+
+            assert_eq!(result, Ok(vec![Ok(()), Ok(())]));
+        });
+
+    let results = most::receive_requests(
+        &mut session,
+        &most,
+        requests.clone(),
+        GUARDIANS[DEFAULT_THRESHOLD as usize - 1].clone(),
+    )
+    .expect("A failed execution should not revert the batch");
+
+    assert_eq!(results[0], Ok(()));
+    assert!(matches!(results[1], Err(MostError::PSP22(_))));
+    assert_eq!(
+        token::balance_of(&mut session, &token, alice()),
+        alice_balance_before + amount
+    );
+
+    // the failed vote isn't recorded, so it can be cast again once the bridge holds enough tokens
+    token::transfer(&mut session, &token, most.into(), amount, BOB)
+        .expect("Transfer should succeed");
+    let results = most::receive_requests(
+        &mut session,
+        &most,
+        requests.clone(),
+        GUARDIANS[DEFAULT_THRESHOLD as usize - 1].clone(),
+    );
+
+    assert_eq!(results, Ok(vec![Ok(()), Ok(())]));
+    assert_eq!(
+        token::balance_of(&mut session, &token, alice()),
+        alice_balance_before + 2 * amount
+    );
+}
+
 #[drink::test]
 fn outdated_oracle_price(mut session: Session) {
     mint_to_default_accounts(&mut session);
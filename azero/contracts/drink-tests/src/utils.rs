@@ -82,7 +82,7 @@ pub const DEFAULT_ETH_TRANSFER_GAS_USAGE: u128 = 60_000;
 pub mod most {
     use super::*;
     use wrappers::most;
-    pub use wrappers::most::{Instance as Most, MostError};
+    pub use wrappers::most::{Instance as Most, MostError, RequestData};
 
     pub fn setup(
         session: &mut Session,
@@ -195,7 +195,7 @@ pub mod most {
         handle_ink_error(
             session
                 .execute(
-                    most::Instance::send_request(most, token, amount, remote_receiver)
+                    most::Instance::send_request(most, token, amount, remote_receiver, None)
                         .with_value(value_transferred),
                 )
                 .unwrap(),
@@ -215,7 +215,7 @@ pub mod most {
         handle_ink_error(
             session
                 .execute(
-                    most::Instance::send_request_native_azero(most, amount, remote_receiver)
+                    most::Instance::send_request_native_azero(most, amount, remote_receiver, None)
                         .with_value(value_transferred),
                 )
                 .unwrap(),
@@ -250,6 +250,21 @@ pub mod most {
         )
     }
 
+    pub fn receive_requests(
+        session: &mut Session,
+        most: &Most,
+        requests: Vec<RequestData>,
+        caller: drink::AccountId32,
+    ) -> Result<Vec<Result<(), most::MostError>>, most::MostError> {
+        let _ = session.set_actor(caller);
+
+        handle_ink_error(
+            session
+                .execute(most::Instance::receive_requests(most, requests))
+                .unwrap(),
+        )
+    }
+
     pub fn set_committee(
         session: &mut Session,
         most: &Most,
@@ -2,6 +2,9 @@
 
 #[ink::contract]
 pub mod oracle {
+    use access_control::{
+        AccessControl, AccessControlData, AccessControlError, AccessControlResult, Role,
+    };
     use gas_oracle_trait::EthGasPriceOracle;
     use ink::{
         env::{set_code_hash, Error as InkEnvError},
@@ -14,6 +17,8 @@ pub mod oracle {
     pub struct Oracle {
         /// data for Ownable2Step - oracle owner
         ownable_data: Ownable2StepData,
+        /// accounts the owner delegated some of its permissions to
+        roles: AccessControlData,
         /// timestamp of the last update in ms since UNIX epoch
         last_update: u64,
         /// price of one unit of ETH gas in picoAZERO (i.e. 10^-12 AZERO)
@@ -70,20 +75,44 @@ pub mod oracle {
         pub new_code_hash: [u8; 32],
     }
 
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RoleGranted {
+        pub role: Role,
+        #[ink(topic)]
+        pub account: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RoleRevoked {
+        pub role: Role,
+        #[ink(topic)]
+        pub account: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
     impl Oracle {
         #[ink(constructor)]
         pub fn new(owner: AccountId, init_price: u128) -> Self {
             Self {
                 ownable_data: Ownable2StepData::new(owner),
+                roles: AccessControlData::new(),
                 last_update: Self::env().block_timestamp(),
                 last_price: init_price,
                 reserved: None,
             }
         }
 
+        /// Can only be called by the owner or an account with the `PriceUpdater` role
         #[ink(message)]
         pub fn update_price(&mut self, new_price: u128) -> Result<(), OracleError> {
-            self.ensure_owner()?;
+            self.ensure_owner_or_role(Role::PriceUpdater)?;
             self.last_update = self.env().block_timestamp();
             self.last_price = new_price;
             self.env().emit_event(PriceUpdated {
@@ -102,6 +131,13 @@ pub mod oracle {
             });
             Ok(())
         }
+
+        fn ensure_owner_or_role(&self, role: Role) -> Result<(), OracleError> {
+            if self.has_role(role, self.env().caller()) {
+                return Ok(());
+            }
+            Ok(self.ensure_owner()?)
+        }
     }
 
     impl EthGasPriceOracle for Oracle {
@@ -149,6 +185,55 @@ pub mod oracle {
         }
     }
 
+    impl AccessControl for Oracle {
+        #[ink(message)]
+        fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.roles.has_role(role, account)
+        }
+
+        #[ink(message)]
+        fn grant_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            let admin = <Self as Ownable2Step>::get_owner(self)
+                .map_err(|_| AccessControlError::CallerNotAdmin(caller))?;
+
+            self.roles.grant_role(caller, admin, role, account)?;
+            self.env().emit_event(RoleGranted {
+                role,
+                account,
+                caller,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn revoke_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            let admin = <Self as Ownable2Step>::get_owner(self)
+                .map_err(|_| AccessControlError::CallerNotAdmin(caller))?;
+
+            self.roles.revoke_role(caller, admin, role, account)?;
+            self.env().emit_event(RoleRevoked {
+                role,
+                account,
+                caller,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn renounce_role(&mut self, role: Role) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            self.roles.renounce_role(caller, role)?;
+            self.env().emit_event(RoleRevoked {
+                role,
+                account: caller,
+                caller,
+            });
+            Ok(())
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use ink::env::{
@@ -218,5 +303,32 @@ pub mod oracle {
             assert!(oracle.update_price(new_price).is_ok());
             assert_eq!(oracle.get_price(), (new_price, 0));
         }
+
+        #[ink::test]
+        fn price_updater_can_update_price() {
+            let owner = default_accounts::<DefEnv>().alice;
+            let price_updater = default_accounts::<DefEnv>().bob;
+            set_caller::<DefEnv>(owner);
+            let mut oracle = Oracle::new(owner, 100);
+            assert_eq!(oracle.grant_role(Role::PriceUpdater, price_updater), Ok(()));
+
+            set_caller::<DefEnv>(price_updater);
+            assert_eq!(oracle.update_price(200), Ok(()));
+            assert_eq!(oracle.get_price(), (200, 0));
+            assert!(oracle.upgrade_contract([0; 32]).is_err());
+
+            set_caller::<DefEnv>(owner);
+            assert_eq!(
+                oracle.revoke_role(Role::PriceUpdater, price_updater),
+                Ok(())
+            );
+            set_caller::<DefEnv>(price_updater);
+            assert!(oracle.update_price(300).is_err());
+
+            set_caller::<DefEnv>(owner);
+            assert_eq!(oracle.grant_role(Role::FeeManager, price_updater), Ok(()));
+            set_caller::<DefEnv>(price_updater);
+            assert!(oracle.update_price(300).is_err());
+        }
     }
 }
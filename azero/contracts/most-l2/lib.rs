@@ -1,12 +1,19 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub use access_control::{AccessControlError, Role};
 pub use ownable2step::Ownable2StepError;
 
-pub use self::most_l2::{MostError, MostL2Ref};
+pub use self::most_l2::{
+    AdminOperation, MostError, MostL2Ref, RequestData, RequestDomain, ScheduledOperation,
+    TransferLimits, TransferQuote,
+};
 
 #[ink::contract]
 pub mod most_l2 {
 
+    use access_control::{
+        AccessControl, AccessControlData, AccessControlError, AccessControlResult, Role,
+    };
     use ink::{
         codegen::TraitCallBuilder,
         contract_ref,
@@ -18,7 +25,10 @@ pub mod most_l2 {
     use psp22::{PSP22Error, PSP22};
     use psp22_traits::WrappedAZERO;
     use scale::{Decode, Encode};
-    use shared::{hash_request_data, Keccak256HashOutput as HashedRequest};
+    use shared::{
+        hash_request_data, hash_request_data_v1, request_domain_separator,
+        Keccak256HashOutput as HashedRequest,
+    };
 
     type CommitteeId = u128;
 
@@ -28,6 +38,9 @@ pub mod most_l2 {
     /// Flat fee equal to 0.5 azero = 1e12 / 2
     const DEFAULT_FLAT_FEE: u128 = 1_000_000_000_000 / 2;
 
+    /// minimum delay in milliseconds of the scheduled owner operations, once the timelock is on
+    const MIN_OPERATION_DELAY: u64 = 24 * 60 * 60 * 1000;
+
     #[ink(event)]
     #[derive(Debug)]
     #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
@@ -102,6 +115,155 @@ pub mod most_l2 {
         pub signer: AccountId,
     }
 
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct CommitteeUpdated {
+        #[ink(topic)]
+        pub committee_id: CommitteeId,
+        pub members: Vec<AccountId>,
+        pub signature_threshold: u128,
+        pub activated_at: BlockNumber,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct CodeHashChanged {
+        pub previous_code_hash: Hash,
+        pub new_code_hash: Hash,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct FlatFeeChanged {
+        pub previous_flat_fee: u128,
+        pub new_flat_fee: u128,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct SupportedPairChanged {
+        #[ink(topic)]
+        pub from: [u8; 32],
+        pub previous_to: Option<[u8; 32]>,
+        pub new_to: Option<[u8; 32]>,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct TransferLimitsChanged {
+        #[ink(topic)]
+        pub token: [u8; 32],
+        pub previous_limits: TransferLimits,
+        pub new_limits: TransferLimits,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct WazeroChanged {
+        pub previous_address: Option<AccountId>,
+        pub new_address: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    /// Emitted when funds are recovered from the contract, `token` is `None` for AZERO
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct FundsRecovered {
+        pub token: Option<AccountId>,
+        #[ink(topic)]
+        pub receiver: AccountId,
+        pub amount: u128,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct OperationScheduled {
+        #[ink(topic)]
+        pub operation_id: u128,
+        pub operation: AdminOperation,
+        pub executable_at: u64,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct OperationCancelled {
+        #[ink(topic)]
+        pub operation_id: u128,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct OperationExecuted {
+        #[ink(topic)]
+        pub operation_id: u128,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct OperationDelayChanged {
+        pub previous_delay: u64,
+        pub new_delay: u64,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RequestDomainSet {
+        pub domain: RequestDomain,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RoleGranted {
+        pub role: Role,
+        #[ink(topic)]
+        pub account: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RoleRevoked {
+        pub role: Role,
+        #[ink(topic)]
+        pub account: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
     #[derive(Default, Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
     #[cfg_attr(
         feature = "std",
@@ -111,6 +273,17 @@ pub mod most_l2 {
         signature_count: u128,
     }
 
+    /// Guardians that signed a request, one bit per position in the committee
+    #[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct RequestSigners {
+        committee_id: CommitteeId,
+        positions: u128,
+    }
+
     #[derive(Debug, Encode, Decode, Clone, Copy)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum RequestStatus {
@@ -119,6 +292,172 @@ pub mod most_l2 {
         RequestHashNotKnown,
     }
 
+    /// Owner operation that takes effect only after the operation delay, once it is set
+    ///
+    /// The owner operations left out of the timelock stay instant on purpose:
+    /// * halting and revoking roles only take power away and are needed in an emergency,
+    ///   resuming only restores what the timelocked configuration allows
+    /// * the flat fee only changes what the users pay, the senders bound it with `max_fee`
+    /// * the request domain can only be set once, while the bridge is halted
+    #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum AdminOperation {
+        SetCode {
+            code_hash: [u8; 32],
+        },
+        SetCommittee {
+            committee: Vec<AccountId>,
+            signature_threshold: u128,
+        },
+        AddPair {
+            from: [u8; 32],
+            to: [u8; 32],
+        },
+        RecoverPsp22 {
+            token: AccountId,
+            receiver: AccountId,
+            amount: u128,
+        },
+        RecoverAzero {
+            receiver: AccountId,
+            amount: u128,
+        },
+        SetOperationDelay {
+            delay: u64,
+        },
+        RemovePair {
+            from: [u8; 32],
+        },
+        SetTransferLimits {
+            token: [u8; 32],
+            limits: TransferLimits,
+        },
+        SetWazero {
+            wazero_address: AccountId,
+        },
+        GrantRole {
+            role: Role,
+            account: AccountId,
+        },
+    }
+
+    #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct ScheduledOperation {
+        pub operation: AdminOperation,
+        /// timestamp in milliseconds after which the operation can be executed
+        pub executable_at: u64,
+    }
+
+    /// Limits on the transfers of a token, zero meaning no limit. The caps apply to the amounts
+    /// transferred within a rolling window: the capacity used up by a transfer frees up linearly
+    /// over the following `window` milliseconds.
+    #[derive(Default, Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct TransferLimits {
+        /// minimum amount of a single outgoing request
+        pub min_amount: u128,
+        /// maximum amount of a single outgoing request
+        pub max_amount: u128,
+        /// maximum amount of the outgoing requests within the window
+        pub outflow_cap: u128,
+        /// maximum amount of the incoming requests processed within the window
+        pub inflow_cap: u128,
+        /// length of the window in milliseconds
+        pub window: u64,
+    }
+
+    impl TransferLimits {
+        fn is_valid(&self) -> bool {
+            let amounts_valid = self.max_amount == 0 || self.max_amount >= self.min_amount;
+            let window_valid = self.window > 0 || (self.outflow_cap == 0 && self.inflow_cap == 0);
+            amounts_valid && window_valid
+        }
+    }
+
+    /// Source of the incoming requests, bound into the hashes of the requests from `from_nonce` on
+    #[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct RequestDomain {
+        /// id of the chain the requests are sent from
+        pub src_chain_id: u128,
+        /// address of the `Most` contract the requests are sent from, left-padded with zeroes
+        pub src_contract: [u8; 32],
+        /// nonce of the first request hashed with `hash_request_data_v1`
+        pub from_nonce: u128,
+    }
+
+    /// Outcome of an outgoing transfer at the current fee
+    #[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TransferQuote {
+        /// token the receiver gets on the destination chain
+        pub dest_token_address: [u8; 32],
+        /// flat fee, to be transferred along with the request
+        pub fee: Balance,
+        /// amount of the request
+        pub dest_amount: u128,
+        /// always `None`, the flat fee only changes when a new one is set
+        pub fee_valid_until: Option<u64>,
+    }
+
+    /// Vote of a guardian on a single request, as passed to `receive_requests`
+    #[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct RequestData {
+        pub request_hash: HashedRequest,
+        pub committee_id: CommitteeId,
+        pub dest_token_address: [u8; 32],
+        pub amount: u128,
+        pub dest_receiver_address: [u8; 32],
+        pub request_nonce: u128,
+    }
+
+    /// Amount of a token transferred in one direction, accounted against a cap
+    #[derive(Default, Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Flow {
+        amount: u128,
+        updated_at: u64,
+    }
+
+    impl Flow {
+        /// The part of the amount that still counts against `cap` at `now`
+        fn current(&self, cap: u128, window: u64, now: u64) -> u128 {
+            let elapsed = now.saturating_sub(self.updated_at);
+            if window == 0 || elapsed >= window {
+                return 0;
+            }
+
+            let released = cap.saturating_mul(elapsed as u128) / window as u128;
+            self.amount.saturating_sub(released)
+        }
+
+        /// Adds `amount` to the flow, `None` if that exceeds `cap`
+        fn add(&self, amount: u128, cap: u128, window: u64, now: u64) -> Option<Self> {
+            let amount = self.current(cap, window, now).checked_add(amount)?;
+            (amount <= cap).then_some(Self {
+                amount,
+                updated_at: now,
+            })
+        }
+    }
+
     #[derive(Debug)]
     #[ink::storage_item]
     pub struct Data {
@@ -137,7 +476,7 @@ pub mod most_l2 {
         ownable_data: Lazy<Ownable2StepData, ManualKey<0xDEADBEEF>>,
         /// requests that are still collecting signatures
         pending_requests: Mapping<HashedRequest, Request, ManualKey<0x50454E44>>,
-        /// signatures per cross chain transfer request
+        /// signatures per cross chain transfer request, of the guardians without a bit in `request_signers`
         signatures: Mapping<(HashedRequest, AccountId), (), ManualKey<0x5349474E>>,
         /// signed & executed requests, a replay protection
         processed_requests: Mapping<HashedRequest, (), ManualKey<0x50524F43>>,
@@ -153,6 +492,32 @@ pub mod most_l2 {
         wazero: Lazy<AccountId, ManualKey<0x77617a65>>,
         /// amount of flat fee paid upon sending transfer request
         flat_fee: Lazy<u128, ManualKey<0x666c6174>>,
+        /// transfer limits per token, keyed by the address of the token on Aleph Zero
+        transfer_limits: Mapping<[u8; 32], TransferLimits, ManualKey<0x4C494D54>>,
+        /// amounts of the outgoing requests per token, accounted against the outflow caps
+        outflows: Mapping<[u8; 32], Flow, ManualKey<0x4F555446>>,
+        /// amounts of the processed incoming requests per token, accounted against the inflow caps
+        inflows: Mapping<[u8; 32], Flow, ManualKey<0x494E4646>>,
+        /// members of each committee, in the order they were set in
+        committee_members: Mapping<CommitteeId, Vec<AccountId>, ManualKey<0x4D454D42>>,
+        /// position of each member in `committee_members`, its bit in `request_signers`
+        committee_positions: Mapping<(CommitteeId, AccountId), u32, ManualKey<0x504F5349>>,
+        /// block at which each committee became the active one
+        committee_activations: Mapping<CommitteeId, BlockNumber, ManualKey<0x41435456>>,
+        /// minimum time in milliseconds between scheduling and executing an owner operation, 0 while the timelock is off
+        operation_delay: Lazy<u64, ManualKey<0x544C434B>>,
+        /// id of the next scheduled owner operation
+        operation_nonce: Lazy<u128, ManualKey<0x4F504E43>>,
+        /// owner operations waiting for the operation delay to pass
+        scheduled_operations: Mapping<u128, ScheduledOperation, ManualKey<0x5343484F>>,
+        /// accounts the owner delegated some of its permissions to
+        roles: AccessControlData,
+        /// source of the incoming requests, once the requests are bound to it
+        request_domain: Lazy<RequestDomain, ManualKey<0x444F4D4E>>,
+        /// guardians that signed each cross chain transfer request
+        request_signers: Mapping<HashedRequest, RequestSigners, ManualKey<0x5349474D>>,
+        /// highest nonce of the incoming requests that reached the quorum, the request domain can only apply above it
+        highest_processed_nonce: Lazy<u128, ManualKey<0x4D41584E>>,
     }
 
     #[derive(Debug, PartialEq, Eq, Encode, Decode)]
@@ -181,6 +546,19 @@ pub mod most_l2 {
         WrappedAzeroNotSet,
         ValueTransferredLowerThanAmount,
         FlatFeeNotSet,
+        InvalidTransferLimits,
+        AmountBelowMinimum,
+        AmountAboveMaximum,
+        OutflowCapExceeded,
+        InflowCapExceeded,
+        DelayNotElapsed,
+        TimelockRequired,
+        InvalidOperationDelay,
+        UnknownOperation,
+        RequestDomainAlreadySet,
+        NonceAlreadyProcessed,
+        FeeAboveMax,
+        AccessControl(AccessControlError),
     }
 
     impl From<InkEnvError> for MostError {
@@ -201,6 +579,12 @@ pub mod most_l2 {
         }
     }
 
+    impl From<AccessControlError> for MostError {
+        fn from(inner: AccessControlError) -> Self {
+            MostError::AccessControl(inner)
+        }
+    }
+
     impl MostL2 {
         #[allow(clippy::too_many_arguments)]
         #[ink(constructor)]
@@ -214,8 +598,8 @@ pub mod most_l2 {
             let committee_id = 0;
 
             let mut committees = Mapping::new();
-            committee.clone().into_iter().for_each(|account| {
-                committees.insert((committee_id, account), &());
+            committee.iter().for_each(|account| {
+                committees.insert((committee_id, *account), &());
             });
 
             let mut committee_sizes = Mapping::new();
@@ -224,6 +608,26 @@ pub mod most_l2 {
             let mut signature_thresholds = Mapping::new();
             signature_thresholds.insert(committee_id, &signature_threshold);
 
+            let activated_at = Self::env().block_number();
+            let mut committee_members = Mapping::new();
+            committee_members.insert(committee_id, &committee);
+            let mut committee_positions = Mapping::new();
+            committee
+                .iter()
+                .enumerate()
+                .for_each(|(position, account)| {
+                    committee_positions.insert((committee_id, *account), &(position as u32));
+                });
+            let mut committee_activations = Mapping::new();
+            committee_activations.insert(committee_id, &activated_at);
+
+            Self::env().emit_event(CommitteeUpdated {
+                committee_id,
+                members: committee,
+                signature_threshold,
+                activated_at,
+            });
+
             let mut data = Lazy::new();
             data.set(&Data {
                 request_nonce: 0,
@@ -249,6 +653,19 @@ pub mod most_l2 {
                 supported_pairs: Mapping::new(),
                 wazero,
                 flat_fee,
+                transfer_limits: Mapping::new(),
+                outflows: Mapping::new(),
+                inflows: Mapping::new(),
+                committee_members,
+                committee_positions,
+                committee_activations,
+                operation_delay: Lazy::new(),
+                operation_nonce: Lazy::new(),
+                scheduled_operations: Mapping::new(),
+                roles: AccessControlData::new(),
+                request_domain: Lazy::new(),
+                request_signers: Mapping::new(),
+                highest_processed_nonce: Lazy::new(),
             })
         }
 
@@ -269,6 +686,8 @@ pub mod most_l2 {
                 return Err(MostError::ZeroTransferAmount);
             }
 
+            self.record_outflow(*src_token_address.as_ref(), amount)?;
+
             let sender = self.env().caller();
 
             if !native_azero_request {
@@ -293,10 +712,18 @@ pub mod most_l2 {
             Ok(())
         }
 
-        fn handle_flat_fee(&mut self, native_to_bridge: u128) -> Result<(), MostError> {
+        fn handle_flat_fee(
+            &mut self,
+            native_to_bridge: u128,
+            max_fee: Option<u128>,
+        ) -> Result<(), MostError> {
             let transferred = self.env().transferred_value();
             let flat_fee = self.flat_fee.get().ok_or(MostError::FlatFeeNotSet)?;
 
+            if matches!(max_fee, Some(max_fee) if flat_fee > max_fee) {
+                return Err(MostError::FeeAboveMax);
+            }
+
             let surplus = transferred
                 .checked_sub(native_to_bridge.saturating_add(flat_fee))
                 .ok_or(MostError::ValueTransferredLowerThanAmount)?;
@@ -318,15 +745,17 @@ pub mod most_l2 {
         ///
         /// Upon checking basic conditions the contract will burn the `amount` number of `src_token_address` tokens from the caller
         /// and emit an event which is to be picked up & acted on up by the bridge guardians.
+        /// Fails if the flat fee is above `max_fee`, when it is given.
         #[ink(message, payable)]
         pub fn send_request(
             &mut self,
             src_token_address: [u8; 32],
             amount: u128,
             dest_receiver_address: [u8; 32],
+            max_fee: Option<u128>,
         ) -> Result<(), MostError> {
             self.ensure_not_halted()?;
-            self.handle_flat_fee(0)?;
+            self.handle_flat_fee(0, max_fee)?;
 
             let dest_token_address = self
                 .supported_pairs
@@ -342,14 +771,19 @@ pub mod most_l2 {
             )
         }
 
+        /// Invoke this tx to initiate native AZERO transfer to the destination chain.
+        ///
+        /// The transferred value covers both `amount_to_bridge` and the flat fee.
+        /// Fails if the flat fee is above `max_fee`, when it is given.
         #[ink(message, payable)]
         pub fn send_request_native_azero(
             &mut self,
             amount_to_bridge: u128,
             dest_receiver_address: [u8; 32],
+            max_fee: Option<u128>,
         ) -> Result<(), MostError> {
             self.ensure_not_halted()?;
-            self.handle_flat_fee(amount_to_bridge)?;
+            self.handle_flat_fee(amount_to_bridge, max_fee)?;
 
             let wrapped_azero_address = self.wazero.get().ok_or(MostError::WrappedAzeroNotSet)?;
             let mut wrapped_azero: contract_ref!(WrappedAZERO) = wrapped_azero_address.into();
@@ -383,7 +817,104 @@ pub mod most_l2 {
             self.ensure_not_halted()?;
 
             let caller = self.env().caller();
-            self.only_committee_member(committee_id, caller)?;
+            let request = RequestData {
+                request_hash,
+                committee_id,
+                dest_token_address,
+                amount,
+                dest_receiver_address,
+                request_nonce,
+            };
+            let signature_threshold = self.check_request(caller, &request)?;
+            self._receive_request(caller, request, signature_threshold)
+        }
+
+        /// Aggregates the votes of a guardian on a batch of requests
+        ///
+        /// Each request is handled as in `receive_request` and its result is returned at the same
+        /// position. A request that fails doesn't revert the batch: its vote isn't recorded, so it can
+        /// be cast again, for example once a request that failed to execute at the quorum can be.
+        #[ink(message)]
+        pub fn receive_requests(
+            &mut self,
+            requests: Vec<RequestData>,
+        ) -> Result<Vec<Result<(), MostError>>, MostError> {
+            self.ensure_not_halted()?;
+
+            let caller = self.env().caller();
+            let mut results = Vec::with_capacity(requests.len());
+            for request in requests {
+                match self.check_request(caller, &request) {
+                    Ok(signature_threshold) => {
+                        results.push(self._receive_request(caller, request, signature_threshold))
+                    }
+                    Err(why) => results.push(Err(why)),
+                }
+            }
+
+            Ok(results)
+        }
+
+        /// Checks a vote of `caller` on a request before any state is changed and returns the
+        /// signature threshold of its committee
+        fn check_request(
+            &self,
+            caller: AccountId,
+            request: &RequestData,
+        ) -> Result<u128, MostError> {
+            self.only_committee_member(request.committee_id, caller)?;
+
+            let hash = self.hash_request(request);
+
+            if !request.request_hash.eq(&hash) {
+                return Err(MostError::HashDoesNotMatchData);
+            }
+
+            self.signature_thresholds
+                .get(request.committee_id)
+                .ok_or(MostError::InvalidThreshold)
+        }
+
+        /// Hashes a request with the version its nonce requires: requests from the nonce set in
+        /// `set_request_domain` on are bound to their source and to this contract
+        fn hash_request(&self, request: &RequestData) -> HashedRequest {
+            match self.request_domain.get() {
+                Some(domain) if request.request_nonce >= domain.from_nonce => hash_request_data_v1(
+                    request_domain_separator(
+                        domain.src_chain_id,
+                        domain.src_contract,
+                        *self.env().account_id().as_ref(),
+                    ),
+                    request.committee_id,
+                    request.dest_token_address.into(),
+                    request.amount,
+                    request.dest_receiver_address.into(),
+                    request.request_nonce,
+                ),
+                _ => hash_request_data(
+                    request.committee_id,
+                    request.dest_token_address.into(),
+                    request.amount,
+                    request.dest_receiver_address.into(),
+                    request.request_nonce,
+                ),
+            }
+        }
+
+        fn _receive_request(
+            &mut self,
+            caller: AccountId,
+            request_data: RequestData,
+            signature_threshold: u128,
+        ) -> Result<(), MostError> {
+            let RequestData {
+                request_hash,
+                committee_id,
+                dest_token_address,
+                amount,
+                dest_receiver_address,
+                request_nonce,
+            } = request_data;
 
             // Don't revert if the request has already been processed as
             // such a call can be made during regular guardian operation.
@@ -395,7 +926,7 @@ pub mod most_l2 {
                 return Ok(());
             }
 
-            if self.signatures.contains((request_hash, caller)) {
+            if self.has_signed_request(caller, request_hash) {
                 self.env().emit_event(RequestAlreadySigned {
                     request_hash,
                     signer: caller,
@@ -403,38 +934,29 @@ pub mod most_l2 {
                 return Ok(());
             }
 
-            let hash = hash_request_data(
-                committee_id,
-                dest_token_address.into(),
-                amount,
-                dest_receiver_address.into(),
-                request_nonce,
-            );
-
-            if !request_hash.eq(&hash) {
-                return Err(MostError::HashDoesNotMatchData);
-            }
-
             let mut request = self.pending_requests.get(request_hash).unwrap_or_default();
-
-            // record vote
             request.signature_count = request
                 .signature_count
                 .checked_add(1)
                 .ok_or(MostError::Arithmetic)?;
-            self.signatures.insert((request_hash, caller), &());
-
-            self.env().emit_event(RequestSigned {
-                signer: caller,
-                request_hash,
-            });
-
-            let signature_threshold = self
-                .signature_thresholds
-                .get(committee_id)
-                .ok_or(MostError::InvalidThreshold)?;
+            let reaches_quorum = request.signature_count >= signature_threshold;
+
+            // the request is executed before anything is recorded, so that a failure leaves no
+            // trace in a batch and the vote can be cast again
+            let limited_token = if dest_token_address == NATIVE_MARKER_ADDRESS {
+                *self
+                    .wazero
+                    .get()
+                    .ok_or(MostError::WrappedAzeroNotSet)?
+                    .as_ref()
+            } else {
+                dest_token_address
+            };
+            if reaches_quorum {
+                if !self.inflow_fits(limited_token, amount) {
+                    return Err(MostError::InflowCapExceeded);
+                }
 
-            if request.signature_count >= signature_threshold {
                 if dest_token_address == NATIVE_MARKER_ADDRESS {
                     self.unwrap_azero_to(dest_receiver_address.into(), amount)?;
                 } else {
@@ -444,26 +966,57 @@ pub mod most_l2 {
                         amount,
                     )?;
                 }
+            }
 
-                // mark it as processed
-                self.processed_requests.insert(request_hash, &());
-                self.pending_requests.remove(request_hash);
+            self.env().emit_event(RequestSigned {
+                signer: caller,
+                request_hash,
+            });
+
+            if reaches_quorum {
+                self.record_inflow(limited_token, amount)?;
+
+                // mark it as processed, its signatures are not needed anymore
+                self.processed_requests.insert(request_hash, &());
+                self.pending_requests.remove(request_hash);
+                self.clear_signatures(request_hash, committee_id);
+                if !matches!(self.get_highest_processed_nonce(), Some(highest) if highest >= request_nonce)
+                {
+                    self.highest_processed_nonce.set(&request_nonce);
+                }
 
                 self.env().emit_event(RequestProcessed {
                     request_hash,
                     dest_token_address,
                 });
             } else {
+                // record vote
+                self.record_signature(request_hash, committee_id, caller);
                 self.pending_requests.insert(request_hash, &request);
             }
 
             Ok(())
         }
         /// Upgrades contract code
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
         #[ink(message)]
         pub fn set_code(&mut self, code_hash: [u8; 32]) -> Result<(), MostError> {
             self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_code(code_hash)
+        }
+
+        fn _set_code(&mut self, code_hash: [u8; 32]) -> Result<(), MostError> {
+            let previous_code_hash = self.env().own_code_hash()?;
             set_code_hash(&code_hash)?;
+
+            self.env().emit_event(CodeHashChanged {
+                previous_code_hash,
+                new_code_hash: code_hash.into(),
+                caller: self.env().caller(),
+            });
+
             Ok(())
         }
 
@@ -474,12 +1027,80 @@ pub mod most_l2 {
             self.flat_fee.get().ok_or(MostError::FlatFeeNotSet)
         }
 
+        /// Quotes an outgoing transfer of `amount` of `src_token` at the current flat fee
+        ///
+        /// Native AZERO sent with `send_request_native_azero` always goes to `NATIVE_MARKER_ADDRESS`, for the same fee and amount.
+        /// Fails with the error the request would fail with now, for an unsupported pair or an amount outside the limits.
+        #[ink(message)]
+        pub fn quote_transfer(
+            &self,
+            src_token: [u8; 32],
+            amount: u128,
+        ) -> Result<TransferQuote, MostError> {
+            let dest_token_address = self
+                .supported_pairs
+                .get(src_token)
+                .ok_or(MostError::UnsupportedPair)?;
+
+            if amount == 0 {
+                return Err(MostError::ZeroTransferAmount);
+            }
+            self.check_outflow(src_token, amount)?;
+
+            Ok(TransferQuote {
+                dest_token_address,
+                fee: self.get_flat_fee()?,
+                dest_amount: amount,
+                fee_valid_until: None,
+            })
+        }
+
         /// Query token pair
         #[ink(message)]
         pub fn get_supported_pair(&self, src_token: [u8; 32]) -> Option<[u8; 32]> {
             self.supported_pairs.get(src_token)
         }
 
+        /// Query transfer limits of a token, keyed by its address on Aleph Zero
+        #[ink(message)]
+        pub fn get_transfer_limits(&self, token: [u8; 32]) -> TransferLimits {
+            self.transfer_limits.get(token).unwrap_or_default()
+        }
+
+        /// Query the amount of the outgoing requests of a token that counts against its outflow cap
+        #[ink(message)]
+        pub fn get_outflow(&self, token: [u8; 32]) -> u128 {
+            let limits = self.get_transfer_limits(token);
+            self.outflows.get(token).unwrap_or_default().current(
+                limits.outflow_cap,
+                limits.window,
+                self.env().block_timestamp(),
+            )
+        }
+
+        /// Query the amount of the incoming requests of a token that counts against its inflow cap
+        #[ink(message)]
+        pub fn get_inflow(&self, token: [u8; 32]) -> u128 {
+            let limits = self.get_transfer_limits(token);
+            self.inflows.get(token).unwrap_or_default().current(
+                limits.inflow_cap,
+                limits.window,
+                self.env().block_timestamp(),
+            )
+        }
+
+        /// Query the source of the incoming requests, if the requests are bound to it
+        #[ink(message)]
+        pub fn get_request_domain(&self) -> Option<RequestDomain> {
+            self.request_domain.get()
+        }
+
+        /// Query the highest nonce of the incoming requests that reached the quorum
+        #[ink(message)]
+        pub fn get_highest_processed_nonce(&self) -> Option<u128> {
+            self.highest_processed_nonce.get()
+        }
+
         /// Query request nonce
         ///
         /// Nonce is incremented with every request
@@ -494,6 +1115,53 @@ pub mod most_l2 {
             Ok(self.data()?.committee_id)
         }
 
+        /// Returns the members of the committee with `committee_id`
+        #[ink(message)]
+        pub fn get_committee(
+            &self,
+            committee_id: CommitteeId,
+        ) -> Result<Vec<AccountId>, MostError> {
+            self.committee_members
+                .get(committee_id)
+                .ok_or(MostError::NoSuchCommittee)
+        }
+
+        /// Returns the number of signatures required to reach a quorum in the committee with `committee_id`
+        #[ink(message)]
+        pub fn get_signature_threshold(
+            &self,
+            committee_id: CommitteeId,
+        ) -> Result<u128, MostError> {
+            self.signature_thresholds
+                .get(committee_id)
+                .ok_or(MostError::NoSuchCommittee)
+        }
+
+        /// Returns the block at which the committee with `committee_id` became the active one
+        #[ink(message)]
+        pub fn get_committee_activation_block(
+            &self,
+            committee_id: CommitteeId,
+        ) -> Result<BlockNumber, MostError> {
+            self.committee_activations
+                .get(committee_id)
+                .ok_or(MostError::NoSuchCommittee)
+        }
+
+        /// Query the minimum time in milliseconds between scheduling and executing an owner operation
+        ///
+        /// 0 while the timelock is off, in which case the operations can be called directly
+        #[ink(message)]
+        pub fn get_operation_delay(&self) -> u64 {
+            self.operation_delay.get().unwrap_or_default()
+        }
+
+        /// Query a scheduled owner operation
+        #[ink(message)]
+        pub fn get_scheduled_operation(&self, operation_id: u128) -> Option<ScheduledOperation> {
+            self.scheduled_operations.get(operation_id)
+        }
+
         /// Returns whether an account is in the committee with `committee_id`
         #[ink(message)]
         pub fn is_in_committee(&self, committee_id: CommitteeId, account: AccountId) -> bool {
@@ -503,7 +1171,62 @@ pub mod most_l2 {
         /// Returns `true` if the account has signed the request with hash `request_hash`
         #[ink(message)]
         pub fn has_signed_request(&self, account: AccountId, request_hash: HashedRequest) -> bool {
-            self.signatures.contains((request_hash, account))
+            if self.signatures.contains((request_hash, account)) {
+                return true;
+            }
+
+            self.request_signers
+                .get(request_hash)
+                .and_then(|signers| {
+                    self.signer_bit(signers.committee_id, account)
+                        .map(|bit| signers.positions & bit != 0)
+                })
+                .unwrap_or(false)
+        }
+
+        /// Bit of `account` in the signers of a request of the committee with `committee_id`,
+        /// `None` if its position doesn't fit in the bitmap
+        fn signer_bit(&self, committee_id: CommitteeId, account: AccountId) -> Option<u128> {
+            let position = self.committee_positions.get((committee_id, account))?;
+            1u128.checked_shl(position)
+        }
+
+        /// Keeps a single bitmap per request, so the storage used doesn't grow with the committee size
+        fn record_signature(
+            &mut self,
+            request_hash: HashedRequest,
+            committee_id: CommitteeId,
+            signer: AccountId,
+        ) {
+            match self.signer_bit(committee_id, signer) {
+                Some(bit) => {
+                    let mut signers =
+                        self.request_signers
+                            .get(request_hash)
+                            .unwrap_or(RequestSigners {
+                                committee_id,
+                                positions: 0,
+                            });
+                    signers.positions |= bit;
+                    self.request_signers.insert(request_hash, &signers);
+                }
+                None => {
+                    self.signatures.insert((request_hash, signer), &());
+                }
+            }
+        }
+
+        /// Removes the signatures of a request that reached the quorum, they are not needed anymore
+        ///
+        /// The members of the committees set before their members were recorded can't be listed,
+        /// so their signatures stay.
+        fn clear_signatures(&mut self, request_hash: HashedRequest, committee_id: CommitteeId) {
+            self.request_signers.remove(request_hash);
+            for member in self.committee_members.get(committee_id).unwrap_or_default() {
+                if self.signer_bit(committee_id, member).is_none() {
+                    self.signatures.remove((request_hash, member));
+                }
+            }
         }
 
         /// Returns an error (reverts) if account is not in the currently active committee
@@ -569,47 +1292,139 @@ pub mod most_l2 {
 
         /// Sets flat fee to the new value
         ///
-        /// Can only be called by the contracts owner but can be called without halting the bridge.
+        /// Can only be called by the contracts owner or an account with the `FeeManager` role, but can be called without halting the bridge.
         #[ink(message)]
         pub fn set_flat_fee(&mut self, new_flat_fee: u128) -> Result<(), MostError> {
-            self.ensure_owner()?;
+            self.ensure_owner_or_role(Role::FeeManager)?;
 
+            let previous_flat_fee = self.flat_fee.get().unwrap_or_default();
             self.flat_fee.set(&new_flat_fee);
 
+            self.env().emit_event(FlatFeeChanged {
+                previous_flat_fee,
+                new_flat_fee,
+                caller: self.env().caller(),
+            });
+
             Ok(())
         }
 
         /// Removes a supported pair from bridging
         ///
         /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
         #[ink(message)]
         pub fn remove_pair(&mut self, from: [u8; 32]) -> Result<(), MostError> {
             self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._remove_pair(from)
+        }
+
+        fn _remove_pair(&mut self, from: [u8; 32]) -> Result<(), MostError> {
             self.ensure_halted()?;
+            let previous_to = self.supported_pairs.get(from);
             self.supported_pairs.remove(from);
+
+            self.env().emit_event(SupportedPairChanged {
+                from,
+                previous_to,
+                new_to: None,
+                caller: self.env().caller(),
+            });
+
             Ok(())
         }
 
         /// Adds a supported pair for bridging
         ///
         /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
         #[ink(message)]
         pub fn add_pair(&mut self, from: [u8; 32], to: [u8; 32]) -> Result<(), MostError> {
             self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._add_pair(from, to)
+        }
+
+        fn _add_pair(&mut self, from: [u8; 32], to: [u8; 32]) -> Result<(), MostError> {
             self.ensure_halted()?;
 
+            let previous_to = self.supported_pairs.get(from);
             self.supported_pairs.insert(from, &to);
+
+            self.env().emit_event(SupportedPairChanged {
+                from,
+                previous_to,
+                new_to: Some(to),
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Sets the transfer limits of a token, keyed by its address on Aleph Zero
+        ///
+        /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set, halt the bridge to stop the transfers right away
+        #[ink(message)]
+        pub fn set_transfer_limits(
+            &mut self,
+            token: [u8; 32],
+            limits: TransferLimits,
+        ) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_transfer_limits(token, limits)
+        }
+
+        fn _set_transfer_limits(
+            &mut self,
+            token: [u8; 32],
+            limits: TransferLimits,
+        ) -> Result<(), MostError> {
+            if !limits.is_valid() {
+                return Err(MostError::InvalidTransferLimits);
+            }
+
+            let previous_limits = self.transfer_limits.get(token).unwrap_or_default();
+            self.transfer_limits.insert(token, &limits);
+
+            self.env().emit_event(TransferLimitsChanged {
+                token,
+                previous_limits,
+                new_limits: limits,
+                caller: self.env().caller(),
+            });
+
             Ok(())
         }
 
         /// Set wazero(azero) psp22 token contract
         ///
         /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
         #[ink(message)]
         pub fn set_wazero(&mut self, wazero_address: AccountId) -> Result<(), MostError> {
             self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_wazero(wazero_address)
+        }
+
+        fn _set_wazero(&mut self, wazero_address: AccountId) -> Result<(), MostError> {
             self.ensure_halted()?;
+            let previous_address = self.wazero.get();
             self.wazero.set(&wazero_address);
+
+            self.env().emit_event(WazeroChanged {
+                previous_address,
+                new_address: wazero_address,
+                caller: self.env().caller(),
+            });
+
             Ok(())
         }
 
@@ -617,6 +1432,8 @@ pub mod most_l2 {
         /// Can only be called by the contracts owner
         ///
         /// Changing the entire set is the ONLY way of upgrading the committee
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
         #[ink(message)]
         pub fn set_committee(
             &mut self,
@@ -624,6 +1441,15 @@ pub mod most_l2 {
             signature_threshold: u128,
         ) -> Result<(), MostError> {
             self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_committee(committee, signature_threshold)
+        }
+
+        fn _set_committee(
+            &mut self,
+            committee: Vec<AccountId>,
+            signature_threshold: u128,
+        ) -> Result<(), MostError> {
             self.ensure_halted()?;
             Self::check_committee(&committee, signature_threshold)?;
 
@@ -633,8 +1459,8 @@ pub mod most_l2 {
             self.committee_sizes
                 .insert(committee_id, &(committee.len() as u128));
             let mut committee_set = Mapping::new();
-            committee.into_iter().for_each(|account| {
-                committee_set.insert((committee_id, account), &());
+            committee.iter().for_each(|account| {
+                committee_set.insert((committee_id, *account), &());
             });
 
             self.committees = committee_set;
@@ -645,15 +1471,38 @@ pub mod most_l2 {
             self.signature_thresholds
                 .insert(committee_id, &signature_threshold);
 
+            let activated_at = self.env().block_number();
+            self.committee_members.insert(committee_id, &committee);
+            committee
+                .iter()
+                .enumerate()
+                .for_each(|(position, account)| {
+                    self.committee_positions
+                        .insert((committee_id, *account), &(position as u32));
+                });
+            self.committee_activations
+                .insert(committee_id, &activated_at);
+
+            self.env().emit_event(CommitteeUpdated {
+                committee_id,
+                members: committee,
+                signature_threshold,
+                activated_at,
+            });
+
             Ok(())
         }
 
         /// Halt/resume the bridge contract
         ///
-        /// Can only be called by the contracts owner
+        /// Can only be called by the contracts owner, or by an account with the `Pauser` role to halt it
         #[ink(message)]
         pub fn set_halted(&mut self, new_state: bool) -> Result<(), MostError> {
-            self.ensure_owner()?;
+            if new_state {
+                self.ensure_owner_or_role(Role::Pauser)?;
+            } else {
+                self.ensure_owner()?;
+            }
 
             let mut data = self.data()?;
             let previous_state = data.is_halted;
@@ -672,9 +1521,44 @@ pub mod most_l2 {
             Ok(())
         }
 
+        /// Binds the hashes of the incoming requests from `domain.from_nonce` on to their source and to
+        /// this contract, so that they can't be replayed on another deployment
+        ///
+        /// Can only be called by the contracts owner, once, while the bridge is halted. The domain never
+        /// changes afterwards, as the requests around a change could be processed twice otherwise.
+        /// For the same reason `domain.from_nonce` has to be above the nonce of every request that
+        /// already reached the quorum. Only the requests processed since the contract tracks their
+        /// nonces are checked, see `get_highest_processed_nonce`.
+        #[ink(message)]
+        pub fn set_request_domain(&mut self, domain: RequestDomain) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_halted()?;
+            if self.request_domain.get().is_some() {
+                return Err(MostError::RequestDomainAlreadySet);
+            }
+            if matches!(self.get_highest_processed_nonce(), Some(highest) if domain.from_nonce <= highest)
+            {
+                return Err(MostError::NonceAlreadyProcessed);
+            }
+            if domain.src_contract == ZERO_ADDRESS {
+                return Err(MostError::ZeroAddress);
+            }
+
+            self.request_domain.set(&domain);
+
+            self.env().emit_event(RequestDomainSet {
+                domain,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
         /// Transfer PSP22 tokens from the bridge contract to a given account.
         ///
         /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
         #[ink(message)]
         pub fn recover_psp22(
             &mut self,
@@ -683,15 +1567,34 @@ pub mod most_l2 {
             amount: u128,
         ) -> Result<(), MostError> {
             self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._recover_psp22(token, receiver, amount)
+        }
+
+        fn _recover_psp22(
+            &mut self,
+            token: AccountId,
+            receiver: AccountId,
+            amount: u128,
+        ) -> Result<(), MostError> {
+            let mut psp22: ink::contract_ref!(PSP22) = token.into();
+            psp22.transfer(receiver, amount, vec![])?;
+
+            self.env().emit_event(FundsRecovered {
+                token: Some(token),
+                receiver,
+                amount,
+                caller: self.env().caller(),
+            });
 
-            let mut token: ink::contract_ref!(PSP22) = token.into();
-            token.transfer(receiver, amount, vec![])?;
             Ok(())
         }
 
         /// Transfer AZERO tokens from the bridge contract to a given account.
         ///
         /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
         #[ink(message)]
         pub fn recover_azero(
             &mut self,
@@ -699,13 +1602,184 @@ pub mod most_l2 {
             amount: u128,
         ) -> Result<(), MostError> {
             self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._recover_azero(receiver, amount)
+        }
 
+        fn _recover_azero(&mut self, receiver: AccountId, amount: u128) -> Result<(), MostError> {
             self.env().transfer(receiver, amount)?;
+
+            self.env().emit_event(FundsRecovered {
+                token: None,
+                receiver,
+                amount,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Turns the timelock on, after which the critical owner operations have to be scheduled
+        /// with `schedule_operation` and can only be executed after `delay` milliseconds
+        ///
+        /// Can only be called by the contracts owner. Once the timelock is on, the delay can only be
+        /// changed with a scheduled `SetOperationDelay` operation and never turned off
+        #[ink(message)]
+        pub fn set_operation_delay(&mut self, delay: u64) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_operation_delay(delay)
+        }
+
+        fn _set_operation_delay(&mut self, delay: u64) -> Result<(), MostError> {
+            if delay < MIN_OPERATION_DELAY {
+                return Err(MostError::InvalidOperationDelay);
+            }
+
+            let previous_delay = self.get_operation_delay();
+            self.operation_delay.set(&delay);
+
+            self.env().emit_event(OperationDelayChanged {
+                previous_delay,
+                new_delay: delay,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Schedules an owner operation, it can be executed with `execute_operation` once the operation delay passes
+        ///
+        /// Can only be called by the contracts owner
+        #[ink(message)]
+        pub fn schedule_operation(&mut self, operation: AdminOperation) -> Result<u128, MostError> {
+            self.ensure_owner()?;
+
+            let operation_id = self.operation_nonce.get().unwrap_or_default();
+            self.operation_nonce
+                .set(&operation_id.checked_add(1).ok_or(MostError::Arithmetic)?);
+
+            let executable_at = self
+                .env()
+                .block_timestamp()
+                .checked_add(self.get_operation_delay())
+                .ok_or(MostError::Arithmetic)?;
+            self.scheduled_operations.insert(
+                operation_id,
+                &ScheduledOperation {
+                    operation: operation.clone(),
+                    executable_at,
+                },
+            );
+
+            self.env().emit_event(OperationScheduled {
+                operation_id,
+                operation,
+                executable_at,
+            });
+
+            Ok(operation_id)
+        }
+
+        /// Cancels a scheduled owner operation
+        ///
+        /// Can only be called by the contracts owner
+        #[ink(message)]
+        pub fn cancel_operation(&mut self, operation_id: u128) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.scheduled_operations
+                .take(operation_id)
+                .ok_or(MostError::UnknownOperation)?;
+
+            self.env().emit_event(OperationCancelled {
+                operation_id,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Executes a scheduled owner operation whose delay has passed
+        ///
+        /// Can only be called by the contracts owner
+        #[ink(message)]
+        pub fn execute_operation(&mut self, operation_id: u128) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            let scheduled = self
+                .get_scheduled_operation(operation_id)
+                .ok_or(MostError::UnknownOperation)?;
+            if self.env().block_timestamp() < scheduled.executable_at {
+                return Err(MostError::DelayNotElapsed);
+            }
+            self.scheduled_operations.remove(operation_id);
+
+            match scheduled.operation {
+                AdminOperation::SetCode { code_hash } => self._set_code(code_hash),
+                AdminOperation::SetCommittee {
+                    committee,
+                    signature_threshold,
+                } => self._set_committee(committee, signature_threshold),
+                AdminOperation::AddPair { from, to } => self._add_pair(from, to),
+                AdminOperation::RecoverPsp22 {
+                    token,
+                    receiver,
+                    amount,
+                } => self._recover_psp22(token, receiver, amount),
+                AdminOperation::RecoverAzero { receiver, amount } => {
+                    self._recover_azero(receiver, amount)
+                }
+                AdminOperation::SetOperationDelay { delay } => self._set_operation_delay(delay),
+                AdminOperation::RemovePair { from } => self._remove_pair(from),
+                AdminOperation::SetTransferLimits { token, limits } => {
+                    self._set_transfer_limits(token, limits)
+                }
+                AdminOperation::SetWazero { wazero_address } => self._set_wazero(wazero_address),
+                AdminOperation::GrantRole { role, account } => {
+                    let owner = self.env().caller();
+                    Ok(self._grant_role(owner, owner, role, account)?)
+                }
+            }?;
+
+            self.env().emit_event(OperationExecuted {
+                operation_id,
+                caller: self.env().caller(),
+            });
+
             Ok(())
         }
 
         // ---  helper functions
 
+        fn _grant_role(
+            &mut self,
+            caller: AccountId,
+            admin: AccountId,
+            role: Role,
+            account: AccountId,
+        ) -> AccessControlResult<()> {
+            self.roles.grant_role(caller, admin, role, account)?;
+            self.env().emit_event(RoleGranted {
+                role,
+                account,
+                caller,
+            });
+            Ok(())
+        }
+
+        fn ensure_not_timelocked(&self) -> Result<(), MostError> {
+            match self.get_operation_delay() {
+                0 => Ok(()),
+                _ => Err(MostError::TimelockRequired),
+            }
+        }
+
+        fn ensure_owner_or_role(&self, role: Role) -> Result<(), MostError> {
+            if self.has_role(role, self.env().caller()) {
+                return Ok(());
+            }
+            Ok(self.ensure_owner()?)
+        }
+
         fn ensure_halted(&self) -> Result<(), MostError> {
             match self.is_halted()? {
                 true => Ok(()),
@@ -752,7 +1826,15 @@ pub mod most_l2 {
             let mut wrapped_azero: ink::contract_ref!(WrappedAZERO) = wrapped_azero_address.into();
 
             wrapped_azero.withdraw(amount)?;
-            self.env().transfer(to, amount)?;
+            if let Err(why) = self.env().transfer(to, amount) {
+                // wrap the AZERO back, the caller may not revert
+                wrapped_azero
+                    .call_mut()
+                    .deposit()
+                    .transferred_value(amount)
+                    .invoke()?;
+                return Err(why.into());
+            }
             Ok(())
         }
 
@@ -767,6 +1849,82 @@ pub mod most_l2 {
             psp22.transfer_from(from, self.env().account_id(), amount, vec![])
         }
 
+        /// Checks an outgoing request against the limits of the token and accounts for it
+        fn record_outflow(&mut self, token: [u8; 32], amount: u128) -> Result<(), MostError> {
+            if let Some(outflow) = self.check_outflow(token, amount)? {
+                self.outflows.insert(token, &outflow);
+            }
+
+            Ok(())
+        }
+
+        /// Checks an outgoing request against the limits of the token and returns the outflow
+        /// including it, `None` if the token has no outflow cap
+        fn check_outflow(&self, token: [u8; 32], amount: u128) -> Result<Option<Flow>, MostError> {
+            let limits = self.get_transfer_limits(token);
+            if amount < limits.min_amount {
+                return Err(MostError::AmountBelowMinimum);
+            }
+            if limits.max_amount > 0 && amount > limits.max_amount {
+                return Err(MostError::AmountAboveMaximum);
+            }
+
+            if limits.outflow_cap == 0 {
+                return Ok(None);
+            }
+
+            self.outflows
+                .get(token)
+                .unwrap_or_default()
+                .add(
+                    amount,
+                    limits.outflow_cap,
+                    limits.window,
+                    self.env().block_timestamp(),
+                )
+                .map(Some)
+                .ok_or(MostError::OutflowCapExceeded)
+        }
+
+        /// Whether an incoming request fits within the inflow cap of the token
+        fn inflow_fits(&self, token: [u8; 32], amount: u128) -> bool {
+            let limits = self.get_transfer_limits(token);
+            limits.inflow_cap == 0
+                || self
+                    .inflows
+                    .get(token)
+                    .unwrap_or_default()
+                    .add(
+                        amount,
+                        limits.inflow_cap,
+                        limits.window,
+                        self.env().block_timestamp(),
+                    )
+                    .is_some()
+        }
+
+        /// Checks an incoming request that reached the quorum against the inflow cap of the token
+        /// and accounts for it
+        fn record_inflow(&mut self, token: [u8; 32], amount: u128) -> Result<(), MostError> {
+            let limits = self.get_transfer_limits(token);
+            if limits.inflow_cap > 0 {
+                let inflow = self
+                    .inflows
+                    .get(token)
+                    .unwrap_or_default()
+                    .add(
+                        amount,
+                        limits.inflow_cap,
+                        limits.window,
+                        self.env().block_timestamp(),
+                    )
+                    .ok_or(MostError::InflowCapExceeded)?;
+                self.inflows.insert(token, &inflow);
+            }
+
+            Ok(())
+        }
+
         fn data(&self) -> Result<Data, MostError> {
             self.data.get().ok_or(MostError::CorruptedStorage)
         }
@@ -816,10 +1974,57 @@ pub mod most_l2 {
         }
     }
 
+    impl AccessControl for MostL2 {
+        #[ink(message)]
+        fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.roles.has_role(role, account)
+        }
+
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set, revoking a role stays instant
+        #[ink(message)]
+        fn grant_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            let admin = <Self as Ownable2Step>::get_owner(self)
+                .map_err(|_| AccessControlError::CallerNotAdmin(caller))?;
+            if caller == admin && self.get_operation_delay() > 0 {
+                return Err(AccessControlError::TimelockRequired);
+            }
+
+            self._grant_role(caller, admin, role, account)
+        }
+
+        #[ink(message)]
+        fn revoke_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            let admin = <Self as Ownable2Step>::get_owner(self)
+                .map_err(|_| AccessControlError::CallerNotAdmin(caller))?;
+
+            self.roles.revoke_role(caller, admin, role, account)?;
+            self.env().emit_event(RoleRevoked {
+                role,
+                account,
+                caller,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn renounce_role(&mut self, role: Role) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            self.roles.renounce_role(caller, role)?;
+            self.env().emit_event(RoleRevoked {
+                role,
+                account: caller,
+                caller,
+            });
+            Ok(())
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use ink::env::{
-            test::{default_accounts, set_caller},
+            test::{self, default_accounts, set_caller},
             DefaultEnvironment, Environment,
         };
 
@@ -957,5 +2162,541 @@ pub mod most_l2 {
             assert_eq!(most.committee_sizes.get(1), Some(1));
             assert!(!most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.bob));
         }
+
+        #[ink::test]
+        fn set_committee_keeps_committee_history() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+
+            ink::env::test::advance_block::<DefEnv>();
+            ink::env::test::advance_block::<DefEnv>();
+            let events_before = ink::env::test::recorded_events().count();
+            assert_eq!(
+                most.set_committee(vec![accounts.bob, accounts.alice], 2),
+                Ok(())
+            );
+            assert_eq!(ink::env::test::recorded_events().count(), events_before + 1);
+
+            assert_eq!(most.get_committee(0), Ok(guardian_accounts()));
+            assert_eq!(most.get_signature_threshold(0), Ok(THRESHOLD));
+            assert_eq!(most.get_committee_activation_block(0), Ok(0));
+            assert_eq!(
+                most.get_committee(1),
+                Ok(vec![accounts.bob, accounts.alice])
+            );
+            assert_eq!(most.get_signature_threshold(1), Ok(2));
+            assert_eq!(most.get_committee_activation_block(1), Ok(2));
+            assert_eq!(most.get_committee(2), Err(MostError::NoSuchCommittee));
+        }
+
+        const TOKEN: [u8; 32] = [7; 32];
+        const RECEIVER: [u8; 32] = [8; 32];
+        const INFLOW_CAP: u128 = 100;
+
+        fn limits() -> TransferLimits {
+            TransferLimits {
+                min_amount: 10,
+                max_amount: 100,
+                outflow_cap: 0,
+                inflow_cap: INFLOW_CAP,
+                window: 1000,
+            }
+        }
+
+        #[ink::test]
+        fn set_transfer_limits_works() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+
+            assert_eq!(most.set_transfer_limits(TOKEN, limits()), Ok(()));
+            assert_eq!(most.get_transfer_limits(TOKEN), limits());
+            assert_eq!(
+                most.set_transfer_limits(
+                    TOKEN,
+                    TransferLimits {
+                        window: 0,
+                        ..limits()
+                    }
+                ),
+                Err(MostError::InvalidTransferLimits)
+            );
+
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(
+                most.set_transfer_limits(TOKEN, TransferLimits::default()),
+                Err(MostError::Ownable(Ownable2StepError::CallerNotOwner(
+                    accounts.bob
+                )))
+            );
+        }
+
+        #[ink::test]
+        fn receive_request_enforces_inflow_cap() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+            assert_eq!(most.set_transfer_limits(TOKEN, limits()), Ok(()));
+            assert_eq!(most.set_halted(false), Ok(()));
+
+            let amount = INFLOW_CAP + 1;
+            let request_hash = hash_request_data(0, TOKEN.into(), amount, RECEIVER.into(), 0);
+            let guardians = guardian_accounts();
+            for guardian in &guardians[..THRESHOLD as usize - 1] {
+                set_caller::<DefEnv>(*guardian);
+                assert_eq!(
+                    most.receive_request(request_hash, 0, TOKEN, amount, RECEIVER, 0),
+                    Ok(())
+                );
+            }
+
+            set_caller::<DefEnv>(guardians[THRESHOLD as usize - 1]);
+            assert_eq!(
+                most.receive_request(request_hash, 0, TOKEN, amount, RECEIVER, 0),
+                Err(MostError::InflowCapExceeded)
+            );
+            assert_eq!(most.get_inflow(TOKEN), 0);
+        }
+
+        #[ink::test]
+        fn signers_are_kept_in_a_bitmap_until_the_quorum() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+            assert_eq!(most.set_halted(false), Ok(()));
+            let guardians = guardian_accounts();
+            // a member without a recorded position signs in `signatures`
+            most.committee_positions.remove((0, guardians[0]));
+
+            let request_hash = hash_request_data(0, TOKEN.into(), 10, RECEIVER.into(), 0);
+            for guardian in &guardians[..THRESHOLD as usize - 1] {
+                set_caller::<DefEnv>(*guardian);
+                assert_eq!(
+                    most.receive_request(request_hash, 0, TOKEN, 10, RECEIVER, 0),
+                    Ok(())
+                );
+            }
+
+            for (position, guardian) in guardians.iter().enumerate() {
+                let signed = position < THRESHOLD as usize - 1;
+                assert_eq!(most.has_signed_request(*guardian, request_hash), signed);
+                assert_eq!(most.needs_signature(request_hash, *guardian, 0), !signed);
+                assert_eq!(
+                    most.signatures.contains((request_hash, *guardian)),
+                    position == 0
+                );
+            }
+            assert_eq!(
+                most.request_signers.get(request_hash),
+                Some(RequestSigners {
+                    committee_id: 0,
+                    positions: (1 << (THRESHOLD - 1)) - 2,
+                })
+            );
+
+            // executing the request calls the token, so the quorum is left out
+            most.clear_signatures(request_hash, 0);
+            assert_eq!(most.request_signers.get(request_hash), None);
+            for guardian in &guardians {
+                assert!(!most.signatures.contains((request_hash, *guardian)));
+            }
+
+            // signatures recorded before the bitmaps are still honored
+            let legacy_hash = hash_request_data(0, TOKEN.into(), 10, RECEIVER.into(), 1);
+            most.signatures.insert((legacy_hash, guardians[0]), &());
+            assert!(most.has_signed_request(guardians[0], legacy_hash));
+            assert!(!most.needs_signature(legacy_hash, guardians[0], 0));
+            assert!(most.needs_signature(legacy_hash, guardians[1], 0));
+        }
+
+        #[ink::test]
+        fn request_domain_binds_hashes_from_its_nonce_on() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+            let domain = RequestDomain {
+                src_chain_id: 1,
+                src_contract: [3; 32],
+                from_nonce: 5,
+            };
+            assert_eq!(most.set_request_domain(domain), Ok(()));
+            assert_eq!(
+                most.set_request_domain(domain),
+                Err(MostError::RequestDomainAlreadySet)
+            );
+            assert_eq!(most.set_halted(false), Ok(()));
+            assert_eq!(most.get_request_domain(), Some(domain));
+
+            let separator =
+                request_domain_separator(1, [3; 32], *test::callee::<DefEnv>().as_ref());
+            set_caller::<DefEnv>(guardian_accounts()[0]);
+            for nonce in [4, 5] {
+                let legacy_hash = hash_request_data(0, TOKEN.into(), 10, RECEIVER.into(), nonce);
+                let v1_hash =
+                    hash_request_data_v1(separator, 0, TOKEN.into(), 10, RECEIVER.into(), nonce);
+                let (valid, invalid) = match nonce < domain.from_nonce {
+                    true => (legacy_hash, v1_hash),
+                    false => (v1_hash, legacy_hash),
+                };
+                assert_eq!(
+                    most.receive_request(invalid, 0, TOKEN, 10, RECEIVER, nonce),
+                    Err(MostError::HashDoesNotMatchData)
+                );
+                assert_eq!(
+                    most.receive_request(valid, 0, TOKEN, 10, RECEIVER, nonce),
+                    Ok(())
+                );
+            }
+        }
+
+        #[ink::test]
+        fn request_domain_starts_above_processed_nonces() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+            // executing a request calls the token, which the off-chain environment can't
+            most.highest_processed_nonce.set(&3);
+
+            let domain = RequestDomain {
+                src_chain_id: 1,
+                src_contract: [3; 32],
+                from_nonce: 3,
+            };
+            assert_eq!(
+                most.set_request_domain(domain),
+                Err(MostError::NonceAlreadyProcessed)
+            );
+            assert_eq!(
+                most.set_request_domain(RequestDomain {
+                    from_nonce: 4,
+                    ..domain
+                }),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn quote_transfer_matches_send_request() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+            assert_eq!(most.add_pair(TOKEN, [9; 32]), Ok(()));
+            assert_eq!(most.set_halted(false), Ok(()));
+            let fee = most.get_flat_fee().expect("Flat fee is set.");
+
+            assert_eq!(
+                most.quote_transfer(TOKEN, 10),
+                Ok(TransferQuote {
+                    dest_token_address: [9; 32],
+                    fee,
+                    dest_amount: 10,
+                    fee_valid_until: None,
+                })
+            );
+            assert_eq!(
+                most.quote_transfer([5; 32], 10),
+                Err(MostError::UnsupportedPair)
+            );
+            assert_eq!(
+                most.quote_transfer(TOKEN, 0),
+                Err(MostError::ZeroTransferAmount)
+            );
+
+            assert_eq!(
+                most.set_transfer_limits(
+                    TOKEN,
+                    TransferLimits {
+                        outflow_cap: 50,
+                        ..limits()
+                    }
+                ),
+                Ok(())
+            );
+            for (amount, error) in [
+                (9, MostError::AmountBelowMinimum),
+                (101, MostError::AmountAboveMaximum),
+                (51, MostError::OutflowCapExceeded),
+            ] {
+                assert_eq!(most.quote_transfer(TOKEN, amount), Err(error));
+            }
+            assert_eq!(most.record_outflow(TOKEN, 40), Ok(()));
+            assert_eq!(
+                most.quote_transfer(TOKEN, 11),
+                Err(MostError::OutflowCapExceeded)
+            );
+            assert!(most.quote_transfer(TOKEN, 10).is_ok());
+            assert_eq!(
+                most.send_request(TOKEN, 10, RECEIVER, Some(fee - 1)),
+                Err(MostError::FeeAboveMax)
+            );
+        }
+
+        #[ink::test]
+        fn receive_requests_returns_result_per_request() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+            assert_eq!(most.set_transfer_limits(TOKEN, limits()), Ok(()));
+            assert_eq!(most.set_halted(false), Ok(()));
+
+            let amount = INFLOW_CAP + 1;
+            let request = RequestData {
+                request_hash: hash_request_data(0, TOKEN.into(), amount, RECEIVER.into(), 0),
+                committee_id: 0,
+                dest_token_address: TOKEN,
+                amount,
+                dest_receiver_address: RECEIVER,
+                request_nonce: 0,
+            };
+            let forged = RequestData {
+                amount: amount - 1,
+                ..request
+            };
+
+            let guardians = guardian_accounts();
+            for guardian in &guardians[..THRESHOLD as usize - 1] {
+                set_caller::<DefEnv>(*guardian);
+                assert_eq!(
+                    most.receive_requests(vec![request, forged, request]),
+                    Ok(vec![Ok(()), Err(MostError::HashDoesNotMatchData), Ok(())])
+                );
+            }
+            assert!(matches!(
+                most.request_status(request.request_hash),
+                RequestStatus::Pending {
+                    collected_signatures
+                } if collected_signatures == THRESHOLD as u32 - 1
+            ));
+
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(
+                most.receive_requests(vec![request]),
+                Ok(vec![Err(MostError::NotInCommittee)])
+            );
+
+            // the execution fails at the quorum without reverting the batch or recording the vote
+            let last_guardian = guardians[THRESHOLD as usize - 1];
+            set_caller::<DefEnv>(last_guardian);
+            assert_eq!(
+                most.receive_requests(vec![forged, request]),
+                Ok(vec![
+                    Err(MostError::HashDoesNotMatchData),
+                    Err(MostError::InflowCapExceeded)
+                ])
+            );
+            assert!(matches!(
+                most.request_status(request.request_hash),
+                RequestStatus::Pending {
+                    collected_signatures
+                } if collected_signatures == THRESHOLD as u32 - 1
+            ));
+            assert!(most.needs_signature(request.request_hash, last_guardian, 0));
+            assert_eq!(most.get_inflow(TOKEN), 0);
+        }
+
+        type Event = <MostL2 as ink::reflect::ContractEventBase>::Type;
+
+        fn last_event() -> Event {
+            let event = ink::env::test::recorded_events()
+                .last()
+                .expect("An event was emitted.");
+            <Event as scale::Decode>::decode(&mut &event.data[..]).expect("Event decodes.")
+        }
+
+        #[ink::test]
+        fn admin_setters_emit_old_and_new_values() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+
+            assert_eq!(most.set_flat_fee(5), Ok(()));
+            match last_event() {
+                Event::FlatFeeChanged(event) => assert_eq!(
+                    event,
+                    FlatFeeChanged {
+                        previous_flat_fee: DEFAULT_FLAT_FEE,
+                        new_flat_fee: 5,
+                        caller: accounts.alice,
+                    }
+                ),
+                _ => panic!("Expected FlatFeeChanged"),
+            }
+
+            assert_eq!(most.add_pair(TOKEN, RECEIVER), Ok(()));
+            match last_event() {
+                Event::SupportedPairChanged(event) => assert_eq!(
+                    event,
+                    SupportedPairChanged {
+                        from: TOKEN,
+                        previous_to: None,
+                        new_to: Some(RECEIVER),
+                        caller: accounts.alice,
+                    }
+                ),
+                _ => panic!("Expected SupportedPairChanged"),
+            }
+        }
+
+        #[ink::test]
+        fn timelock_delays_owner_operations() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+
+            assert_eq!(
+                most.set_operation_delay(MIN_OPERATION_DELAY - 1),
+                Err(MostError::InvalidOperationDelay)
+            );
+            assert_eq!(most.set_operation_delay(MIN_OPERATION_DELAY), Ok(()));
+            assert_eq!(
+                most.set_operation_delay(2 * MIN_OPERATION_DELAY),
+                Err(MostError::TimelockRequired)
+            );
+            assert_eq!(
+                most.add_pair([10; 32], [11; 32]),
+                Err(MostError::TimelockRequired)
+            );
+
+            let operation = AdminOperation::AddPair {
+                from: [10; 32],
+                to: [11; 32],
+            };
+            assert_eq!(most.schedule_operation(operation.clone()), Ok(0));
+            assert_eq!(
+                most.get_scheduled_operation(0),
+                Some(ScheduledOperation {
+                    operation,
+                    executable_at: MIN_OPERATION_DELAY,
+                })
+            );
+            assert_eq!(most.execute_operation(0), Err(MostError::DelayNotElapsed));
+
+            ink::env::test::set_block_timestamp::<DefEnv>(MIN_OPERATION_DELAY);
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(
+                most.execute_operation(0),
+                Err(MostError::Ownable(Ownable2StepError::CallerNotOwner(
+                    accounts.bob
+                )))
+            );
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(most.execute_operation(0), Ok(()));
+            assert_eq!(most.supported_pairs.get([10; 32]), Some([11; 32]));
+            assert_eq!(most.execute_operation(0), Err(MostError::UnknownOperation));
+
+            assert_eq!(
+                most.schedule_operation(AdminOperation::SetOperationDelay {
+                    delay: 2 * MIN_OPERATION_DELAY
+                }),
+                Ok(1)
+            );
+            assert_eq!(most.cancel_operation(1), Ok(()));
+            assert_eq!(most.get_scheduled_operation(1), None);
+            assert_eq!(most.execute_operation(1), Err(MostError::UnknownOperation));
+            assert_eq!(most.get_operation_delay(), MIN_OPERATION_DELAY);
+        }
+
+        #[ink::test]
+        fn timelock_covers_the_critical_setters() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+            assert_eq!(most.add_pair(TOKEN, [9; 32]), Ok(()));
+            assert_eq!(most.grant_role(Role::Pauser, accounts.bob), Ok(()));
+            assert_eq!(most.set_operation_delay(MIN_OPERATION_DELAY), Ok(()));
+
+            for result in [
+                most.remove_pair(TOKEN),
+                most.set_transfer_limits(TOKEN, limits()),
+                most.set_wazero(accounts.eve),
+            ] {
+                assert_eq!(result, Err(MostError::TimelockRequired));
+            }
+            assert_eq!(
+                most.grant_role(Role::FeeManager, accounts.charlie),
+                Err(AccessControlError::TimelockRequired)
+            );
+
+            // taking power away stays instant
+            assert_eq!(most.revoke_role(Role::Pauser, accounts.bob), Ok(()));
+
+            for operation in [
+                AdminOperation::SetTransferLimits {
+                    token: TOKEN,
+                    limits: limits(),
+                },
+                AdminOperation::GrantRole {
+                    role: Role::FeeManager,
+                    account: accounts.charlie,
+                },
+                AdminOperation::RemovePair { from: TOKEN },
+            ] {
+                assert!(most.schedule_operation(operation).is_ok());
+            }
+            ink::env::test::set_block_timestamp::<DefEnv>(MIN_OPERATION_DELAY);
+            for operation_id in 0..3 {
+                assert_eq!(most.execute_operation(operation_id), Ok(()));
+            }
+            assert_eq!(most.get_transfer_limits(TOKEN), limits());
+            assert!(most.has_role(Role::FeeManager, accounts.charlie));
+            assert_eq!(most.get_supported_pair(TOKEN), None);
+        }
+
+        #[ink::test]
+        fn roles_delegate_halting_and_fees() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = MostL2::new(guardian_accounts(), THRESHOLD, accounts.alice)
+                .expect("Threshold is valid.");
+            assert_eq!(most.set_halted(false), Ok(()));
+
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(
+                most.grant_role(Role::Pauser, accounts.bob),
+                Err(AccessControlError::CallerNotAdmin(accounts.bob))
+            );
+            assert!(most.set_halted(true).is_err());
+
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(most.grant_role(Role::Pauser, accounts.bob), Ok(()));
+            assert_eq!(most.grant_role(Role::FeeManager, accounts.charlie), Ok(()));
+            assert!(most.has_role(Role::Pauser, accounts.bob));
+            assert!(!most.has_role(Role::FeeManager, accounts.bob));
+
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(most.set_halted(true), Ok(()));
+            assert!(most.set_halted(false).is_err());
+            assert!(most.set_flat_fee(5).is_err());
+
+            set_caller::<DefEnv>(accounts.charlie);
+            assert_eq!(most.set_flat_fee(5), Ok(()));
+            assert!(most.set_halted(true).is_err());
+            assert_eq!(most.renounce_role(Role::FeeManager), Ok(()));
+            assert!(most.set_flat_fee(5).is_err());
+            assert_eq!(
+                most.renounce_role(Role::FeeManager),
+                Err(AccessControlError::MissingRole(
+                    accounts.charlie,
+                    Role::FeeManager
+                ))
+            );
+
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(most.revoke_role(Role::Pauser, accounts.bob), Ok(()));
+            assert!(!most.has_role(Role::Pauser, accounts.bob));
+            assert_eq!(most.set_halted(false), Ok(()));
+        }
     }
 }
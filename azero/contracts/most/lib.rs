@@ -1,16 +1,23 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub use access_control::{AccessControlError, Role};
 pub use ownable2step::Ownable2StepError;
 
-pub use self::most::{MostError, MostRef};
+pub use self::most::{
+    AdminOperation, MostError, MostRef, PairPause, RequestData, RequestDomain, ScheduledOperation,
+    TransferLimits, TransferQuote,
+};
 
 #[ink::contract]
 pub mod most {
+    use access_control::{
+        AccessControl, AccessControlData, AccessControlError, AccessControlResult, Role,
+    };
     use gas_oracle_trait::EthGasPriceOracle;
     use ink::{
         codegen::TraitCallBuilder,
         contract_ref,
-        env::{set_code_hash, Error as InkEnvError},
+        env::{hash::Keccak256, set_code_hash, Error as InkEnvError},
         prelude::{format, string::String, vec, vec::Vec},
         storage::{traits::ManualKey, Lazy, Mapping},
     };
@@ -18,9 +25,14 @@ pub mod most {
     use psp22::{PSP22Error, PSP22};
     use psp22_traits::{Burnable, Mintable, WrappedAZERO};
     use scale::{Decode, Encode};
-    use shared::{hash_request_data, Keccak256HashOutput as HashedRequest};
+    use shared::{
+        hash_request_data, hash_request_data_v1, request_domain_separator,
+        Keccak256HashOutput as HashedRequest,
+    };
 
     type CommitteeId = u128;
+    /// refund vote of a committee member, per nonce of the refunded request
+    type RefundVote = (CommitteeId, u128, AccountId);
 
     const ZERO_ADDRESS: [u8; 32] = [0; 32];
     const NATIVE_MARKER_ADDRESS: [u8; 32] = [0; 32];
@@ -28,6 +40,9 @@ pub mod most {
     /// ratio between wazero decimals on l1 and azero-erc20 on eth. 12 vs 18 decimals
     const RATIO: u128 = 1_000_000;
 
+    /// minimum delay in milliseconds of the scheduled owner operations, once the timelock is on
+    const MIN_OPERATION_DELAY: u64 = 24 * 60 * 60 * 1000;
+
     #[ink(event)]
     #[derive(Debug)]
     #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
@@ -112,1568 +127,4651 @@ pub mod most {
         pub account: AccountId,
     }
 
-    #[derive(Default, Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
-    #[cfg_attr(
-        feature = "std",
-        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
-    )]
-    pub struct Request {
-        signature_count: u128,
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RequestDelayed {
+        pub request_hash: HashedRequest,
+        #[ink(topic)]
+        pub dest_token_address: [u8; 32],
+        pub executable_at: u64,
     }
 
-    #[derive(Debug, Encode, Decode, Clone, Copy)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub enum RequestStatus {
-        Pending { collected_signatures: u32 },
-        Processed,
-        RequestHashNotKnown,
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct DelayedRequestCancelled {
+        pub request_hash: HashedRequest,
+        #[ink(topic)]
+        pub caller: AccountId,
     }
 
+    #[ink(event)]
     #[derive(Debug)]
-    #[ink::storage_item]
-    pub struct Data {
-        /// nonce for outgoing cross-chain transfer requests
-        request_nonce: u128,
-        /// accounting helper
-        committee_id: CommitteeId,
-        /// max value of a subsidy transferred along with the bridged tokens to the destination account on aleph zero to bootstrap
-        max_pocket_money: u128,
-        /// balance that can be paid out as pocket money
-        pocket_money_balance: u128,
-        /// How much gas does a single confirmation of a cross-chain transfer request use on the destination chain on average.
-        /// This value is calculated by summing the total gas usage of *all* the transactions it takes to relay a single request and dividing it by the current committee size and multiplying by 1.2
-        relay_gas_usage: u128,
-        /// minimum gas price used to calculate the fee that can be charged for a cross-chain transfer request
-        min_gas_price: u128,
-        /// maximum gas price used to calculate the fee that can be charged for a cross-chain transfer request
-        max_gas_price: u128,
-        /// default gas price used to calculate the fee that is charged for a cross-chain transfer request if the gas price oracle is not available/malfunctioning
-        default_gas_price: u128,
-        /// maximum time in milliseconds since last oracle update
-        gas_oracle_max_age: u64,
-        /// maximum limit on gas for a call to the oracle
-        oracle_call_gas_limit: u64,
-        /// percentage buffer over the estimated cost
-        base_fee_buffer_percentage: u128,
-        /// gas price oracle that is used to calculate the fee for a cross-chain transfer request
-        gas_price_oracle: Option<AccountId>,
-        /// Is the bridge in a halted state
-        is_halted: bool,
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RefundSigned {
+        pub request_nonce: u128,
+        #[ink(topic)]
+        pub signer: AccountId,
     }
 
-    #[ink(storage)]
-    pub struct Most {
-        data: Lazy<Data, ManualKey<0x44415441>>,
-        /// stores the data used by the Ownable2Step trait
-        ownable_data: Lazy<Ownable2StepData, ManualKey<0xDEADBEEF>>,
-        /// requests that are still collecting signatures
-        pending_requests: Mapping<HashedRequest, Request, ManualKey<0x50454E44>>,
-        /// signatures per cross chain transfer request
-        signatures: Mapping<(HashedRequest, AccountId), (), ManualKey<0x5349474E>>,
-        /// signed & executed requests, a replay protection
-        processed_requests: Mapping<HashedRequest, (), ManualKey<0x50524F43>>,
-        /// set of guardian accounts that can sign requests
-        committees: Mapping<(CommitteeId, AccountId), (), ManualKey<0x434F4D4D>>,
-        /// accounting helper
-        committee_sizes: Mapping<CommitteeId, u128, ManualKey<0x53495A45>>,
-        /// number of signatures required to reach a quorum and costsxecute a transfer
-        signature_thresholds: Mapping<CommitteeId, u128, ManualKey<0x54485245>>,
-        /// source - destination token pairs that can be transferred across the bridge
-        supported_pairs: Mapping<[u8; 32], [u8; 32], ManualKey<0x53555050>>,
-        /// rewards collected by the commitee for relaying cross-chain transfer requests
-        collected_committee_rewards: Mapping<CommitteeId, u128, ManualKey<0x434F4C4C>>,
-        /// rewards collected by the individual commitee members for relaying cross-chain transfer requests
-        paid_out_member_rewards: Mapping<(AccountId, CommitteeId), u128, ManualKey<0x50414944>>,
-        /// committe members can specify a special account for collecting the rewards, different from the one used for signing
-        payout_accounts: Mapping<AccountId, AccountId, ManualKey<0x18f97340>>,
-        /// Wrapped ethereum (azero) address. Necessary to perform bridging weth(azero) -> ether(eth).
-        weth: Lazy<AccountId, ManualKey<0x7CD95FED>>,
-        /// Mapping holding the information wheter a specific token originates on Aleph Zero chain
-        local_token: Mapping<AccountId, (), ManualKey<0x6c6f6361>>,
-        /// Wrapped AZERO address
-        wazero: Lazy<AccountId, ManualKey<0x77617a65>>,
-        /// How much gas does user use when requesting a bridging transfer.
-        eth_transfer_gas_usage: Lazy<u128, ManualKey<0x6574685f>>,
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct OutgoingRequestRecorded {
+        pub request_nonce: u128,
+        pub request: OutgoingRequest,
     }
 
-    #[derive(Debug, PartialEq, Eq, Encode, Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub enum MostError {
-        Constructor,
-        InvalidThreshold,
-        DuplicateCommitteeMember,
-        ZeroTransferAmount,
-        NotInCommittee,
-        NoSuchCommittee,
-        HashDoesNotMatchData,
-        PSP22(PSP22Error),
-        Ownable(Ownable2StepError),
-        UnsupportedPair,
-        InkEnvError(String),
-        RequestAlreadySigned,
-        BaseFeeTooLow,
-        Arithmetic,
-        CorruptedStorage,
-        IsHalted,
-        HaltRequired,
-        NoMintPermission,
-        ZeroAddress,
-        WrappedEthNotSet,
-        WrappedAzeroNotSet,
-        ValueTransferredLowerThanAmount,
-        Other,
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RequestRefunded {
+        pub request_nonce: u128,
+        #[ink(topic)]
+        pub sender: AccountId,
+        pub amount: u128,
     }
 
-    impl From<InkEnvError> for MostError {
-        fn from(why: InkEnvError) -> Self {
-            Self::InkEnvError(format!("{:?}", why))
-        }
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct CommitteeUpdated {
+        #[ink(topic)]
+        pub committee_id: CommitteeId,
+        pub members: Vec<AccountId>,
+        pub signature_threshold: u128,
+        pub activated_at: BlockNumber,
     }
 
-    impl From<PSP22Error> for MostError {
-        fn from(inner: PSP22Error) -> Self {
-            MostError::PSP22(inner)
-        }
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct MaxPocketMoneyChanged {
+        pub previous_max_pocket_money: u128,
+        pub new_max_pocket_money: u128,
+        #[ink(topic)]
+        pub caller: AccountId,
     }
 
-    impl From<Ownable2StepError> for MostError {
-        fn from(inner: Ownable2StepError) -> Self {
-            MostError::Ownable(inner)
-        }
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct CodeHashChanged {
+        pub previous_code_hash: Hash,
+        pub new_code_hash: Hash,
+        #[ink(topic)]
+        pub caller: AccountId,
     }
 
-    impl Most {
-        #[allow(clippy::too_many_arguments)]
-        #[ink(constructor)]
-        pub fn new(
-            committee: Vec<AccountId>,
-            signature_threshold: u128,
-            max_pocket_money: Balance,
-            relay_gas_usage: u128,
-            min_gas_price: u128,
-            max_gas_price: u128,
-            default_gas_price: u128,
-            gas_oracle_max_age: u64,
-            oracle_call_gas_limit: u64,
-            base_fee_buffer_percentage: u128,
-            gas_price_oracle: Option<AccountId>,
-            owner: AccountId,
-            initial_eth_transfer_gas_usage: u128,
-        ) -> Result<Self, MostError> {
-            Self::check_committee(&committee, signature_threshold)?;
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct BaseFeeConstraintsChanged {
+        pub previous_constraints: BaseFeeConstraints,
+        pub new_constraints: BaseFeeConstraints,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            let committee_id = 0;
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct SupportedPairChanged {
+        #[ink(topic)]
+        pub from: [u8; 32],
+        pub previous_to: Option<[u8; 32]>,
+        pub new_to: Option<[u8; 32]>,
+        pub local_token: bool,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            let mut committees = Mapping::new();
-            committee.clone().into_iter().for_each(|account| {
-                committees.insert((committee_id, account), &());
-            });
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct TransferLimitsChanged {
+        #[ink(topic)]
+        pub token: [u8; 32],
+        pub previous_limits: TransferLimits,
+        pub new_limits: TransferLimits,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            let mut committee_sizes = Mapping::new();
-            committee_sizes.insert(committee_id, &(committee.len() as u128));
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct LargeTransferThresholdChanged {
+        #[ink(topic)]
+        pub token: [u8; 32],
+        pub previous_threshold: u128,
+        pub new_threshold: u128,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            let mut signature_thresholds = Mapping::new();
-            signature_thresholds.insert(committee_id, &signature_threshold);
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct LargeTransferDelayChanged {
+        pub previous_delay: u64,
+        pub new_delay: u64,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            let mut data = Lazy::new();
-            data.set(&Data {
-                request_nonce: 0,
-                committee_id,
-                max_pocket_money,
-                pocket_money_balance: 0,
-                relay_gas_usage,
-                min_gas_price,
-                max_gas_price,
-                default_gas_price,
-                gas_oracle_max_age,
-                oracle_call_gas_limit,
-                base_fee_buffer_percentage,
-                gas_price_oracle,
-                is_halted: true,
-            });
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct AdvisoryChanged {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub previous_state: bool,
+        pub new_state: bool,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            let mut ownable_data = Lazy::new();
-            ownable_data.set(&Ownable2StepData::new(owner));
-            let weth = Lazy::new();
-            let wazero = Lazy::new();
-            let mut eth_transfer_gas_usage = Lazy::new();
-            eth_transfer_gas_usage.set(&initial_eth_transfer_gas_usage);
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct WethChanged {
+        pub previous_address: Option<AccountId>,
+        pub new_address: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            Ok(Self {
-                data,
-                ownable_data,
-                signature_thresholds,
-                committees,
-                committee_sizes,
-                pending_requests: Mapping::new(),
-                signatures: Mapping::new(),
-                processed_requests: Mapping::new(),
-                supported_pairs: Mapping::new(),
-                collected_committee_rewards: Mapping::new(),
-                paid_out_member_rewards: Mapping::new(),
-                payout_accounts: Mapping::new(),
-                weth,
-                local_token: Mapping::new(),
-                wazero,
-                eth_transfer_gas_usage,
-            })
-        }
-
-        // --- business logic
-        fn _send_request(
-            &mut self,
-            src_token_address: AccountId,
-            dest_token_address: [u8; 32],
-            amount: u128,
-            dest_receiver_address: [u8; 32],
-            native_azero_request: bool,
-            transferred_fee: u128,
-        ) -> Result<(), MostError> {
-            if dest_receiver_address == ZERO_ADDRESS {
-                return Err(MostError::ZeroAddress);
-            }
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct WazeroChanged {
+        pub previous_address: Option<AccountId>,
+        pub new_address: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            if amount == 0 {
-                return Err(MostError::ZeroTransferAmount);
-            }
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct GasPriceOracleChanged {
+        pub previous_oracle: Option<AccountId>,
+        pub new_oracle: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            let current_base_fee = self.get_base_fee()?;
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct EthTransferGasUsageChanged {
+        pub previous_gas_usage: u128,
+        pub new_gas_usage: u128,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            if transferred_fee.lt(&current_base_fee) {
-                return Err(MostError::BaseFeeTooLow);
-            }
+    /// Emitted when funds are recovered from the contract, `token` is `None` for AZERO
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct FundsRecovered {
+        pub token: Option<AccountId>,
+        #[ink(topic)]
+        pub receiver: AccountId,
+        pub amount: u128,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            let sender = self.env().caller();
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct OperationScheduled {
+        #[ink(topic)]
+        pub operation_id: u128,
+        pub operation: AdminOperation,
+        pub executable_at: u64,
+    }
 
-            if self.local_token.get(src_token_address).is_none() {
-                self.burn_from(src_token_address, sender, amount)?;
-            } else if !native_azero_request {
-                // lock the tokens in the contract
-                self.transfer_from(src_token_address, sender, amount)?;
-            } // if the transfer is done in native AZERO, then the tokens are already in the contract, so no action is needed
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct OperationCancelled {
+        #[ink(topic)]
+        pub operation_id: u128,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            let mut data = self.data()?;
-            // NOTE: this allows the committee members to take a payout for requests that are not neccessarily finished
-            // by that time (no signature threshold reached yet).
-            // We could be recording the base fee when the request collects quorum, but it could change in the meantime
-            // which is potentially even worse
-            let base_fee_total = self
-                .collected_committee_rewards
-                .get(data.committee_id)
-                .unwrap_or(0)
-                .checked_add(current_base_fee)
-                .ok_or(MostError::Arithmetic)?;
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct OperationExecuted {
+        #[ink(topic)]
+        pub operation_id: u128,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            self.collected_committee_rewards
-                .insert(data.committee_id, &base_fee_total);
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct OperationDelayChanged {
+        pub previous_delay: u64,
+        pub new_delay: u64,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            let request_nonce = data.request_nonce;
-            data.request_nonce = request_nonce.checked_add(1).ok_or(MostError::Arithmetic)?;
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RoleGranted {
+        pub role: Role,
+        #[ink(topic)]
+        pub account: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            self.data.set(&data);
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RoleRevoked {
+        pub role: Role,
+        #[ink(topic)]
+        pub account: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            // return surplus if any
-            if let Some(surplus) = transferred_fee.checked_sub(current_base_fee) {
-                if surplus > 0 {
-                    self.env().transfer(sender, surplus)?;
-                }
-            };
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RequestDomainSet {
+        pub domain: RequestDomain,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            self.env().emit_event(CrosschainTransferRequest {
-                committee_id: data.committee_id,
-                dest_token_address,
-                amount,
-                dest_receiver_address,
-                request_nonce,
-            });
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct PairPauseChanged {
+        #[ink(topic)]
+        pub token: [u8; 32],
+        pub previous_pause: PairPause,
+        pub new_pause: PairPause,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
 
-            Ok(())
-        }
+    #[derive(Default, Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Request {
+        signature_count: u128,
+    }
 
-        /// Invoke this tx to initiate funds transfer to the destination chain.
-        ///
-        /// Upon checking basic conditions the contract will burn the `amount` number of `src_token_address` tokens from the caller
-        /// and emit an event which is to be picked up & acted on up by the bridge guardians.
-        #[ink(message, payable)]
-        pub fn send_request(
-            &mut self,
-            src_token_address: [u8; 32],
-            amount: u128,
-            dest_receiver_address: [u8; 32],
-        ) -> Result<(), MostError> {
-            self.ensure_not_halted()?;
+    /// Guardians that signed a request, one bit per position in the committee
+    #[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct RequestSigners {
+        committee_id: CommitteeId,
+        positions: u128,
+    }
 
-            let wrapped_azero_address = self.wazero.get().ok_or(MostError::WrappedAzeroNotSet)?;
-            let wrapped_azero_address_bytes: [u8; 32] = *wrapped_azero_address.as_ref();
-            if src_token_address == wrapped_azero_address_bytes {
-                // native AZERO transfers must be called via send_request_native_azero
-                return Err(MostError::UnsupportedPair);
-            }
+    #[derive(Debug, Encode, Decode, Clone, Copy)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum RequestStatus {
+        Pending { collected_signatures: u32 },
+        Processed,
+        RequestHashNotKnown,
+        Delayed { executable_at: u64 },
+        Cancelled,
+    }
 
-            let dest_token_address = self
-                .supported_pairs
-                .get(src_token_address)
-                .ok_or(MostError::UnsupportedPair)?;
+    /// An outgoing cross-chain transfer request, as needed to refund it
+    ///
+    /// Only its hash is stored, the request itself is emitted in `OutgoingRequestRecorded`.
+    #[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct OutgoingRequest {
+        /// committee the request was sent to, the only one that can vote for its refund
+        pub committee_id: CommitteeId,
+        pub sender: AccountId,
+        pub src_token_address: AccountId,
+        /// amount of `src_token_address` burned or locked by the request
+        pub amount: u128,
+        /// whether the request was made in native AZERO
+        pub native_azero: bool,
+    }
 
-            self._send_request(
-                src_token_address.into(),
-                dest_token_address,
-                amount,
-                dest_receiver_address,
-                false,
-                self.env().transferred_value(),
-            )
-        }
+    /// A request that reached the quorum but couldn't be executed right away, executable by anyone from `executable_at` on
+    #[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct DelayedRequest {
+        dest_token_address: [u8; 32],
+        amount: u128,
+        dest_receiver_address: [u8; 32],
+        executable_at: u64,
+    }
 
-        /// Invoke this tx to initiate weth -> ether transfer to the destination chain.
-        ///
-        /// Upon checking basic conditions the contract will burn the `amount` number of weth tokens from the caller
-        /// and emit an event which is to be picked up & acted on up by the bridge guardians.
-        #[ink(message, payable)]
-        pub fn send_request_native_ether(
-            &mut self,
+    /// Owner operation that takes effect only after the operation delay, once it is set
+    ///
+    /// The owner operations left out of the timelock stay instant on purpose:
+    /// * halting, pausing pairs, revoking roles and removing advisories only take power away and are needed in an emergency,
+    ///   resuming only restores what the timelocked configuration allows
+    /// * the fee and pocket money parameters only change what the users pay or get, the senders bound the fee with `max_fee`
+    /// * the request domain can only be set once, while the bridge is halted
+    #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum AdminOperation {
+        SetCode {
+            code_hash: [u8; 32],
+        },
+        SetCommittee {
+            committee: Vec<AccountId>,
+            signature_threshold: u128,
+        },
+        AddPair {
+            from: [u8; 32],
+            to: [u8; 32],
+            local_token: bool,
+        },
+        RemovePair {
+            from: [u8; 32],
+        },
+        SetTransferLimits {
+            token: [u8; 32],
+            limits: TransferLimits,
+        },
+        SetLargeTransferThreshold {
+            token: [u8; 32],
+            threshold: u128,
+        },
+        SetLargeTransferDelay {
+            delay: u64,
+        },
+        AddAdvisory {
+            account: AccountId,
+        },
+        SetWeth {
+            weth_address: AccountId,
+        },
+        SetWazero {
+            wazero_address: AccountId,
+        },
+        SetGasPriceOracle {
+            gas_price_oracle: AccountId,
+        },
+        GrantRole {
+            role: Role,
+            account: AccountId,
+        },
+        RecoverPsp22 {
+            token: AccountId,
+            receiver: AccountId,
             amount: u128,
-            dest_receiver_address: [u8; 32],
-        ) -> Result<(), MostError> {
-            self.ensure_not_halted()?;
+        },
+        RecoverAzero {
+            receiver: AccountId,
+            amount: u128,
+        },
+        SetOperationDelay {
+            delay: u64,
+        },
+    }
 
-            let src_token_address = self.weth.get().ok_or(MostError::WrappedEthNotSet)?;
+    #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct ScheduledOperation {
+        pub operation: AdminOperation,
+        /// timestamp in milliseconds after which the operation can be executed
+        pub executable_at: u64,
+    }
 
-            self._send_request(
-                src_token_address,
-                NATIVE_MARKER_ADDRESS,
-                amount,
-                dest_receiver_address,
-                false,
-                self.env().transferred_value(),
-            )
-        }
-
-        #[ink(message, payable)]
-        pub fn send_request_native_azero(
-            &mut self,
-            amount_to_bridge: u128,
-            dest_receiver_address: [u8; 32],
-        ) -> Result<(), MostError> {
-            self.ensure_not_halted()?;
-            let transferred_fee = self
-                .env()
-                .transferred_value()
-                .checked_sub(amount_to_bridge)
-                .ok_or(MostError::ValueTransferredLowerThanAmount)?;
+    /// Directions in which the transfers of a token are paused, independently of the whole bridge
+    #[derive(Default, Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct PairPause {
+        /// no new requests from Aleph Zero
+        pub outgoing: bool,
+        /// requests to Aleph Zero that reach the quorum are delayed until the pause is lifted
+        pub incoming: bool,
+    }
 
-            let wrapped_azero_address = self.wazero.get().ok_or(MostError::WrappedAzeroNotSet)?;
-            let wrapped_azero_address_bytes: [u8; 32] = *wrapped_azero_address.as_ref();
-            let mut wrapped_azero: contract_ref!(WrappedAZERO) = wrapped_azero_address.into();
+    /// Source of the incoming requests, bound into the hashes of the requests from `from_nonce` on
+    #[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct RequestDomain {
+        /// id of the chain the requests are sent from
+        pub src_chain_id: u128,
+        /// address of the `Most` contract the requests are sent from, left-padded with zeroes
+        pub src_contract: [u8; 32],
+        /// nonce of the first request hashed with `hash_request_data_v1`
+        pub from_nonce: u128,
+    }
 
-            wrapped_azero
-                .call_mut()
-                .deposit()
-                .transferred_value(amount_to_bridge)
-                .invoke()?;
+    /// Outcome of an outgoing transfer at the current fee
+    #[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TransferQuote {
+        /// token the receiver gets on the destination chain
+        pub dest_token_address: [u8; 32],
+        /// base fee in AZERO, to be transferred along with the request
+        pub fee: Balance,
+        /// amount of the request, scaled by `RATIO` for native AZERO
+        pub dest_amount: u128,
+        /// timestamp in milliseconds at which the oracle price behind the fee goes stale, `None` if the fee isn't based on a fresh oracle price
+        ///
+        /// Only bounds how old the price can get, the fee can still change before then. Use `max_fee` to bound the fee.
+        pub fee_valid_until: Option<u64>,
+    }
 
-            let dest_token_address = self
-                .supported_pairs
-                .get(wrapped_azero_address_bytes)
-                .ok_or(MostError::UnsupportedPair)?;
+    /// Vote of a guardian on a single request, as passed to `receive_requests`
+    #[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct RequestData {
+        pub request_hash: HashedRequest,
+        pub committee_id: CommitteeId,
+        pub dest_token_address: [u8; 32],
+        pub amount: u128,
+        pub dest_receiver_address: [u8; 32],
+        pub request_nonce: u128,
+    }
 
-            let amount = amount_to_bridge.saturating_mul(RATIO);
+    /// Limits on the transfers of a token, zero meaning no limit. The caps apply to the amounts
+    /// transferred within a rolling window: the capacity used up by a transfer frees up linearly
+    /// over the following `window` milliseconds.
+    ///
+    /// All amounts are in units of the token on Aleph Zero. The limits of native AZERO are set for
+    /// wAZERO and are in wAZERO units, not in the `RATIO`-scaled units of the requests.
+    #[derive(Default, Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct TransferLimits {
+        /// minimum amount of a single outgoing request
+        pub min_amount: u128,
+        /// maximum amount of a single outgoing request
+        pub max_amount: u128,
+        /// maximum amount of the outgoing requests within the window
+        pub outflow_cap: u128,
+        /// maximum amount of the incoming requests processed within the window
+        pub inflow_cap: u128,
+        /// length of the window in milliseconds
+        pub window: u64,
+    }
 
-            self._send_request(
-                wrapped_azero_address,
-                dest_token_address,
-                amount,
-                dest_receiver_address,
-                true,
-                transferred_fee,
-            )
+    impl TransferLimits {
+        fn is_valid(&self) -> bool {
+            let amounts_valid = self.max_amount == 0 || self.max_amount >= self.min_amount;
+            let window_valid = self.window > 0 || (self.outflow_cap == 0 && self.inflow_cap == 0);
+            amounts_valid && window_valid
         }
+    }
 
-        /// Aggregates request votes cast by guardians and mints/burns tokens
-        #[ink(message)]
-        pub fn receive_request(
-            &mut self,
-            request_hash: HashedRequest,
-            committee_id: CommitteeId,
-            dest_token_address: [u8; 32],
-            amount: u128,
-            dest_receiver_address: [u8; 32],
-            request_nonce: u128,
-        ) -> Result<(), MostError> {
-            self.ensure_not_halted()?;
-
-            let caller = self.env().caller();
-            self.only_committee_member(committee_id, caller)?;
+    /// Amount of a token transferred in one direction, accounted against a cap
+    #[derive(Default, Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Flow {
+        amount: u128,
+        updated_at: u64,
+    }
 
-            // Don't revert if the request has already been processed as
-            // such a call can be made during regular guardian operation.
-            if self.processed_requests.contains(request_hash) {
-                self.env().emit_event(SignedProcessedRequest {
-                    request_hash,
-                    signer: caller,
-                });
-                return Ok(());
+    impl Flow {
+        /// The part of the amount that still counts against `cap` at `now`
+        fn current(&self, cap: u128, window: u64, now: u64) -> u128 {
+            let elapsed = now.saturating_sub(self.updated_at);
+            if window == 0 || elapsed >= window {
+                return 0;
             }
 
-            if self.signatures.contains((request_hash, caller)) {
-                self.env().emit_event(RequestAlreadySigned {
-                    request_hash,
-                    signer: caller,
-                });
-                return Ok(());
-            }
+            let released = cap.saturating_mul(elapsed as u128) / window as u128;
+            self.amount.saturating_sub(released)
+        }
 
-            let hash = hash_request_data(
-                committee_id,
-                dest_token_address.into(),
+        /// Adds `amount` to the flow, `None` if that exceeds `cap`
+        fn add(&self, amount: u128, cap: u128, window: u64, now: u64) -> Option<Self> {
+            let amount = self.current(cap, window, now).checked_add(amount)?;
+            (amount <= cap).then_some(Self {
                 amount,
-                dest_receiver_address.into(),
-                request_nonce,
-            );
+                updated_at: now,
+            })
+        }
 
-            if !request_hash.eq(&hash) {
-                return Err(MostError::HashDoesNotMatchData);
+        /// Takes `amount` back out of the flow, down to zero at most
+        fn sub(&self, amount: u128, cap: u128, window: u64, now: u64) -> Self {
+            Self {
+                amount: self.current(cap, window, now).saturating_sub(amount),
+                updated_at: now,
             }
+        }
+    }
 
-            let mut request = self.pending_requests.get(request_hash).unwrap_or_default();
-
-            // record vote
-            request.signature_count = request
-                .signature_count
-                .checked_add(1)
-                .ok_or(MostError::Arithmetic)?;
-            self.signatures.insert((request_hash, caller), &());
+    /// Parameters of the base fee charged for a cross-chain transfer request, as set with `set_base_fee_constraints`
+    #[derive(Default, Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct BaseFeeConstraints {
+        pub relay_gas_usage: u128,
+        pub min_gas_price: u128,
+        pub max_gas_price: u128,
+        pub default_gas_price: u128,
+        pub gas_oracle_max_age: u64,
+        pub oracle_call_gas_limit: u64,
+        pub base_fee_buffer_percentage: u128,
+    }
 
-            self.env().emit_event(RequestSigned {
-                signer: caller,
-                request_hash,
-            });
+    #[derive(Debug)]
+    #[ink::storage_item]
+    pub struct Data {
+        /// nonce for outgoing cross-chain transfer requests
+        request_nonce: u128,
+        /// accounting helper
+        committee_id: CommitteeId,
+        /// max value of a subsidy transferred along with the bridged tokens to the destination account on aleph zero to bootstrap
+        max_pocket_money: u128,
+        /// balance that can be paid out as pocket money
+        pocket_money_balance: u128,
+        /// How much gas does a single confirmation of a cross-chain transfer request use on the destination chain on average.
+        /// This value is calculated by summing the total gas usage of *all* the transactions it takes to relay a single request and dividing it by the current committee size and multiplying by 1.2
+        relay_gas_usage: u128,
+        /// minimum gas price used to calculate the fee that can be charged for a cross-chain transfer request
+        min_gas_price: u128,
+        /// maximum gas price used to calculate the fee that can be charged for a cross-chain transfer request
+        max_gas_price: u128,
+        /// default gas price used to calculate the fee that is charged for a cross-chain transfer request if the gas price oracle is not available/malfunctioning
+        default_gas_price: u128,
+        /// maximum time in milliseconds since last oracle update
+        gas_oracle_max_age: u64,
+        /// maximum limit on gas for a call to the oracle
+        oracle_call_gas_limit: u64,
+        /// percentage buffer over the estimated cost
+        base_fee_buffer_percentage: u128,
+        /// gas price oracle that is used to calculate the fee for a cross-chain transfer request
+        gas_price_oracle: Option<AccountId>,
+        /// Is the bridge in a halted state
+        is_halted: bool,
+    }
 
-            let signature_threshold = self
-                .signature_thresholds
-                .get(committee_id)
-                .ok_or(MostError::InvalidThreshold)?;
+    impl Data {
+        fn base_fee_constraints(&self) -> BaseFeeConstraints {
+            BaseFeeConstraints {
+                relay_gas_usage: self.relay_gas_usage,
+                min_gas_price: self.min_gas_price,
+                max_gas_price: self.max_gas_price,
+                default_gas_price: self.default_gas_price,
+                gas_oracle_max_age: self.gas_oracle_max_age,
+                oracle_call_gas_limit: self.oracle_call_gas_limit,
+                base_fee_buffer_percentage: self.base_fee_buffer_percentage,
+            }
+        }
+    }
 
-            if request.signature_count >= signature_threshold {
-                let is_local_token = self
-                    .local_token
-                    .contains::<AccountId>(dest_token_address.into());
-
-                if dest_token_address == NATIVE_MARKER_ADDRESS {
-                    let amount = amount.saturating_div(RATIO);
-                    self.unwrap_azero_to(dest_receiver_address.into(), amount)?;
-                } else if is_local_token {
-                    self.transfer(
-                        dest_token_address.into(),
-                        dest_receiver_address.into(),
-                        amount,
-                    )?;
-                } else {
-                    self.mint_to(
-                        dest_token_address.into(),
-                        dest_receiver_address.into(),
-                        amount,
-                    )?;
-                }
+    #[ink(storage)]
+    pub struct Most {
+        data: Lazy<Data, ManualKey<0x44415441>>,
+        /// stores the data used by the Ownable2Step trait
+        ownable_data: Lazy<Ownable2StepData, ManualKey<0xDEADBEEF>>,
+        /// requests that are still collecting signatures
+        pending_requests: Mapping<HashedRequest, Request, ManualKey<0x50454E44>>,
+        /// signatures per cross chain transfer request, of the guardians without a bit in `request_signers`
+        signatures: Mapping<(HashedRequest, AccountId), (), ManualKey<0x5349474E>>,
+        /// signed & executed requests, a replay protection
+        processed_requests: Mapping<HashedRequest, (), ManualKey<0x50524F43>>,
+        /// set of guardian accounts that can sign requests
+        committees: Mapping<(CommitteeId, AccountId), (), ManualKey<0x434F4D4D>>,
+        /// accounting helper
+        committee_sizes: Mapping<CommitteeId, u128, ManualKey<0x53495A45>>,
+        /// number of signatures required to reach a quorum and costsxecute a transfer
+        signature_thresholds: Mapping<CommitteeId, u128, ManualKey<0x54485245>>,
+        /// source - destination token pairs that can be transferred across the bridge
+        supported_pairs: Mapping<[u8; 32], [u8; 32], ManualKey<0x53555050>>,
+        /// rewards collected by the commitee for relaying cross-chain transfer requests
+        collected_committee_rewards: Mapping<CommitteeId, u128, ManualKey<0x434F4C4C>>,
+        /// rewards collected by the individual commitee members for relaying cross-chain transfer requests
+        paid_out_member_rewards: Mapping<(AccountId, CommitteeId), u128, ManualKey<0x50414944>>,
+        /// committe members can specify a special account for collecting the rewards, different from the one used for signing
+        payout_accounts: Mapping<AccountId, AccountId, ManualKey<0x18f97340>>,
+        /// Wrapped ethereum (azero) address. Necessary to perform bridging weth(azero) -> ether(eth).
+        weth: Lazy<AccountId, ManualKey<0x7CD95FED>>,
+        /// Mapping holding the information wheter a specific token originates on Aleph Zero chain
+        local_token: Mapping<AccountId, (), ManualKey<0x6c6f6361>>,
+        /// Wrapped AZERO address
+        wazero: Lazy<AccountId, ManualKey<0x77617a65>>,
+        /// How much gas does user use when requesting a bridging transfer.
+        eth_transfer_gas_usage: Lazy<u128, ManualKey<0x6574685f>>,
+        /// transfer limits per token, keyed by the address of the token on Aleph Zero
+        transfer_limits: Mapping<[u8; 32], TransferLimits, ManualKey<0x4C494D54>>,
+        /// amounts of the outgoing requests per token, accounted against the outflow caps
+        outflows: Mapping<[u8; 32], Flow, ManualKey<0x4F555446>>,
+        /// amounts of the processed incoming requests per token, accounted against the inflow caps
+        inflows: Mapping<[u8; 32], Flow, ManualKey<0x494E4646>>,
+        /// amounts per token above which requests are delayed, keyed by the address of the token on Aleph Zero and in its units
+        large_transfer_thresholds: Mapping<[u8; 32], u128, ManualKey<0x4C415247>>,
+        /// time in milliseconds between reaching the quorum and executing a large request
+        large_transfer_delay: Lazy<u64, ManualKey<0x44454C41>>,
+        /// requests that reached the quorum, waiting for their delay, a pause of their pair or their inflow cap
+        delayed_requests: Mapping<HashedRequest, DelayedRequest, ManualKey<0x57414954>>,
+        /// delayed requests that were cancelled, they will never be executed
+        cancelled_requests: Mapping<HashedRequest, (), ManualKey<0x43414E43>>,
+        /// accounts that can cancel delayed requests, along with the owner
+        advisories: Mapping<AccountId, (), ManualKey<0x41445649>>,
+        /// hashes of the outgoing cross-chain transfer requests per nonce, in case they need to be refunded
+        ///
+        /// Each entry adds a 32 byte hash to the storage deposit, paid by the caller of `send_request`,
+        /// until the request is refunded
+        outgoing_requests: Mapping<u128, HashedRequest, ManualKey<0x4F555452>>,
+        /// refunds that are still collecting signatures, per nonce of the refunded request
+        pending_refunds: Mapping<u128, Request, ManualKey<0x50524546>>,
+        /// signatures per refund, by the members of the committee of the refunded request
+        refund_signatures: Mapping<RefundVote, (), ManualKey<0x52534947>>,
+        /// nonces of the refunded outgoing requests, guardians must not process them on the destination chain
+        refunded_requests: Mapping<u128, (), ManualKey<0x52454655>>,
+        /// members of each committee, in the order they were set in
+        committee_members: Mapping<CommitteeId, Vec<AccountId>, ManualKey<0x4D454D42>>,
+        /// position of each member in `committee_members`, its bit in `request_signers`
+        committee_positions: Mapping<(CommitteeId, AccountId), u32, ManualKey<0x504F5349>>,
+        /// block at which each committee became the active one
+        committee_activations: Mapping<CommitteeId, BlockNumber, ManualKey<0x41435456>>,
+        /// minimum time in milliseconds between scheduling and executing an owner operation, 0 while the timelock is off
+        operation_delay: Lazy<u64, ManualKey<0x544C434B>>,
+        /// id of the next scheduled owner operation
+        operation_nonce: Lazy<u128, ManualKey<0x4F504E43>>,
+        /// owner operations waiting for the operation delay to pass
+        scheduled_operations: Mapping<u128, ScheduledOperation, ManualKey<0x5343484F>>,
+        /// accounts the owner delegated some of its permissions to
+        roles: AccessControlData,
+        /// pauses per token, keyed by the address of the token on Aleph Zero
+        paused_pairs: Mapping<[u8; 32], PairPause, ManualKey<0x50415553>>,
+        /// source of the incoming requests, once the requests are bound to it
+        request_domain: Lazy<RequestDomain, ManualKey<0x444F4D4E>>,
+        /// guardians that signed each cross chain transfer request
+        request_signers: Mapping<HashedRequest, RequestSigners, ManualKey<0x5349474D>>,
+        /// highest nonce of the incoming requests that reached the quorum, the request domain can only apply above it
+        highest_processed_nonce: Lazy<u128, ManualKey<0x4D41584E>>,
+    }
 
-                self.send_pocket_money(dest_receiver_address.into())?;
+    #[derive(Debug, PartialEq, Eq, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum MostError {
+        Constructor,
+        InvalidThreshold,
+        DuplicateCommitteeMember,
+        ZeroTransferAmount,
+        NotInCommittee,
+        NoSuchCommittee,
+        HashDoesNotMatchData,
+        PSP22(PSP22Error),
+        Ownable(Ownable2StepError),
+        UnsupportedPair,
+        InkEnvError(String),
+        RequestAlreadySigned,
+        BaseFeeTooLow,
+        Arithmetic,
+        CorruptedStorage,
+        IsHalted,
+        HaltRequired,
+        NoMintPermission,
+        ZeroAddress,
+        WrappedEthNotSet,
+        WrappedAzeroNotSet,
+        ValueTransferredLowerThanAmount,
+        Other,
+        InvalidTransferLimits,
+        AmountBelowMinimum,
+        AmountAboveMaximum,
+        OutflowCapExceeded,
+        InflowCapExceeded,
+        RequestNotDelayed,
+        DelayNotElapsed,
+        UnknownRequest,
+        RequestAlreadyRefunded,
+        TimelockRequired,
+        InvalidOperationDelay,
+        UnknownOperation,
+        PairPaused,
+        RequestDomainAlreadySet,
+        NonceAlreadyProcessed,
+        FeeAboveMax,
+        AccessControl(AccessControlError),
+    }
 
-                // mark it as processed
-                self.processed_requests.insert(request_hash, &());
-                self.pending_requests.remove(request_hash);
+    impl From<InkEnvError> for MostError {
+        fn from(why: InkEnvError) -> Self {
+            Self::InkEnvError(format!("{:?}", why))
+        }
+    }
 
-                self.env().emit_event(RequestProcessed {
-                    request_hash,
-                    dest_token_address,
-                });
-            } else {
-                self.pending_requests.insert(request_hash, &request);
-            }
+    impl From<PSP22Error> for MostError {
+        fn from(inner: PSP22Error) -> Self {
+            MostError::PSP22(inner)
+        }
+    }
 
-            Ok(())
+    impl From<Ownable2StepError> for MostError {
+        fn from(inner: Ownable2StepError) -> Self {
+            MostError::Ownable(inner)
         }
+    }
 
-        fn send_pocket_money(&mut self, dest_receiver_address: AccountId) -> Result<(), MostError> {
-            let mut data = self.data()?;
-            let pocket_money_to_send = self.current_pocket_money_value();
+    impl From<AccessControlError> for MostError {
+        fn from(inner: AccessControlError) -> Self {
+            MostError::AccessControl(inner)
+        }
+    }
 
-            // we have nothing to send
-            if pocket_money_to_send == 0 {
-                return Ok(());
-            }
+    /// The amount of an incoming request in units of its token on Aleph Zero, as requests for
+    /// native AZERO are scaled by `RATIO`
+    fn local_amount(dest_token_address: [u8; 32], amount: u128) -> u128 {
+        match dest_token_address == NATIVE_MARKER_ADDRESS {
+            true => amount.saturating_div(RATIO),
+            false => amount,
+        }
+    }
 
-            // bootstrap account with pocket money
-            if data.pocket_money_balance < pocket_money_to_send {
-                return Ok(());
-            }
+    impl Most {
+        #[allow(clippy::too_many_arguments)]
+        #[ink(constructor)]
+        pub fn new(
+            committee: Vec<AccountId>,
+            signature_threshold: u128,
+            max_pocket_money: Balance,
+            relay_gas_usage: u128,
+            min_gas_price: u128,
+            max_gas_price: u128,
+            default_gas_price: u128,
+            gas_oracle_max_age: u64,
+            oracle_call_gas_limit: u64,
+            base_fee_buffer_percentage: u128,
+            gas_price_oracle: Option<AccountId>,
+            owner: AccountId,
+            initial_eth_transfer_gas_usage: u128,
+        ) -> Result<Self, MostError> {
+            Self::check_committee(&committee, signature_threshold)?;
 
-            // don't revert if the transfer fails
-            if self
-                .env()
-                .transfer(dest_receiver_address, pocket_money_to_send)
-                .is_ok()
-            {
-                data.pocket_money_balance = data
-                    .pocket_money_balance
-                    .checked_sub(pocket_money_to_send)
-                    .ok_or(MostError::Arithmetic)?;
-                self.data.set(&data);
-            }
+            let committee_id = 0;
 
-            Ok(())
-        }
+            let mut committees = Mapping::new();
+            committee.iter().for_each(|account| {
+                committees.insert((committee_id, *account), &());
+            });
 
-        /// Calculates amount of pocket money that will be transferred to destination account upon
-        /// bridging transfer.
-        #[ink(message)]
-        pub fn current_pocket_money_value(&self) -> u128 {
-            const DEFAULT_POCKET_MONEY: u128 = 0;
+            let mut committee_sizes = Mapping::new();
+            committee_sizes.insert(committee_id, &(committee.len() as u128));
 
-            let gas_price = match self.get_gas_price() {
-                Ok(Some((price, _))) => price,
-                _ => return DEFAULT_POCKET_MONEY,
-            };
-            let data = match self.data() {
-                Ok(data) => data,
-                _ => return DEFAULT_POCKET_MONEY,
-            };
-            let eth_transfer_gas_usage = match self.eth_transfer_gas_usage.get() {
-                Some(gas_usage) => gas_usage,
-                _ => return DEFAULT_POCKET_MONEY,
-            };
-            let max_pocket_money = data.max_pocket_money;
+            let mut signature_thresholds = Mapping::new();
+            signature_thresholds.insert(committee_id, &signature_threshold);
 
-            // ~75% of the gas spend on eth side but no more than allowed max
-            let pocket_money =
-                u128::min(max_pocket_money, eth_transfer_gas_usage * gas_price * 3 / 4);
+            let activated_at = Self::env().block_number();
+            let mut committee_members = Mapping::new();
+            committee_members.insert(committee_id, &committee);
+            let mut committee_positions = Mapping::new();
+            committee
+                .iter()
+                .enumerate()
+                .for_each(|(position, account)| {
+                    committee_positions.insert((committee_id, *account), &(position as u32));
+                });
+            let mut committee_activations = Mapping::new();
+            committee_activations.insert(committee_id, &activated_at);
 
-            u128::min(pocket_money, data.pocket_money_balance)
+            Self::env().emit_event(CommitteeUpdated {
+                committee_id,
+                members: committee,
+                signature_threshold,
+                activated_at,
+            });
+
+            let mut data = Lazy::new();
+            data.set(&Data {
+                request_nonce: 0,
+                committee_id,
+                max_pocket_money,
+                pocket_money_balance: 0,
+                relay_gas_usage,
+                min_gas_price,
+                max_gas_price,
+                default_gas_price,
+                gas_oracle_max_age,
+                oracle_call_gas_limit,
+                base_fee_buffer_percentage,
+                gas_price_oracle,
+                is_halted: true,
+            });
+
+            let mut ownable_data = Lazy::new();
+            ownable_data.set(&Ownable2StepData::new(owner));
+            let weth = Lazy::new();
+            let wazero = Lazy::new();
+            let mut eth_transfer_gas_usage = Lazy::new();
+            eth_transfer_gas_usage.set(&initial_eth_transfer_gas_usage);
+
+            Ok(Self {
+                data,
+                ownable_data,
+                signature_thresholds,
+                committees,
+                committee_sizes,
+                pending_requests: Mapping::new(),
+                signatures: Mapping::new(),
+                processed_requests: Mapping::new(),
+                supported_pairs: Mapping::new(),
+                collected_committee_rewards: Mapping::new(),
+                paid_out_member_rewards: Mapping::new(),
+                payout_accounts: Mapping::new(),
+                weth,
+                local_token: Mapping::new(),
+                wazero,
+                eth_transfer_gas_usage,
+                transfer_limits: Mapping::new(),
+                outflows: Mapping::new(),
+                inflows: Mapping::new(),
+                large_transfer_thresholds: Mapping::new(),
+                large_transfer_delay: Lazy::new(),
+                delayed_requests: Mapping::new(),
+                cancelled_requests: Mapping::new(),
+                advisories: Mapping::new(),
+                outgoing_requests: Mapping::new(),
+                pending_refunds: Mapping::new(),
+                refund_signatures: Mapping::new(),
+                refunded_requests: Mapping::new(),
+                committee_members,
+                committee_positions,
+                committee_activations,
+                operation_delay: Lazy::new(),
+                operation_nonce: Lazy::new(),
+                scheduled_operations: Mapping::new(),
+                roles: AccessControlData::new(),
+                paused_pairs: Mapping::new(),
+                request_domain: Lazy::new(),
+                request_signers: Mapping::new(),
+                highest_processed_nonce: Lazy::new(),
+            })
         }
 
-        /// Request payout of rewards for signing & relaying cross-chain transfers.
-        ///
-        /// Reverts if `member_id` account is not in the committee with `committee_id`
-        #[ink(message)]
-        pub fn payout_rewards(
+        // --- business logic
+        #[allow(clippy::too_many_arguments)]
+        fn _send_request(
             &mut self,
-            committee_id: CommitteeId,
-            member_id: AccountId,
+            src_token_address: AccountId,
+            dest_token_address: [u8; 32],
+            amount: u128,
+            dest_receiver_address: [u8; 32],
+            native_azero_request: bool,
+            transferred_fee: u128,
+            max_fee: Option<u128>,
         ) -> Result<(), MostError> {
-            self.ensure_not_halted()?;
-            self.only_committee_member(committee_id, member_id)?;
+            if dest_receiver_address == ZERO_ADDRESS {
+                return Err(MostError::ZeroAddress);
+            }
 
-            let paid_out_rewards = self.get_paid_out_member_rewards(committee_id, member_id);
+            if amount == 0 {
+                return Err(MostError::ZeroTransferAmount);
+            }
 
-            let outstanding_rewards =
-                self.get_outstanding_member_rewards(committee_id, member_id)?;
+            if self.get_pair_pause(*src_token_address.as_ref()).outgoing {
+                return Err(MostError::PairPaused);
+            }
 
-            if outstanding_rewards.gt(&0) {
-                let payout_account = self.payout_accounts.get(member_id).unwrap_or(member_id);
-                self.env().transfer(payout_account, outstanding_rewards)?;
+            // the limits of native AZERO apply to the amount in wAZERO units
+            let locked_amount = match native_azero_request {
+                true => amount.saturating_div(RATIO),
+                false => amount,
+            };
+            self.record_outflow(*src_token_address.as_ref(), locked_amount)?;
 
-                self.paid_out_member_rewards.insert(
-                    (member_id, committee_id),
-                    &paid_out_rewards
-                        .checked_add(outstanding_rewards)
-                        .ok_or(MostError::Arithmetic)?,
-                );
+            let current_base_fee = self.get_base_fee()?;
+
+            if matches!(max_fee, Some(max_fee) if current_base_fee > max_fee) {
+                return Err(MostError::FeeAboveMax);
             }
 
-            Ok(())
-        }
+            if transferred_fee.lt(&current_base_fee) {
+                return Err(MostError::BaseFeeTooLow);
+            }
+
+            let sender = self.env().caller();
+
+            if self.local_token.get(src_token_address).is_none() {
+                self.burn_from(src_token_address, sender, amount)?;
+            } else if !native_azero_request {
+                // lock the tokens in the contract
+                self.transfer_from(src_token_address, sender, amount)?;
+            } // if the transfer is done in native AZERO, then the tokens are already in the contract, so no action is needed
 
-        /// Method used to provide the contract with funds for pocket money
-        #[ink(message, payable)]
-        pub fn fund_pocket_money(&mut self) -> Result<(), MostError> {
-            let funds = self.env().transferred_value();
             let mut data = self.data()?;
-            data.pocket_money_balance = data
-                .pocket_money_balance
-                .checked_add(funds)
+            // NOTE: this allows the committee members to take a payout for requests that are not neccessarily finished
+            // by that time (no signature threshold reached yet).
+            // We could be recording the base fee when the request collects quorum, but it could change in the meantime
+            // which is potentially even worse
+            let base_fee_total = self
+                .collected_committee_rewards
+                .get(data.committee_id)
+                .unwrap_or(0)
+                .checked_add(current_base_fee)
                 .ok_or(MostError::Arithmetic)?;
-            self.data.set(&data);
-            Ok(())
-        }
 
-        /// Method used to change max_pocket_money value.
-        #[ink(message)]
-        pub fn set_max_pocket_money(
-            &mut self,
-            new_max_pocket_money: u128,
-        ) -> Result<(), MostError> {
-            self.ensure_owner()?;
-            let mut data = self.data()?;
+            self.collected_committee_rewards
+                .insert(data.committee_id, &base_fee_total);
 
-            data.max_pocket_money = new_max_pocket_money;
-            self.data.set(&data);
+            let request_nonce = data.request_nonce;
+            data.request_nonce = request_nonce.checked_add(1).ok_or(MostError::Arithmetic)?;
 
-            Ok(())
-        }
+            self.data.set(&data);
 
-        /// Upgrades contract code
-        #[ink(message)]
-        pub fn set_code(&mut self, code_hash: [u8; 32]) -> Result<(), MostError> {
-            self.ensure_owner()?;
-            set_code_hash(&code_hash)?;
-            Ok(())
-        }
+            let outgoing_request = OutgoingRequest {
+                committee_id: data.committee_id,
+                sender,
+                src_token_address,
+                amount: locked_amount,
+                native_azero: native_azero_request,
+            };
+            self.outgoing_requests.insert(
+                request_nonce,
+                &self.hash_outgoing_request(&outgoing_request),
+            );
 
-        // --- getters
+            // return surplus if any
+            if let Some(surplus) = transferred_fee.checked_sub(current_base_fee) {
+                if surplus > 0 {
+                    self.env().transfer(sender, surplus)?;
+                }
+            };
 
-        /// Query average gas it takes for a confirmation of a single cross-chain transfer request on the destination chain
-        ///
-        /// Sum of the total gas usage of *all* the relay transactions divided it by the current committee size and multiplied by 1.2
-        #[ink(message)]
-        pub fn get_relay_gas_usage(&self) -> Result<u128, MostError> {
-            Ok(self.data()?.relay_gas_usage)
-        }
+            self.env().emit_event(CrosschainTransferRequest {
+                committee_id: data.committee_id,
+                dest_token_address,
+                amount,
+                dest_receiver_address,
+                request_nonce,
+            });
+            self.env().emit_event(OutgoingRequestRecorded {
+                request_nonce,
+                request: outgoing_request,
+            });
 
-        /// Query minimum gas price used to calculate the fee that can be charged for a cross-chain transfer request
-        #[ink(message)]
-        pub fn get_min_gas_price(&self) -> Result<u128, MostError> {
-            Ok(self.data()?.min_gas_price)
+            Ok(())
         }
 
-        /// Query maximum gas price used to calculate the fee that can be charged for a cross-chain transfer request
-        #[ink(message)]
-        pub fn get_max_gas_price(&self) -> Result<u128, MostError> {
-            Ok(self.data()?.max_gas_price)
-        }
+        /// Invoke this tx to initiate funds transfer to the destination chain.
+        ///
+        /// Upon checking basic conditions the contract will burn the `amount` number of `src_token_address` tokens from the caller
+        /// and emit an event which is to be picked up & acted on up by the bridge guardians.
+        /// Fails if the base fee is above `max_fee`, when it is given.
+        #[ink(message, payable)]
+        pub fn send_request(
+            &mut self,
+            src_token_address: [u8; 32],
+            amount: u128,
+            dest_receiver_address: [u8; 32],
+            max_fee: Option<u128>,
+        ) -> Result<(), MostError> {
+            self.ensure_not_halted()?;
 
-        /// Query default gas price used to calculate the fee that is charged for a cross-chain transfer request if the gas price oracle is not available
-        #[ink(message)]
-        pub fn get_default_gas_price(&self) -> Result<u128, MostError> {
-            Ok(self.data()?.default_gas_price)
-        }
+            let wrapped_azero_address = self.wazero.get().ok_or(MostError::WrappedAzeroNotSet)?;
+            let wrapped_azero_address_bytes: [u8; 32] = *wrapped_azero_address.as_ref();
+            if src_token_address == wrapped_azero_address_bytes {
+                // native AZERO transfers must be called via send_request_native_azero
+                return Err(MostError::UnsupportedPair);
+            }
 
-        /// Query maximum time in milliseconds since last oracle update
-        #[ink(message)]
-        pub fn get_gas_oracle_max_age(&self) -> Result<u64, MostError> {
-            Ok(self.data()?.gas_oracle_max_age)
-        }
+            let dest_token_address = self
+                .supported_pairs
+                .get(src_token_address)
+                .ok_or(MostError::UnsupportedPair)?;
 
-        /// Query maximum limit on gas for a call to the oracle
-        #[ink(message)]
-        pub fn get_oracle_call_gas_limit(&self) -> Result<u64, MostError> {
-            Ok(self.data()?.oracle_call_gas_limit)
+            self._send_request(
+                src_token_address.into(),
+                dest_token_address,
+                amount,
+                dest_receiver_address,
+                false,
+                self.env().transferred_value(),
+                max_fee,
+            )
         }
 
-        /// Query percentage buffer over the estimated cost
-        #[ink(message)]
-        pub fn get_base_fee_buffer_percentage(&self) -> Result<u128, MostError> {
-            Ok(self.data()?.base_fee_buffer_percentage)
-        }
+        /// Invoke this tx to initiate weth -> ether transfer to the destination chain.
+        ///
+        /// Upon checking basic conditions the contract will burn the `amount` number of weth tokens from the caller
+        /// and emit an event which is to be picked up & acted on up by the bridge guardians.
+        /// Fails if the base fee is above `max_fee`, when it is given.
+        #[ink(message, payable)]
+        pub fn send_request_native_ether(
+            &mut self,
+            amount: u128,
+            dest_receiver_address: [u8; 32],
+            max_fee: Option<u128>,
+        ) -> Result<(), MostError> {
+            self.ensure_not_halted()?;
 
-        /// Query gas price oracle address
-        #[ink(message)]
-        pub fn get_gas_price_oracle(&self) -> Result<Option<AccountId>, MostError> {
-            Ok(self.data()?.gas_price_oracle)
-        }
+            let src_token_address = self.weth.get().ok_or(MostError::WrappedEthNotSet)?;
 
-        /// Query token pair
-        #[ink(message)]
-        pub fn get_supported_pair(&self, src_token: [u8; 32]) -> Option<[u8; 32]> {
-            self.supported_pairs.get(src_token)
+            self._send_request(
+                src_token_address,
+                NATIVE_MARKER_ADDRESS,
+                amount,
+                dest_receiver_address,
+                false,
+                self.env().transferred_value(),
+                max_fee,
+            )
         }
 
-        /// Query payout_account for a committee member (if any)
-        #[ink(message)]
-        pub fn get_payout_account(&self, member_id: AccountId) -> Option<AccountId> {
-            self.payout_accounts.get(member_id)
-        }
+        /// Invoke this tx to initiate native AZERO transfer to the destination chain.
+        ///
+        /// The transferred value covers both `amount_to_bridge` and the base fee.
+        /// Fails if the base fee is above `max_fee`, when it is given.
+        #[ink(message, payable)]
+        pub fn send_request_native_azero(
+            &mut self,
+            amount_to_bridge: u128,
+            dest_receiver_address: [u8; 32],
+            max_fee: Option<u128>,
+        ) -> Result<(), MostError> {
+            self.ensure_not_halted()?;
+            let transferred_fee = self
+                .env()
+                .transferred_value()
+                .checked_sub(amount_to_bridge)
+                .ok_or(MostError::ValueTransferredLowerThanAmount)?;
 
-        /// Query wazero(azero) psp22 token contract
-        #[ink(message)]
-        pub fn get_wazero(&self) -> Option<AccountId> {
-            self.wazero.get()
-        }
+            let wrapped_azero_address = self.wazero.get().ok_or(MostError::WrappedAzeroNotSet)?;
+            let wrapped_azero_address_bytes: [u8; 32] = *wrapped_azero_address.as_ref();
+            let mut wrapped_azero: contract_ref!(WrappedAZERO) = wrapped_azero_address.into();
 
-        /// Query weth(azero) psp22 token contract
-        #[ink(message)]
-        pub fn get_weth(&self) -> Option<AccountId> {
-            self.weth.get()
-        }
+            wrapped_azero
+                .call_mut()
+                .deposit()
+                .transferred_value(amount_to_bridge)
+                .invoke()?;
 
-        /// Query if given token originates on AlephNode chain
-        #[ink(message)]
-        pub fn is_local_token(&self, token_address: AccountId) -> bool {
-            self.local_token.contains::<AccountId>(token_address)
-        }
+            let dest_token_address = self
+                .supported_pairs
+                .get(wrapped_azero_address_bytes)
+                .ok_or(MostError::UnsupportedPair)?;
 
-        /// Query request nonce
-        ///
-        /// Nonce is incremented with every request
-        #[ink(message)]
-        pub fn get_request_nonce(&self) -> Result<u128, MostError> {
-            Ok(self.data()?.request_nonce)
-        }
+            let amount = amount_to_bridge.saturating_mul(RATIO);
 
-        /// Query pocket money
-        ///
-        /// An amount of the native token that is tranferred with every request
-        #[ink(message)]
-        pub fn get_max_pocket_money(&self) -> Result<Balance, MostError> {
-            Ok(self.data()?.max_pocket_money)
+            self._send_request(
+                wrapped_azero_address,
+                dest_token_address,
+                amount,
+                dest_receiver_address,
+                true,
+                transferred_fee,
+                max_fee,
+            )
         }
 
-        /// Query pocket money balance
-        ///
-        /// An amount of the native token that can be used for pocket money transfers
+        /// Aggregates request votes cast by guardians and mints/burns tokens
         #[ink(message)]
-        pub fn get_pocket_money_balance(&self) -> Result<Balance, MostError> {
-            Ok(self.data()?.pocket_money_balance)
-        }
+        pub fn receive_request(
+            &mut self,
+            request_hash: HashedRequest,
+            committee_id: CommitteeId,
+            dest_token_address: [u8; 32],
+            amount: u128,
+            dest_receiver_address: [u8; 32],
+            request_nonce: u128,
+        ) -> Result<(), MostError> {
+            self.ensure_not_halted()?;
 
-        /// Returns current active committee id
-        #[ink(message)]
-        pub fn get_current_committee_id(&self) -> Result<u128, MostError> {
-            Ok(self.data()?.committee_id)
+            let caller = self.env().caller();
+            let request = RequestData {
+                request_hash,
+                committee_id,
+                dest_token_address,
+                amount,
+                dest_receiver_address,
+                request_nonce,
+            };
+            let signature_threshold = self.check_request(caller, &request)?;
+            self._receive_request(caller, request, signature_threshold)
         }
 
-        /// Query total rewards for this committee
+        /// Aggregates the votes of a guardian on a batch of requests
         ///
-        /// Denominated in AZERO
+        /// Each request is handled as in `receive_request` and its result is returned at the same
+        /// position. A request that fails doesn't revert the batch: its vote isn't recorded, so it can
+        /// be cast again, for example once a request that failed to execute at the quorum can be.
         #[ink(message)]
-        pub fn get_collected_committee_rewards(&self, committee_id: CommitteeId) -> u128 {
-            self.collected_committee_rewards
-                .get(committee_id)
-                .unwrap_or_default()
-        }
+        pub fn receive_requests(
+            &mut self,
+            requests: Vec<RequestData>,
+        ) -> Result<Vec<Result<(), MostError>>, MostError> {
+            self.ensure_not_halted()?;
 
-        /// Query already paid out committee member rewards
-        ///
-        /// Denominated in AZERO
-        #[ink(message)]
-        pub fn get_paid_out_member_rewards(
-            &self,
-            committee_id: CommitteeId,
-            member_id: AccountId,
-        ) -> u128 {
-            self.paid_out_member_rewards
-                .get((member_id, committee_id))
-                .unwrap_or_default()
+            let caller = self.env().caller();
+            let mut results = Vec::with_capacity(requests.len());
+            for request in requests {
+                match self.check_request(caller, &request) {
+                    Ok(signature_threshold) => {
+                        results.push(self._receive_request(caller, request, signature_threshold))
+                    }
+                    Err(why) => results.push(Err(why)),
+                }
+            }
+
+            Ok(results)
         }
 
-        /// Query outstanding committee member rewards
-        ///
-        /// The amount that can still be requested.
-        /// Denominated in AZERO
-        /// Returns 0 if the `member_id` account is not in the committee with `committee_id`
-        #[ink(message)]
-        pub fn get_outstanding_member_rewards(
+        /// Checks a vote of `caller` on a request before any state is changed and returns the
+        /// signature threshold of its committee
+        fn check_request(
             &self,
-            committee_id: CommitteeId,
-            member_id: AccountId,
+            caller: AccountId,
+            request: &RequestData,
         ) -> Result<u128, MostError> {
-            if self.is_in_committee(committee_id, member_id) {
-                let total_amount = self
-                    .get_collected_committee_rewards(committee_id)
-                    .checked_div(
-                        self.committee_sizes
-                            .get(committee_id)
-                            .ok_or(MostError::NoSuchCommittee)?,
-                    )
-                    .ok_or(MostError::Arithmetic)?;
+            self.only_committee_member(request.committee_id, caller)?;
 
-                let collected_amount = self.get_paid_out_member_rewards(committee_id, member_id);
+            let hash = self.hash_request(request);
 
-                return Ok(total_amount.saturating_sub(collected_amount));
+            if !request.request_hash.eq(&hash) {
+                return Err(MostError::HashDoesNotMatchData);
             }
-            Ok(0)
-        }
 
-        fn get_gas_price(&self) -> Result<Option<(Balance, u64)>, MostError> {
-            if let Some(gas_price_oracle_address) = self.data()?.gas_price_oracle {
-                let gas_price_oracle: contract_ref!(EthGasPriceOracle) =
-                    gas_price_oracle_address.into();
+            self.token_key(request.dest_token_address)?;
 
-                return Ok(
-                    match gas_price_oracle
-                        .call()
-                        .get_price()
-                        .gas_limit(self.data()?.oracle_call_gas_limit)
-                        .try_invoke()
-                    {
-                        Ok(Ok(res)) => Some(res),
-                        _ => None,
-                    },
-                );
+            self.signature_thresholds
+                .get(request.committee_id)
+                .ok_or(MostError::InvalidThreshold)
+        }
+
+        /// Hashes a request with the version its nonce requires: requests from the nonce set in
+        /// `set_request_domain` on are bound to their source and to this contract
+        fn hash_request(&self, request: &RequestData) -> HashedRequest {
+            match self.request_domain.get() {
+                Some(domain) if request.request_nonce >= domain.from_nonce => hash_request_data_v1(
+                    request_domain_separator(
+                        domain.src_chain_id,
+                        domain.src_contract,
+                        *self.env().account_id().as_ref(),
+                    ),
+                    request.committee_id,
+                    request.dest_token_address.into(),
+                    request.amount,
+                    request.dest_receiver_address.into(),
+                    request.request_nonce,
+                ),
+                _ => hash_request_data(
+                    request.committee_id,
+                    request.dest_token_address.into(),
+                    request.amount,
+                    request.dest_receiver_address.into(),
+                    request.request_nonce,
+                ),
             }
+        }
 
-            Ok(None)
+        fn hash_outgoing_request(&self, request: &OutgoingRequest) -> HashedRequest {
+            self.env().hash_encoded::<Keccak256, _>(request)
         }
 
-        fn get_gas_price_with_limits(&self) -> Result<Balance, MostError> {
-            let gas_price = match self.get_gas_price()? {
-                Some((gas_price, timestamp)) => {
-                    if timestamp + self.data()?.gas_oracle_max_age < self.env().block_timestamp() {
-                        self.data()?.default_gas_price
-                    } else if gas_price < self.data()?.min_gas_price {
-                        self.data()?.min_gas_price
-                    } else if gas_price > self.data()?.max_gas_price {
-                        self.data()?.max_gas_price
-                    } else {
-                        gas_price
-                    }
-                }
-                _ => self.data()?.default_gas_price,
-            };
+        /// Records a vote checked with `check_request`, executing the request if it reaches the quorum
+        ///
+        /// Everything that can fail is done before any state is changed, so that a failure leaves no
+        /// trace in a batch and the vote can be cast again.
+        fn _receive_request(
+            &mut self,
+            caller: AccountId,
+            request_data: RequestData,
+            signature_threshold: u128,
+        ) -> Result<(), MostError> {
+            let RequestData {
+                request_hash,
+                committee_id,
+                dest_token_address,
+                amount,
+                dest_receiver_address,
+                request_nonce,
+            } = request_data;
 
-            Ok(gas_price)
-        }
+            // Don't revert if the request has already been processed as
+            // such a call can be made during regular guardian operation.
+            if self.processed_requests.contains(request_hash) {
+                self.env().emit_event(SignedProcessedRequest {
+                    request_hash,
+                    signer: caller,
+                });
+                return Ok(());
+            }
 
-        /// Queries a gas price oracle and returns the current base_fee charged per cross chain transfer denominated in AZERO
-        #[ink(message)]
-        pub fn get_base_fee(&self) -> Result<Balance, MostError> {
-            let gas_price = self.get_gas_price_with_limits()?;
+            if self.has_signed_request(caller, request_hash) {
+                self.env().emit_event(RequestAlreadySigned {
+                    request_hash,
+                    signer: caller,
+                });
+                return Ok(());
+            }
 
-            let base_fee = gas_price
-                .checked_mul(self.data()?.relay_gas_usage)
-                .ok_or(MostError::Arithmetic)?
-                .checked_mul(100u128 + self.data()?.base_fee_buffer_percentage)
-                .ok_or(MostError::Arithmetic)?
-                .checked_div(100u128)
+            let mut request = self.pending_requests.get(request_hash).unwrap_or_default();
+            request.signature_count = request
+                .signature_count
+                .checked_add(1)
                 .ok_or(MostError::Arithmetic)?;
-            Ok(base_fee)
-        }
+            let reaches_quorum = request.signature_count >= signature_threshold;
+
+            let mut delayed_request = None;
+            if reaches_quorum {
+                let token = self.token_key(dest_token_address)?;
+                let local_amount = local_amount(dest_token_address, amount);
+                let threshold = self.get_large_transfer_threshold(token);
+                let is_large = threshold > 0 && local_amount > threshold;
+                // requests of a paused pair or over the inflow cap wait to be retried instead of failing the vote
+                if is_large
+                    || self.get_pair_pause(token).incoming
+                    || !self.inflow_fits(token, local_amount)
+                {
+                    let delay = match is_large {
+                        true => self.get_large_transfer_delay(),
+                        false => 0,
+                    };
+                    let executable_at = self
+                        .env()
+                        .block_timestamp()
+                        .checked_add(delay)
+                        .ok_or(MostError::Arithmetic)?;
+                    delayed_request = Some(DelayedRequest {
+                        dest_token_address,
+                        amount,
+                        dest_receiver_address,
+                        executable_at,
+                    });
+                } else {
+                    self.execute_request(
+                        request_hash,
+                        dest_token_address,
+                        amount,
+                        dest_receiver_address,
+                    )?;
+                }
+            }
 
-        /// Returns whether an account is in the committee with `committee_id`
-        #[ink(message)]
-        pub fn is_in_committee(&self, committee_id: CommitteeId, account: AccountId) -> bool {
-            self.committees.contains((committee_id, account))
-        }
+            self.env().emit_event(RequestSigned {
+                signer: caller,
+                request_hash,
+            });
 
-        /// Returns `true` if the account has signed the request with hash `request_hash`
-        #[ink(message)]
-        pub fn has_signed_request(&self, account: AccountId, request_hash: HashedRequest) -> bool {
-            self.signatures.contains((request_hash, account))
-        }
+            if reaches_quorum {
+                // mark it as processed, its signatures are not needed anymore
+                self.processed_requests.insert(request_hash, &());
+                self.pending_requests.remove(request_hash);
+                self.clear_signatures(request_hash, committee_id);
+                if !matches!(self.get_highest_processed_nonce(), Some(highest) if highest >= request_nonce)
+                {
+                    self.highest_processed_nonce.set(&request_nonce);
+                }
 
-        /// Returns an error (reverts) if account is not in the currently active committee
-        #[ink(message)]
-        pub fn only_committee_member(
-            &self,
-            committee_id: CommitteeId,
-            account: AccountId,
-        ) -> Result<(), MostError> {
-            match self.is_in_committee(committee_id, account) {
-                true => Ok(()),
-                false => Err(MostError::NotInCommittee),
+                if let Some(delayed_request) = delayed_request {
+                    self.delayed_requests.insert(request_hash, &delayed_request);
+                    self.env().emit_event(RequestDelayed {
+                        request_hash,
+                        dest_token_address,
+                        executable_at: delayed_request.executable_at,
+                    });
+                }
+            } else {
+                // record vote
+                self.record_signature(request_hash, committee_id, caller);
+                self.pending_requests.insert(request_hash, &request);
             }
+
+            Ok(())
         }
 
+        /// Aggregates votes of guardians for refunding an outgoing request that can never be processed
+        ///
+        /// Once the refund reaches the quorum, the tokens are minted or unlocked back to the sender
+        /// of the request, the request is marked as refunded and its amount no longer counts against the outflow cap.
+        ///
+        /// `outgoing_request` is the request as emitted in `OutgoingRequestRecorded`, it has to match the stored hash.
+        /// Only the members of the committee the request was sent to can vote, with the threshold of that committee.
+        /// Nothing here knows about the destination chain: a guardian may only vote once the request is cancelled
+        /// on Ethereum with `cancelRequest`, which guarantees that the request is never processed there.
+        /// Otherwise the sender is paid twice.
         #[ink(message)]
-        pub fn needs_signature(
-            &self,
-            request_hash: HashedRequest,
-            account: AccountId,
-            committee_id: CommitteeId,
-        ) -> bool {
-            if !self.is_in_committee(committee_id, account) {
-                // signer not in the current committee
-                return false;
-            }
+        pub fn refund_request(
+            &mut self,
+            request_nonce: u128,
+            outgoing_request: OutgoingRequest,
+        ) -> Result<(), MostError> {
+            self.ensure_not_halted()?;
 
-            if self.processed_requests.contains(request_hash) {
-                // request already processed
-                return false;
+            if self.refunded_requests.contains(request_nonce) {
+                return Err(MostError::RequestAlreadyRefunded);
             }
 
-            if self.has_signed_request(account, request_hash) {
-                // request already signed
-                return false;
+            let request_hash = self
+                .outgoing_requests
+                .get(request_nonce)
+                .ok_or(MostError::UnknownRequest)?;
+            if request_hash != self.hash_outgoing_request(&outgoing_request) {
+                return Err(MostError::HashDoesNotMatchData);
             }
-            true
-        }
 
-        /// Is the bridge halted?
-        #[ink(message)]
-        pub fn is_halted(&self) -> Result<bool, MostError> {
-            Ok(self.data()?.is_halted)
-        }
+            let caller = self.env().caller();
+            let committee_id = outgoing_request.committee_id;
+            self.only_committee_member(committee_id, caller)?;
 
-        /// Returns the status of a given cross-chain transfer request
-        #[ink(message)]
-        pub fn request_status(&self, hashed_request: HashedRequest) -> RequestStatus {
-            if self.processed_requests.contains(hashed_request) {
-                RequestStatus::Processed
-            } else if let Some(Request { signature_count }) =
-                self.pending_requests.get(hashed_request)
+            if self
+                .refund_signatures
+                .contains((committee_id, request_nonce, caller))
             {
-                RequestStatus::Pending {
-                    collected_signatures: signature_count as u32,
+                return Err(MostError::RequestAlreadySigned);
+            }
+
+            let mut refund = self.pending_refunds.get(request_nonce).unwrap_or_default();
+
+            // record vote
+            refund.signature_count = refund
+                .signature_count
+                .checked_add(1)
+                .ok_or(MostError::Arithmetic)?;
+            self.refund_signatures
+                .insert((committee_id, request_nonce, caller), &());
+
+            self.env().emit_event(RefundSigned {
+                request_nonce,
+                signer: caller,
+            });
+
+            let signature_threshold = self
+                .signature_thresholds
+                .get(committee_id)
+                .ok_or(MostError::InvalidThreshold)?;
+
+            if refund.signature_count >= signature_threshold {
+                let OutgoingRequest {
+                    sender,
+                    src_token_address,
+                    amount,
+                    native_azero,
+                    ..
+                } = outgoing_request;
+
+                if native_azero {
+                    self.unwrap_azero_to(sender, amount)?;
+                } else if self.local_token.contains(src_token_address) {
+                    self.transfer(src_token_address, sender, amount)?;
+                } else {
+                    self.mint_to(src_token_address, sender, amount)?;
+                }
+
+                self.release_outflow(*src_token_address.as_ref(), amount);
+
+                // mark it as refunded, its votes are not needed anymore
+                self.refunded_requests.insert(request_nonce, &());
+                self.pending_refunds.remove(request_nonce);
+                for member in self.committee_members.get(committee_id).unwrap_or_default() {
+                    self.refund_signatures
+                        .remove((committee_id, request_nonce, member));
                 }
+                self.outgoing_requests.remove(request_nonce);
+
+                self.env().emit_event(RequestRefunded {
+                    request_nonce,
+                    sender,
+                    amount,
+                });
             } else {
-                RequestStatus::RequestHashNotKnown
+                self.pending_refunds.insert(request_nonce, &refund);
             }
-        }
 
-        // ---  setter txs
+            Ok(())
+        }
 
-        #[allow(clippy::too_many_arguments)]
+        /// Executes a delayed request once its delay has elapsed, its pair is not paused and it fits
+        /// within the inflow cap of its token
+        ///
+        /// Can be called by anyone
         #[ink(message)]
-        pub fn set_base_fee_constraints(
+        pub fn execute_delayed_request(
             &mut self,
-            relay_gas_usage: u128,
-            min_gas_price: u128,
-            max_gas_price: u128,
-            default_gas_price: u128,
-            gas_oracle_max_age: u64,
-            oracle_call_gas_limit: u64,
-            base_fee_buffer_percentage: u128,
+            request_hash: HashedRequest,
         ) -> Result<(), MostError> {
-            self.ensure_owner()?;
-            let mut data = self.data()?;
-            data.relay_gas_usage = relay_gas_usage;
-            data.min_gas_price = min_gas_price;
-            data.max_gas_price = max_gas_price;
-            data.default_gas_price = default_gas_price;
-            data.gas_oracle_max_age = gas_oracle_max_age;
-            data.oracle_call_gas_limit = oracle_call_gas_limit;
-            data.base_fee_buffer_percentage = base_fee_buffer_percentage;
-            self.data.set(&data);
-            Ok(())
+            self.ensure_not_halted()?;
+
+            let DelayedRequest {
+                dest_token_address,
+                amount,
+                dest_receiver_address,
+                executable_at,
+            } = self
+                .delayed_requests
+                .get(request_hash)
+                .ok_or(MostError::RequestNotDelayed)?;
+            if self.env().block_timestamp() < executable_at {
+                return Err(MostError::DelayNotElapsed);
+            }
+            let token = self.token_key(dest_token_address)?;
+            if self.get_pair_pause(token).incoming {
+                return Err(MostError::PairPaused);
+            }
+            if !self.inflow_fits(token, local_amount(dest_token_address, amount)) {
+                return Err(MostError::InflowCapExceeded);
+            }
+
+            self.delayed_requests.remove(request_hash);
+            self.execute_request(
+                request_hash,
+                dest_token_address,
+                amount,
+                dest_receiver_address,
+            )
         }
 
-        /// Removes a supported pair from bridging
+        /// Cancels a delayed request, so that it's never executed
         ///
-        /// Can only be called by the contracts owner
+        /// Can only be called by the contracts owner or an advisory, while the bridge is halted
         #[ink(message)]
-        pub fn remove_pair(&mut self, from: [u8; 32]) -> Result<(), MostError> {
-            self.ensure_owner()?;
+        pub fn cancel_delayed_request(
+            &mut self,
+            request_hash: HashedRequest,
+        ) -> Result<(), MostError> {
+            self.ensure_owner_or_advisory()?;
             self.ensure_halted()?;
-            self.supported_pairs.remove(from);
+
+            self.delayed_requests
+                .take(request_hash)
+                .ok_or(MostError::RequestNotDelayed)?;
+            self.cancelled_requests.insert(request_hash, &());
+
+            self.env().emit_event(DelayedRequestCancelled {
+                request_hash,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Mints, transfers or unwraps the tokens of a request that reached the quorum
+        ///
+        /// Changes no state of this contract when it fails, so that the request can be retried.
+        fn execute_request(
+            &mut self,
+            request_hash: HashedRequest,
+            dest_token_address: [u8; 32],
+            amount: u128,
+            dest_receiver_address: [u8; 32],
+        ) -> Result<(), MostError> {
+            let token = self.token_key(dest_token_address)?;
+            let local_amount = local_amount(dest_token_address, amount);
+            if !self.inflow_fits(token, local_amount) {
+                return Err(MostError::InflowCapExceeded);
+            }
+
+            let is_local_token = self
+                .local_token
+                .contains::<AccountId>(dest_token_address.into());
+
+            if dest_token_address == NATIVE_MARKER_ADDRESS {
+                self.unwrap_azero_to(dest_receiver_address.into(), local_amount)?;
+            } else if is_local_token {
+                self.transfer(
+                    dest_token_address.into(),
+                    dest_receiver_address.into(),
+                    amount,
+                )?;
+            } else {
+                self.mint_to(
+                    dest_token_address.into(),
+                    dest_receiver_address.into(),
+                    amount,
+                )?;
+            }
+
+            self.record_inflow(token, local_amount)?;
+            self.send_pocket_money(dest_receiver_address.into())?;
+
+            self.env().emit_event(RequestProcessed {
+                request_hash,
+                dest_token_address,
+            });
+
+            Ok(())
+        }
+
+        fn send_pocket_money(&mut self, dest_receiver_address: AccountId) -> Result<(), MostError> {
+            let mut data = self.data()?;
+            let pocket_money_to_send = self.current_pocket_money_value();
+
+            // we have nothing to send
+            if pocket_money_to_send == 0 {
+                return Ok(());
+            }
+
+            // bootstrap account with pocket money
+            if data.pocket_money_balance < pocket_money_to_send {
+                return Ok(());
+            }
+
+            // don't revert if the transfer fails
+            if self
+                .env()
+                .transfer(dest_receiver_address, pocket_money_to_send)
+                .is_ok()
+            {
+                data.pocket_money_balance = data
+                    .pocket_money_balance
+                    .checked_sub(pocket_money_to_send)
+                    .ok_or(MostError::Arithmetic)?;
+                self.data.set(&data);
+            }
+
             Ok(())
         }
 
-        /// Adds a supported pair for bridging
-        ///
-        /// Can only be called by the contracts owner
-        #[ink(message)]
-        pub fn add_pair(
-            &mut self,
-            from: [u8; 32],
-            to: [u8; 32],
-            local_token: bool,
-        ) -> Result<(), MostError> {
-            self.ensure_owner()?;
-            self.ensure_halted()?;
+        /// Calculates amount of pocket money that will be transferred to destination account upon
+        /// bridging transfer.
+        #[ink(message)]
+        pub fn current_pocket_money_value(&self) -> u128 {
+            const DEFAULT_POCKET_MONEY: u128 = 0;
+
+            let gas_price = match self.get_gas_price() {
+                Ok(Some((price, _))) => price,
+                _ => return DEFAULT_POCKET_MONEY,
+            };
+            let data = match self.data() {
+                Ok(data) => data,
+                _ => return DEFAULT_POCKET_MONEY,
+            };
+            let eth_transfer_gas_usage = match self.eth_transfer_gas_usage.get() {
+                Some(gas_usage) => gas_usage,
+                _ => return DEFAULT_POCKET_MONEY,
+            };
+            let max_pocket_money = data.max_pocket_money;
+
+            // ~75% of the gas spend on eth side but no more than allowed max
+            let pocket_money =
+                u128::min(max_pocket_money, eth_transfer_gas_usage * gas_price * 3 / 4);
+
+            u128::min(pocket_money, data.pocket_money_balance)
+        }
+
+        /// Request payout of rewards for signing & relaying cross-chain transfers.
+        ///
+        /// Reverts if `member_id` account is not in the committee with `committee_id`
+        #[ink(message)]
+        pub fn payout_rewards(
+            &mut self,
+            committee_id: CommitteeId,
+            member_id: AccountId,
+        ) -> Result<(), MostError> {
+            self.ensure_not_halted()?;
+            self.only_committee_member(committee_id, member_id)?;
+
+            let paid_out_rewards = self.get_paid_out_member_rewards(committee_id, member_id);
+
+            let outstanding_rewards =
+                self.get_outstanding_member_rewards(committee_id, member_id)?;
+
+            if outstanding_rewards.gt(&0) {
+                let payout_account = self.payout_accounts.get(member_id).unwrap_or(member_id);
+                self.env().transfer(payout_account, outstanding_rewards)?;
+
+                self.paid_out_member_rewards.insert(
+                    (member_id, committee_id),
+                    &paid_out_rewards
+                        .checked_add(outstanding_rewards)
+                        .ok_or(MostError::Arithmetic)?,
+                );
+            }
+
+            Ok(())
+        }
+
+        /// Method used to provide the contract with funds for pocket money
+        #[ink(message, payable)]
+        pub fn fund_pocket_money(&mut self) -> Result<(), MostError> {
+            let funds = self.env().transferred_value();
+            let mut data = self.data()?;
+            data.pocket_money_balance = data
+                .pocket_money_balance
+                .checked_add(funds)
+                .ok_or(MostError::Arithmetic)?;
+            self.data.set(&data);
+            Ok(())
+        }
+
+        /// Method used to change max_pocket_money value.
+        #[ink(message)]
+        pub fn set_max_pocket_money(
+            &mut self,
+            new_max_pocket_money: u128,
+        ) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            let mut data = self.data()?;
+
+            let previous_max_pocket_money = data.max_pocket_money;
+            data.max_pocket_money = new_max_pocket_money;
+            self.data.set(&data);
+
+            self.env().emit_event(MaxPocketMoneyChanged {
+                previous_max_pocket_money,
+                new_max_pocket_money,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Upgrades contract code
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: [u8; 32]) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_code(code_hash)
+        }
+
+        fn _set_code(&mut self, code_hash: [u8; 32]) -> Result<(), MostError> {
+            let previous_code_hash = self.env().own_code_hash()?;
+            set_code_hash(&code_hash)?;
+
+            self.env().emit_event(CodeHashChanged {
+                previous_code_hash,
+                new_code_hash: code_hash.into(),
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        // --- getters
+
+        /// Query average gas it takes for a confirmation of a single cross-chain transfer request on the destination chain
+        ///
+        /// Sum of the total gas usage of *all* the relay transactions divided it by the current committee size and multiplied by 1.2
+        #[ink(message)]
+        pub fn get_relay_gas_usage(&self) -> Result<u128, MostError> {
+            Ok(self.data()?.relay_gas_usage)
+        }
+
+        /// Query minimum gas price used to calculate the fee that can be charged for a cross-chain transfer request
+        #[ink(message)]
+        pub fn get_min_gas_price(&self) -> Result<u128, MostError> {
+            Ok(self.data()?.min_gas_price)
+        }
+
+        /// Query maximum gas price used to calculate the fee that can be charged for a cross-chain transfer request
+        #[ink(message)]
+        pub fn get_max_gas_price(&self) -> Result<u128, MostError> {
+            Ok(self.data()?.max_gas_price)
+        }
+
+        /// Query default gas price used to calculate the fee that is charged for a cross-chain transfer request if the gas price oracle is not available
+        #[ink(message)]
+        pub fn get_default_gas_price(&self) -> Result<u128, MostError> {
+            Ok(self.data()?.default_gas_price)
+        }
+
+        /// Query maximum time in milliseconds since last oracle update
+        #[ink(message)]
+        pub fn get_gas_oracle_max_age(&self) -> Result<u64, MostError> {
+            Ok(self.data()?.gas_oracle_max_age)
+        }
+
+        /// Query maximum limit on gas for a call to the oracle
+        #[ink(message)]
+        pub fn get_oracle_call_gas_limit(&self) -> Result<u64, MostError> {
+            Ok(self.data()?.oracle_call_gas_limit)
+        }
+
+        /// Query percentage buffer over the estimated cost
+        #[ink(message)]
+        pub fn get_base_fee_buffer_percentage(&self) -> Result<u128, MostError> {
+            Ok(self.data()?.base_fee_buffer_percentage)
+        }
+
+        /// Query gas price oracle address
+        #[ink(message)]
+        pub fn get_gas_price_oracle(&self) -> Result<Option<AccountId>, MostError> {
+            Ok(self.data()?.gas_price_oracle)
+        }
+
+        /// Query token pair
+        #[ink(message)]
+        pub fn get_supported_pair(&self, src_token: [u8; 32]) -> Option<[u8; 32]> {
+            self.supported_pairs.get(src_token)
+        }
+
+        /// Query transfer limits of a token, keyed by its address on Aleph Zero
+        #[ink(message)]
+        pub fn get_transfer_limits(&self, token: [u8; 32]) -> TransferLimits {
+            self.transfer_limits.get(token).unwrap_or_default()
+        }
+
+        /// Query the amount of the outgoing requests of a token that counts against its outflow cap
+        #[ink(message)]
+        pub fn get_outflow(&self, token: [u8; 32]) -> u128 {
+            let limits = self.get_transfer_limits(token);
+            self.outflows.get(token).unwrap_or_default().current(
+                limits.outflow_cap,
+                limits.window,
+                self.env().block_timestamp(),
+            )
+        }
+
+        /// Query the amount of the incoming requests of a token that counts against its inflow cap
+        #[ink(message)]
+        pub fn get_inflow(&self, token: [u8; 32]) -> u128 {
+            let limits = self.get_transfer_limits(token);
+            self.inflows.get(token).unwrap_or_default().current(
+                limits.inflow_cap,
+                limits.window,
+                self.env().block_timestamp(),
+            )
+        }
+
+        /// Query the amount of a token above which requests are delayed, 0 if they never are
+        #[ink(message)]
+        pub fn get_large_transfer_threshold(&self, token: [u8; 32]) -> u128 {
+            self.large_transfer_thresholds
+                .get(token)
+                .unwrap_or_default()
+        }
+
+        /// Query time in milliseconds between reaching the quorum and executing a large request
+        #[ink(message)]
+        pub fn get_large_transfer_delay(&self) -> u64 {
+            self.large_transfer_delay.get().unwrap_or_default()
+        }
+
+        /// Query if the account can cancel delayed requests
+        #[ink(message)]
+        pub fn is_advisory(&self, account: AccountId) -> bool {
+            self.advisories.contains(account)
+        }
+
+        /// Query the directions in which the transfers of a token are paused
+        #[ink(message)]
+        pub fn get_pair_pause(&self, token: [u8; 32]) -> PairPause {
+            self.paused_pairs.get(token).unwrap_or_default()
+        }
+
+        /// Query the source of the incoming requests, if the requests are bound to it
+        #[ink(message)]
+        pub fn get_request_domain(&self) -> Option<RequestDomain> {
+            self.request_domain.get()
+        }
+
+        /// Query the highest nonce of the incoming requests that reached the quorum
+        #[ink(message)]
+        pub fn get_highest_processed_nonce(&self) -> Option<u128> {
+            self.highest_processed_nonce.get()
+        }
+
+        /// Query if the outgoing request with `request_nonce` was refunded
+        #[ink(message)]
+        pub fn is_refunded(&self, request_nonce: u128) -> bool {
+            self.refunded_requests.contains(request_nonce)
+        }
+
+        /// Query payout_account for a committee member (if any)
+        #[ink(message)]
+        pub fn get_payout_account(&self, member_id: AccountId) -> Option<AccountId> {
+            self.payout_accounts.get(member_id)
+        }
+
+        /// Query wazero(azero) psp22 token contract
+        #[ink(message)]
+        pub fn get_wazero(&self) -> Option<AccountId> {
+            self.wazero.get()
+        }
+
+        /// Query weth(azero) psp22 token contract
+        #[ink(message)]
+        pub fn get_weth(&self) -> Option<AccountId> {
+            self.weth.get()
+        }
+
+        /// Query if given token originates on AlephNode chain
+        #[ink(message)]
+        pub fn is_local_token(&self, token_address: AccountId) -> bool {
+            self.local_token.contains::<AccountId>(token_address)
+        }
+
+        /// Query request nonce
+        ///
+        /// Nonce is incremented with every request
+        #[ink(message)]
+        pub fn get_request_nonce(&self) -> Result<u128, MostError> {
+            Ok(self.data()?.request_nonce)
+        }
+
+        /// Query pocket money
+        ///
+        /// An amount of the native token that is tranferred with every request
+        #[ink(message)]
+        pub fn get_max_pocket_money(&self) -> Result<Balance, MostError> {
+            Ok(self.data()?.max_pocket_money)
+        }
+
+        /// Query pocket money balance
+        ///
+        /// An amount of the native token that can be used for pocket money transfers
+        #[ink(message)]
+        pub fn get_pocket_money_balance(&self) -> Result<Balance, MostError> {
+            Ok(self.data()?.pocket_money_balance)
+        }
+
+        /// Returns current active committee id
+        #[ink(message)]
+        pub fn get_current_committee_id(&self) -> Result<u128, MostError> {
+            Ok(self.data()?.committee_id)
+        }
+
+        /// Query total rewards for this committee
+        ///
+        /// Denominated in AZERO
+        #[ink(message)]
+        pub fn get_collected_committee_rewards(&self, committee_id: CommitteeId) -> u128 {
+            self.collected_committee_rewards
+                .get(committee_id)
+                .unwrap_or_default()
+        }
+
+        /// Query already paid out committee member rewards
+        ///
+        /// Denominated in AZERO
+        #[ink(message)]
+        pub fn get_paid_out_member_rewards(
+            &self,
+            committee_id: CommitteeId,
+            member_id: AccountId,
+        ) -> u128 {
+            self.paid_out_member_rewards
+                .get((member_id, committee_id))
+                .unwrap_or_default()
+        }
+
+        /// Query outstanding committee member rewards
+        ///
+        /// The amount that can still be requested.
+        /// Denominated in AZERO
+        /// Returns 0 if the `member_id` account is not in the committee with `committee_id`
+        #[ink(message)]
+        pub fn get_outstanding_member_rewards(
+            &self,
+            committee_id: CommitteeId,
+            member_id: AccountId,
+        ) -> Result<u128, MostError> {
+            if self.is_in_committee(committee_id, member_id) {
+                let total_amount = self
+                    .get_collected_committee_rewards(committee_id)
+                    .checked_div(
+                        self.committee_sizes
+                            .get(committee_id)
+                            .ok_or(MostError::NoSuchCommittee)?,
+                    )
+                    .ok_or(MostError::Arithmetic)?;
+
+                let collected_amount = self.get_paid_out_member_rewards(committee_id, member_id);
+
+                return Ok(total_amount.saturating_sub(collected_amount));
+            }
+            Ok(0)
+        }
+
+        fn get_gas_price(&self) -> Result<Option<(Balance, u64)>, MostError> {
+            if let Some(gas_price_oracle_address) = self.data()?.gas_price_oracle {
+                let gas_price_oracle: contract_ref!(EthGasPriceOracle) =
+                    gas_price_oracle_address.into();
+
+                return Ok(
+                    match gas_price_oracle
+                        .call()
+                        .get_price()
+                        .gas_limit(self.data()?.oracle_call_gas_limit)
+                        .try_invoke()
+                    {
+                        Ok(Ok(res)) => Some(res),
+                        _ => None,
+                    },
+                );
+            }
+
+            Ok(None)
+        }
+
+        fn get_gas_price_with_limits(&self) -> Result<Balance, MostError> {
+            let gas_price = match self.get_gas_price()? {
+                Some((gas_price, timestamp)) => {
+                    if timestamp + self.data()?.gas_oracle_max_age < self.env().block_timestamp() {
+                        self.data()?.default_gas_price
+                    } else if gas_price < self.data()?.min_gas_price {
+                        self.data()?.min_gas_price
+                    } else if gas_price > self.data()?.max_gas_price {
+                        self.data()?.max_gas_price
+                    } else {
+                        gas_price
+                    }
+                }
+                _ => self.data()?.default_gas_price,
+            };
+
+            Ok(gas_price)
+        }
+
+        /// Queries a gas price oracle and returns the current base_fee charged per cross chain transfer denominated in AZERO
+        #[ink(message)]
+        pub fn get_base_fee(&self) -> Result<Balance, MostError> {
+            let gas_price = self.get_gas_price_with_limits()?;
+
+            let base_fee = gas_price
+                .checked_mul(self.data()?.relay_gas_usage)
+                .ok_or(MostError::Arithmetic)?
+                .checked_mul(100u128 + self.data()?.base_fee_buffer_percentage)
+                .ok_or(MostError::Arithmetic)?
+                .checked_div(100u128)
+                .ok_or(MostError::Arithmetic)?;
+            Ok(base_fee)
+        }
+
+        /// Quotes an outgoing transfer of `amount` of `src_token` at the current base fee
+        ///
+        /// For native AZERO, `src_token` is the wrapped AZERO and `amount` the value to bridge, as in `send_request_native_azero`.
+        /// Fails with the error the request would fail with now, for an unsupported or paused pair or an amount outside the limits.
+        #[ink(message)]
+        pub fn quote_transfer(
+            &self,
+            src_token: [u8; 32],
+            amount: u128,
+        ) -> Result<TransferQuote, MostError> {
+            let dest_token_address = self
+                .supported_pairs
+                .get(src_token)
+                .ok_or(MostError::UnsupportedPair)?;
+
+            if amount == 0 {
+                return Err(MostError::ZeroTransferAmount);
+            }
+            if self.get_pair_pause(src_token).outgoing {
+                return Err(MostError::PairPaused);
+            }
+            self.check_outflow(src_token, amount)?;
+
+            let native_azero =
+                matches!(self.wazero.get(), Some(wazero) if wazero == AccountId::from(src_token));
+            let dest_amount = match native_azero {
+                true => amount.saturating_mul(RATIO),
+                false => amount,
+            };
+
+            // once the oracle price goes stale the fee falls back to the default gas price
+            let fee_valid_until = match self.get_gas_price()? {
+                Some((_, timestamp)) => timestamp
+                    .checked_add(self.data()?.gas_oracle_max_age)
+                    .filter(|valid_until| *valid_until >= self.env().block_timestamp()),
+                None => None,
+            };
+
+            Ok(TransferQuote {
+                dest_token_address,
+                fee: self.get_base_fee()?,
+                dest_amount,
+                fee_valid_until,
+            })
+        }
+
+        /// Returns the members of the committee with `committee_id`
+        #[ink(message)]
+        pub fn get_committee(
+            &self,
+            committee_id: CommitteeId,
+        ) -> Result<Vec<AccountId>, MostError> {
+            self.committee_members
+                .get(committee_id)
+                .ok_or(MostError::NoSuchCommittee)
+        }
+
+        /// Returns the number of signatures required to reach a quorum in the committee with `committee_id`
+        #[ink(message)]
+        pub fn get_signature_threshold(
+            &self,
+            committee_id: CommitteeId,
+        ) -> Result<u128, MostError> {
+            self.signature_thresholds
+                .get(committee_id)
+                .ok_or(MostError::NoSuchCommittee)
+        }
+
+        /// Returns the block at which the committee with `committee_id` became the active one
+        #[ink(message)]
+        pub fn get_committee_activation_block(
+            &self,
+            committee_id: CommitteeId,
+        ) -> Result<BlockNumber, MostError> {
+            self.committee_activations
+                .get(committee_id)
+                .ok_or(MostError::NoSuchCommittee)
+        }
+
+        /// Query the minimum time in milliseconds between scheduling and executing an owner operation
+        ///
+        /// 0 while the timelock is off, in which case the operations can be called directly
+        #[ink(message)]
+        pub fn get_operation_delay(&self) -> u64 {
+            self.operation_delay.get().unwrap_or_default()
+        }
+
+        /// Query a scheduled owner operation
+        #[ink(message)]
+        pub fn get_scheduled_operation(&self, operation_id: u128) -> Option<ScheduledOperation> {
+            self.scheduled_operations.get(operation_id)
+        }
+
+        /// Returns whether an account is in the committee with `committee_id`
+        #[ink(message)]
+        pub fn is_in_committee(&self, committee_id: CommitteeId, account: AccountId) -> bool {
+            self.committees.contains((committee_id, account))
+        }
+
+        /// Returns `true` if the account has signed the request with hash `request_hash`
+        #[ink(message)]
+        pub fn has_signed_request(&self, account: AccountId, request_hash: HashedRequest) -> bool {
+            if self.signatures.contains((request_hash, account)) {
+                return true;
+            }
+
+            self.request_signers
+                .get(request_hash)
+                .and_then(|signers| {
+                    self.signer_bit(signers.committee_id, account)
+                        .map(|bit| signers.positions & bit != 0)
+                })
+                .unwrap_or(false)
+        }
+
+        /// Bit of `account` in the signers of a request of the committee with `committee_id`,
+        /// `None` if its position doesn't fit in the bitmap
+        fn signer_bit(&self, committee_id: CommitteeId, account: AccountId) -> Option<u128> {
+            let position = self.committee_positions.get((committee_id, account))?;
+            1u128.checked_shl(position)
+        }
+
+        /// Keeps a single bitmap per request, so the storage used doesn't grow with the committee size
+        fn record_signature(
+            &mut self,
+            request_hash: HashedRequest,
+            committee_id: CommitteeId,
+            signer: AccountId,
+        ) {
+            match self.signer_bit(committee_id, signer) {
+                Some(bit) => {
+                    let mut signers =
+                        self.request_signers
+                            .get(request_hash)
+                            .unwrap_or(RequestSigners {
+                                committee_id,
+                                positions: 0,
+                            });
+                    signers.positions |= bit;
+                    self.request_signers.insert(request_hash, &signers);
+                }
+                None => {
+                    self.signatures.insert((request_hash, signer), &());
+                }
+            }
+        }
+
+        /// Removes the signatures of a request that reached the quorum, they are not needed anymore
+        ///
+        /// The members of the committees set before their members were recorded can't be listed,
+        /// so their signatures stay.
+        fn clear_signatures(&mut self, request_hash: HashedRequest, committee_id: CommitteeId) {
+            self.request_signers.remove(request_hash);
+            for member in self.committee_members.get(committee_id).unwrap_or_default() {
+                if self.signer_bit(committee_id, member).is_none() {
+                    self.signatures.remove((request_hash, member));
+                }
+            }
+        }
+
+        /// Returns an error (reverts) if account is not in the currently active committee
+        #[ink(message)]
+        pub fn only_committee_member(
+            &self,
+            committee_id: CommitteeId,
+            account: AccountId,
+        ) -> Result<(), MostError> {
+            match self.is_in_committee(committee_id, account) {
+                true => Ok(()),
+                false => Err(MostError::NotInCommittee),
+            }
+        }
+
+        #[ink(message)]
+        pub fn needs_signature(
+            &self,
+            request_hash: HashedRequest,
+            account: AccountId,
+            committee_id: CommitteeId,
+        ) -> bool {
+            if !self.is_in_committee(committee_id, account) {
+                // signer not in the current committee
+                return false;
+            }
+
+            if self.processed_requests.contains(request_hash) {
+                // request already processed
+                return false;
+            }
+
+            if self.has_signed_request(account, request_hash) {
+                // request already signed
+                return false;
+            }
+            true
+        }
+
+        /// Is the bridge halted?
+        #[ink(message)]
+        pub fn is_halted(&self) -> Result<bool, MostError> {
+            Ok(self.data()?.is_halted)
+        }
+
+        /// Returns the status of a given cross-chain transfer request
+        #[ink(message)]
+        pub fn request_status(&self, hashed_request: HashedRequest) -> RequestStatus {
+            if let Some(DelayedRequest { executable_at, .. }) =
+                self.delayed_requests.get(hashed_request)
+            {
+                RequestStatus::Delayed { executable_at }
+            } else if self.cancelled_requests.contains(hashed_request) {
+                RequestStatus::Cancelled
+            } else if self.processed_requests.contains(hashed_request) {
+                RequestStatus::Processed
+            } else if let Some(Request { signature_count }) =
+                self.pending_requests.get(hashed_request)
+            {
+                RequestStatus::Pending {
+                    collected_signatures: signature_count as u32,
+                }
+            } else {
+                RequestStatus::RequestHashNotKnown
+            }
+        }
+
+        // ---  setter txs
+
+        /// Sets the parameters of the base fee charged for a cross-chain transfer request
+        ///
+        /// Can only be called by the contracts owner or an account with the `FeeManager` role
+        #[allow(clippy::too_many_arguments)]
+        #[ink(message)]
+        pub fn set_base_fee_constraints(
+            &mut self,
+            relay_gas_usage: u128,
+            min_gas_price: u128,
+            max_gas_price: u128,
+            default_gas_price: u128,
+            gas_oracle_max_age: u64,
+            oracle_call_gas_limit: u64,
+            base_fee_buffer_percentage: u128,
+        ) -> Result<(), MostError> {
+            self.ensure_owner_or_role(Role::FeeManager)?;
+            let mut data = self.data()?;
+            let previous_constraints = data.base_fee_constraints();
+            data.relay_gas_usage = relay_gas_usage;
+            data.min_gas_price = min_gas_price;
+            data.max_gas_price = max_gas_price;
+            data.default_gas_price = default_gas_price;
+            data.gas_oracle_max_age = gas_oracle_max_age;
+            data.oracle_call_gas_limit = oracle_call_gas_limit;
+            data.base_fee_buffer_percentage = base_fee_buffer_percentage;
+            self.data.set(&data);
+
+            self.env().emit_event(BaseFeeConstraintsChanged {
+                previous_constraints,
+                new_constraints: data.base_fee_constraints(),
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Removes a supported pair from bridging
+        ///
+        /// Can only be called by the contracts owner, while the bridge is halted or the pair is paused in both directions
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn remove_pair(&mut self, from: [u8; 32]) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._remove_pair(from)
+        }
+
+        fn _remove_pair(&mut self, from: [u8; 32]) -> Result<(), MostError> {
+            self.ensure_halted_or_pair_paused(from)?;
+            let previous_to = self.supported_pairs.get(from);
+            self.supported_pairs.remove(from);
+
+            self.env().emit_event(SupportedPairChanged {
+                from,
+                previous_to,
+                new_to: None,
+                local_token: self.local_token.contains::<AccountId>(from.into()),
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Adds a supported pair for bridging
+        ///
+        /// Can only be called by the contracts owner, while the bridge is halted or the pair is paused in both directions
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn add_pair(
+            &mut self,
+            from: [u8; 32],
+            to: [u8; 32],
+            local_token: bool,
+        ) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._add_pair(from, to, local_token)
+        }
+
+        fn _add_pair(
+            &mut self,
+            from: [u8; 32],
+            to: [u8; 32],
+            local_token: bool,
+        ) -> Result<(), MostError> {
+            self.ensure_halted_or_pair_paused(from)?;
+
+            // Check if MOST has mint permission to the PSP22 token
+            if local_token {
+                self.local_token.insert::<AccountId, ()>(from.into(), &());
+            } else {
+                let psp22_address: AccountId = from.into();
+                let psp22: ink::contract_ref!(Mintable) = psp22_address.into();
+                if psp22.minter() != self.env().account_id() {
+                    return Err(MostError::NoMintPermission);
+                }
+            }
+
+            let previous_to = self.supported_pairs.get(from);
+            self.supported_pairs.insert(from, &to);
+
+            self.env().emit_event(SupportedPairChanged {
+                from,
+                previous_to,
+                new_to: Some(to),
+                local_token,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Sets the transfer limits of a token, keyed by its address on Aleph Zero
+        ///
+        /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set, pause the pair to stop its transfers right away
+        #[ink(message)]
+        pub fn set_transfer_limits(
+            &mut self,
+            token: [u8; 32],
+            limits: TransferLimits,
+        ) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_transfer_limits(token, limits)
+        }
+
+        fn _set_transfer_limits(
+            &mut self,
+            token: [u8; 32],
+            limits: TransferLimits,
+        ) -> Result<(), MostError> {
+            if !limits.is_valid() {
+                return Err(MostError::InvalidTransferLimits);
+            }
+
+            let previous_limits = self.transfer_limits.get(token).unwrap_or_default();
+            self.transfer_limits.insert(token, &limits);
+
+            self.env().emit_event(TransferLimitsChanged {
+                token,
+                previous_limits,
+                new_limits: limits,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Sets the amount of a token above which requests are delayed, 0 to never delay them
+        ///
+        /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn set_large_transfer_threshold(
+            &mut self,
+            token: [u8; 32],
+            threshold: u128,
+        ) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_large_transfer_threshold(token, threshold)
+        }
+
+        fn _set_large_transfer_threshold(
+            &mut self,
+            token: [u8; 32],
+            threshold: u128,
+        ) -> Result<(), MostError> {
+            let previous_threshold = self.get_large_transfer_threshold(token);
+            self.large_transfer_thresholds.insert(token, &threshold);
+
+            self.env().emit_event(LargeTransferThresholdChanged {
+                token,
+                previous_threshold,
+                new_threshold: threshold,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Sets the time in milliseconds between reaching the quorum and executing a large request
+        ///
+        /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn set_large_transfer_delay(&mut self, delay: u64) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_large_transfer_delay(delay)
+        }
+
+        fn _set_large_transfer_delay(&mut self, delay: u64) -> Result<(), MostError> {
+            let previous_delay = self.get_large_transfer_delay();
+            self.large_transfer_delay.set(&delay);
+
+            self.env().emit_event(LargeTransferDelayChanged {
+                previous_delay,
+                new_delay: delay,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Allows or disallows an account to cancel delayed requests
+        ///
+        /// Can only be called by the contracts owner
+        ///
+        /// Allowing an account has to be scheduled with `schedule_operation` once the operation delay is set,
+        /// disallowing it stays instant
+        #[ink(message)]
+        pub fn set_advisory(
+            &mut self,
+            account: AccountId,
+            is_advisory: bool,
+        ) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            if is_advisory {
+                self.ensure_not_timelocked()?;
+            }
+            self._set_advisory(account, is_advisory)
+        }
+
+        fn _set_advisory(
+            &mut self,
+            account: AccountId,
+            is_advisory: bool,
+        ) -> Result<(), MostError> {
+            let previous_state = self.is_advisory(account);
+            if is_advisory {
+                self.advisories.insert(account, &());
+            } else {
+                self.advisories.remove(account);
+            }
+
+            self.env().emit_event(AdvisoryChanged {
+                account,
+                previous_state,
+                new_state: is_advisory,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Set weth(azero) psp22 token contract
+        ///
+        /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn set_weth(&mut self, weth_address: AccountId) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_weth(weth_address)
+        }
+
+        fn _set_weth(&mut self, weth_address: AccountId) -> Result<(), MostError> {
+            self.ensure_halted()?;
+            let previous_address = self.weth.get();
+            self.weth.set(&weth_address);
+
+            self.env().emit_event(WethChanged {
+                previous_address,
+                new_address: weth_address,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Set wazero(azero) psp22 token contract
+        ///
+        /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn set_wazero(&mut self, wazero_address: AccountId) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_wazero(wazero_address)
+        }
+
+        fn _set_wazero(&mut self, wazero_address: AccountId) -> Result<(), MostError> {
+            self.ensure_halted()?;
+            let previous_address = self.wazero.get();
+            self.wazero.set(&wazero_address);
+
+            self.env().emit_event(WazeroChanged {
+                previous_address,
+                new_address: wazero_address,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Sets address of the account that receives rewards on behalf of the committee member.
+        ///
+        /// Can only be called by an account that was a committee member in `committee_id`.
+        /// All the unpaid rewards will then be paid out to this account address when `payout_request` is called.
+        /// Comittee members that have set the `payout_account` do not need to call this method again when the committee changes, unless they want to change the payouts account.
+        #[ink(message)]
+        pub fn set_payout_account(
+            &mut self,
+            committee_id: CommitteeId,
+            account: AccountId,
+        ) -> Result<(), MostError> {
+            let caller = self.env().caller();
+            self.only_committee_member(committee_id, caller)?;
+            self.payout_accounts.insert(caller, &account);
+
+            self.env().emit_event(PayoutAccountSet {
+                member: caller,
+                account,
+            });
+
+            Ok(())
+        }
+
+        /// Sets address of the gas price oracle
+        ///
+        /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn set_gas_price_oracle(
+            &mut self,
+            gas_price_oracle: AccountId,
+        ) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_gas_price_oracle(gas_price_oracle)
+        }
+
+        fn _set_gas_price_oracle(&mut self, gas_price_oracle: AccountId) -> Result<(), MostError> {
+            let mut data = self.data()?;
+            let previous_oracle = data.gas_price_oracle;
+            data.gas_price_oracle = Some(gas_price_oracle);
+            self.data.set(&data);
+
+            self.env().emit_event(GasPriceOracleChanged {
+                previous_oracle,
+                new_oracle: gas_price_oracle,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Sets an `eth_transfer_gas_usage`.
+        ///
+        /// Can only be called by the contracts owner
+        #[ink(message)]
+        pub fn set_eth_transfer_gas_usage(
+            &mut self,
+            new_eth_transfer_gas_usage: u128,
+        ) -> Result<(), crate::most::MostError> {
+            self.ensure_owner()?;
+            let previous_gas_usage = self.eth_transfer_gas_usage.get().unwrap_or_default();
+            self.eth_transfer_gas_usage.set(&new_eth_transfer_gas_usage);
+
+            self.env().emit_event(EthTransferGasUsageChanged {
+                previous_gas_usage,
+                new_gas_usage: new_eth_transfer_gas_usage,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Change the committee and increase committe id
+        /// Can only be called by the contracts owner
+        ///
+        /// Changing the entire set is the ONLY way of upgrading the committee
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn set_committee(
+            &mut self,
+            committee: Vec<AccountId>,
+            signature_threshold: u128,
+        ) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_committee(committee, signature_threshold)
+        }
+
+        fn _set_committee(
+            &mut self,
+            committee: Vec<AccountId>,
+            signature_threshold: u128,
+        ) -> Result<(), MostError> {
+            self.ensure_halted()?;
+            Self::check_committee(&committee, signature_threshold)?;
+
+            let mut data = self.data()?;
+
+            let committee_id = data.committee_id + 1;
+            self.committee_sizes
+                .insert(committee_id, &(committee.len() as u128));
+            let mut committee_set = Mapping::new();
+            committee.iter().for_each(|account| {
+                committee_set.insert((committee_id, *account), &());
+            });
+
+            self.committees = committee_set;
+            data.committee_id = committee_id;
+
+            self.data.set(&data);
+
+            self.signature_thresholds
+                .insert(committee_id, &signature_threshold);
+
+            let activated_at = self.env().block_number();
+            self.committee_members.insert(committee_id, &committee);
+            committee
+                .iter()
+                .enumerate()
+                .for_each(|(position, account)| {
+                    self.committee_positions
+                        .insert((committee_id, *account), &(position as u32));
+                });
+            self.committee_activations
+                .insert(committee_id, &activated_at);
+
+            self.env().emit_event(CommitteeUpdated {
+                committee_id,
+                members: committee,
+                signature_threshold,
+                activated_at,
+            });
+
+            Ok(())
+        }
+
+        /// Halt/resume the bridge contract
+        ///
+        /// Can only be called by the contracts owner, or by an account with the `Pauser` role to halt it
+        #[ink(message)]
+        pub fn set_halted(&mut self, new_state: bool) -> Result<(), MostError> {
+            if new_state {
+                self.ensure_owner_or_role(Role::Pauser)?;
+            } else {
+                self.ensure_owner()?;
+            }
+
+            let mut data = self.data()?;
+            let previous_state = data.is_halted;
+
+            if new_state != previous_state {
+                data.is_halted = new_state;
+
+                self.data.set(&data);
+                self.env().emit_event(HaltedStateChanged {
+                    previous_state,
+                    new_state,
+                    caller: self.env().caller(),
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Pauses or resumes the transfers of a token in each direction
+        ///
+        /// Can only be called by the contracts owner, or by an account with the `Pauser` role to pause them
+        #[ink(message)]
+        pub fn set_pair_pause(
+            &mut self,
+            token: [u8; 32],
+            pause: PairPause,
+        ) -> Result<(), MostError> {
+            let previous_pause = self.get_pair_pause(token);
+            let resumes = (previous_pause.outgoing && !pause.outgoing)
+                || (previous_pause.incoming && !pause.incoming);
+            if resumes {
+                self.ensure_owner()?;
+            } else {
+                self.ensure_owner_or_role(Role::Pauser)?;
+            }
+
+            if pause == PairPause::default() {
+                self.paused_pairs.remove(token);
+            } else {
+                self.paused_pairs.insert(token, &pause);
+            }
+
+            self.env().emit_event(PairPauseChanged {
+                token,
+                previous_pause,
+                new_pause: pause,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Binds the hashes of the incoming requests from `domain.from_nonce` on to their source and to
+        /// this contract, so that they can't be replayed on another deployment
+        ///
+        /// Can only be called by the contracts owner, once, while the bridge is halted. The domain never
+        /// changes afterwards, as the requests around a change could be processed twice otherwise.
+        /// For the same reason `domain.from_nonce` has to be above the nonce of every request that
+        /// already reached the quorum. Only the requests processed since the contract tracks their
+        /// nonces are checked, see `get_highest_processed_nonce`.
+        #[ink(message)]
+        pub fn set_request_domain(&mut self, domain: RequestDomain) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_halted()?;
+            if self.request_domain.get().is_some() {
+                return Err(MostError::RequestDomainAlreadySet);
+            }
+            if matches!(self.get_highest_processed_nonce(), Some(highest) if domain.from_nonce <= highest)
+            {
+                return Err(MostError::NonceAlreadyProcessed);
+            }
+            if domain.src_contract == ZERO_ADDRESS {
+                return Err(MostError::ZeroAddress);
+            }
+
+            self.request_domain.set(&domain);
+
+            self.env().emit_event(RequestDomainSet {
+                domain,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Transfer PSP22 tokens from the bridge contract to a given account.
+        ///
+        /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn recover_psp22(
+            &mut self,
+            token: AccountId,
+            receiver: AccountId,
+            amount: u128,
+        ) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._recover_psp22(token, receiver, amount)
+        }
+
+        fn _recover_psp22(
+            &mut self,
+            token: AccountId,
+            receiver: AccountId,
+            amount: u128,
+        ) -> Result<(), MostError> {
+            let mut psp22: ink::contract_ref!(PSP22) = token.into();
+            psp22.transfer(receiver, amount, vec![])?;
+
+            self.env().emit_event(FundsRecovered {
+                token: Some(token),
+                receiver,
+                amount,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Transfer AZERO tokens from the bridge contract to a given account.
+        ///
+        /// Can only be called by the contracts owner
+        ///
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set
+        #[ink(message)]
+        pub fn recover_azero(
+            &mut self,
+            receiver: AccountId,
+            amount: u128,
+        ) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._recover_azero(receiver, amount)
+        }
+
+        fn _recover_azero(&mut self, receiver: AccountId, amount: u128) -> Result<(), MostError> {
+            self.env().transfer(receiver, amount)?;
+
+            self.env().emit_event(FundsRecovered {
+                token: None,
+                receiver,
+                amount,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Turns the timelock on, after which the critical owner operations have to be scheduled
+        /// with `schedule_operation` and can only be executed after `delay` milliseconds
+        ///
+        /// Can only be called by the contracts owner. Once the timelock is on, the delay can only be
+        /// changed with a scheduled `SetOperationDelay` operation and never turned off
+        #[ink(message)]
+        pub fn set_operation_delay(&mut self, delay: u64) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.ensure_not_timelocked()?;
+            self._set_operation_delay(delay)
+        }
+
+        fn _set_operation_delay(&mut self, delay: u64) -> Result<(), MostError> {
+            if delay < MIN_OPERATION_DELAY {
+                return Err(MostError::InvalidOperationDelay);
+            }
+
+            let previous_delay = self.get_operation_delay();
+            self.operation_delay.set(&delay);
+
+            self.env().emit_event(OperationDelayChanged {
+                previous_delay,
+                new_delay: delay,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Schedules an owner operation, it can be executed with `execute_operation` once the operation delay passes
+        ///
+        /// Can only be called by the contracts owner
+        #[ink(message)]
+        pub fn schedule_operation(&mut self, operation: AdminOperation) -> Result<u128, MostError> {
+            self.ensure_owner()?;
+
+            let operation_id = self.operation_nonce.get().unwrap_or_default();
+            self.operation_nonce
+                .set(&operation_id.checked_add(1).ok_or(MostError::Arithmetic)?);
+
+            let executable_at = self
+                .env()
+                .block_timestamp()
+                .checked_add(self.get_operation_delay())
+                .ok_or(MostError::Arithmetic)?;
+            self.scheduled_operations.insert(
+                operation_id,
+                &ScheduledOperation {
+                    operation: operation.clone(),
+                    executable_at,
+                },
+            );
+
+            self.env().emit_event(OperationScheduled {
+                operation_id,
+                operation,
+                executable_at,
+            });
+
+            Ok(operation_id)
+        }
+
+        /// Cancels a scheduled owner operation
+        ///
+        /// Can only be called by the contracts owner
+        #[ink(message)]
+        pub fn cancel_operation(&mut self, operation_id: u128) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            self.scheduled_operations
+                .take(operation_id)
+                .ok_or(MostError::UnknownOperation)?;
+
+            self.env().emit_event(OperationCancelled {
+                operation_id,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Executes a scheduled owner operation whose delay has passed
+        ///
+        /// Can only be called by the contracts owner
+        #[ink(message)]
+        pub fn execute_operation(&mut self, operation_id: u128) -> Result<(), MostError> {
+            self.ensure_owner()?;
+            let scheduled = self
+                .get_scheduled_operation(operation_id)
+                .ok_or(MostError::UnknownOperation)?;
+            if self.env().block_timestamp() < scheduled.executable_at {
+                return Err(MostError::DelayNotElapsed);
+            }
+            self.scheduled_operations.remove(operation_id);
+
+            match scheduled.operation {
+                AdminOperation::SetCode { code_hash } => self._set_code(code_hash),
+                AdminOperation::SetCommittee {
+                    committee,
+                    signature_threshold,
+                } => self._set_committee(committee, signature_threshold),
+                AdminOperation::AddPair {
+                    from,
+                    to,
+                    local_token,
+                } => self._add_pair(from, to, local_token),
+                AdminOperation::RecoverPsp22 {
+                    token,
+                    receiver,
+                    amount,
+                } => self._recover_psp22(token, receiver, amount),
+                AdminOperation::RecoverAzero { receiver, amount } => {
+                    self._recover_azero(receiver, amount)
+                }
+                AdminOperation::SetOperationDelay { delay } => self._set_operation_delay(delay),
+                AdminOperation::RemovePair { from } => self._remove_pair(from),
+                AdminOperation::SetTransferLimits { token, limits } => {
+                    self._set_transfer_limits(token, limits)
+                }
+                AdminOperation::SetLargeTransferThreshold { token, threshold } => {
+                    self._set_large_transfer_threshold(token, threshold)
+                }
+                AdminOperation::SetLargeTransferDelay { delay } => {
+                    self._set_large_transfer_delay(delay)
+                }
+                AdminOperation::AddAdvisory { account } => self._set_advisory(account, true),
+                AdminOperation::SetWeth { weth_address } => self._set_weth(weth_address),
+                AdminOperation::SetWazero { wazero_address } => self._set_wazero(wazero_address),
+                AdminOperation::SetGasPriceOracle { gas_price_oracle } => {
+                    self._set_gas_price_oracle(gas_price_oracle)
+                }
+                AdminOperation::GrantRole { role, account } => {
+                    let owner = self.env().caller();
+                    Ok(self._grant_role(owner, owner, role, account)?)
+                }
+            }?;
+
+            self.env().emit_event(OperationExecuted {
+                operation_id,
+                caller: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        // ---  helper functions
+
+        fn _grant_role(
+            &mut self,
+            caller: AccountId,
+            admin: AccountId,
+            role: Role,
+            account: AccountId,
+        ) -> AccessControlResult<()> {
+            self.roles.grant_role(caller, admin, role, account)?;
+            self.env().emit_event(RoleGranted {
+                role,
+                account,
+                caller,
+            });
+            Ok(())
+        }
+
+        fn ensure_not_timelocked(&self) -> Result<(), MostError> {
+            match self.get_operation_delay() {
+                0 => Ok(()),
+                _ => Err(MostError::TimelockRequired),
+            }
+        }
+
+        fn ensure_halted_or_pair_paused(&self, token: [u8; 32]) -> Result<(), MostError> {
+            let pause = self.get_pair_pause(token);
+            if pause.outgoing && pause.incoming {
+                return Ok(());
+            }
+            self.ensure_halted()
+        }
+
+        fn ensure_owner_or_role(&self, role: Role) -> Result<(), MostError> {
+            if self.has_role(role, self.env().caller()) {
+                return Ok(());
+            }
+            Ok(self.ensure_owner()?)
+        }
+
+        fn ensure_halted(&self) -> Result<(), MostError> {
+            match self.is_halted()? {
+                true => Ok(()),
+                false => Err(MostError::HaltRequired),
+            }
+        }
+
+        fn ensure_not_halted(&self) -> Result<(), MostError> {
+            match self.is_halted()? {
+                true => Err(MostError::IsHalted),
+                false => Ok(()),
+            }
+        }
+
+        fn ensure_owner_or_advisory(&self) -> Result<(), MostError> {
+            if self.is_advisory(self.env().caller()) {
+                return Ok(());
+            }
+            Ok(self.ensure_owner()?)
+        }
+
+        fn check_committee(committee: &[AccountId], threshold: u128) -> Result<(), MostError> {
+            if threshold == 0 || committee.len().lt(&(threshold as usize)) {
+                return Err(MostError::InvalidThreshold);
+            }
+
+            for i in 0..committee.len() {
+                for j in i + 1..committee.len() {
+                    if committee[i] == committee[j] {
+                        return Err(MostError::DuplicateCommitteeMember);
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Mints the specified amount of token to the designated account
+        ///
+        /// Most contract needs to have a Minter role on the token contract
+        fn mint_to(&self, token: AccountId, to: AccountId, amount: u128) -> Result<(), PSP22Error> {
+            let mut psp22: ink::contract_ref!(Mintable) = token.into();
+            psp22.mint(to, amount)
+        }
+
+        /// Transfers the specified amount of token to the designated account
+        fn transfer(
+            &self,
+            token: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<(), PSP22Error> {
+            let mut psp22: ink::contract_ref!(PSP22) = token.into();
+            psp22.transfer(to, amount, vec![])
+        }
+
+        /// Unwraps the specified amount of AZERO locked in the bridge contract and transfers it to the designated account
+        fn unwrap_azero_to(&self, to: AccountId, amount: u128) -> Result<(), MostError> {
+            let wrapped_azero_address = self.wazero.get().ok_or(MostError::WrappedAzeroNotSet)?;
+            let mut wrapped_azero: ink::contract_ref!(WrappedAZERO) = wrapped_azero_address.into();
+
+            wrapped_azero.withdraw(amount)?;
+            if let Err(why) = self.env().transfer(to, amount) {
+                // wrap the AZERO back, the caller may not revert
+                wrapped_azero
+                    .call_mut()
+                    .deposit()
+                    .transferred_value(amount)
+                    .invoke()?;
+                return Err(why.into());
+            }
+            Ok(())
+        }
+
+        /// Burn the specified amount of token from the designated account
+        ///
+        /// Most contract needs to have a Burner role on the token contract
+        fn burn_from(
+            &self,
+            token: AccountId,
+            from: AccountId,
+            amount: u128,
+        ) -> Result<(), PSP22Error> {
+            self.transfer_from(token, from, amount)?;
+
+            let mut psp22_burnable: ink::contract_ref!(Burnable) = token.into();
+            psp22_burnable.burn(amount)
+        }
+
+        /// Transfers the specified amount of token from the designated account
+        fn transfer_from(
+            &self,
+            token: AccountId,
+            from: AccountId,
+            amount: u128,
+        ) -> Result<(), PSP22Error> {
+            let mut psp22: ink::contract_ref!(PSP22) = token.into();
+            psp22.transfer_from(from, self.env().account_id(), amount, vec![])
+        }
+
+        /// The address on Aleph Zero of the token a request is for, native AZERO being wrapped
+        fn token_key(&self, dest_token_address: [u8; 32]) -> Result<[u8; 32], MostError> {
+            if dest_token_address == NATIVE_MARKER_ADDRESS {
+                let wrapped_azero_address =
+                    self.wazero.get().ok_or(MostError::WrappedAzeroNotSet)?;
+                return Ok(*wrapped_azero_address.as_ref());
+            }
+            Ok(dest_token_address)
+        }
+
+        /// Checks an outgoing request against the limits of the token and accounts for it
+        fn record_outflow(&mut self, token: [u8; 32], amount: u128) -> Result<(), MostError> {
+            if let Some(outflow) = self.check_outflow(token, amount)? {
+                self.outflows.insert(token, &outflow);
+            }
+
+            Ok(())
+        }
+
+        /// Checks an outgoing request against the limits of the token and returns the outflow
+        /// including it, `None` if the token has no outflow cap
+        fn check_outflow(&self, token: [u8; 32], amount: u128) -> Result<Option<Flow>, MostError> {
+            let limits = self.get_transfer_limits(token);
+            if amount < limits.min_amount {
+                return Err(MostError::AmountBelowMinimum);
+            }
+            if limits.max_amount > 0 && amount > limits.max_amount {
+                return Err(MostError::AmountAboveMaximum);
+            }
+
+            if limits.outflow_cap == 0 {
+                return Ok(None);
+            }
+
+            self.outflows
+                .get(token)
+                .unwrap_or_default()
+                .add(
+                    amount,
+                    limits.outflow_cap,
+                    limits.window,
+                    self.env().block_timestamp(),
+                )
+                .map(Some)
+                .ok_or(MostError::OutflowCapExceeded)
+        }
+
+        /// Releases the outflow of an outgoing request that was refunded
+        fn release_outflow(&mut self, token: [u8; 32], amount: u128) {
+            let limits = self.get_transfer_limits(token);
+            if limits.outflow_cap > 0 {
+                let outflow = self.outflows.get(token).unwrap_or_default().sub(
+                    amount,
+                    limits.outflow_cap,
+                    limits.window,
+                    self.env().block_timestamp(),
+                );
+                self.outflows.insert(token, &outflow);
+            }
+        }
+
+        /// Whether an incoming request fits within the inflow cap of the token
+        fn inflow_fits(&self, token: [u8; 32], amount: u128) -> bool {
+            let limits = self.get_transfer_limits(token);
+            limits.inflow_cap == 0
+                || self
+                    .inflows
+                    .get(token)
+                    .unwrap_or_default()
+                    .add(
+                        amount,
+                        limits.inflow_cap,
+                        limits.window,
+                        self.env().block_timestamp(),
+                    )
+                    .is_some()
+        }
+
+        /// Checks an incoming request that is being executed against the inflow cap of the token
+        /// and accounts for it
+        fn record_inflow(&mut self, token: [u8; 32], amount: u128) -> Result<(), MostError> {
+            let limits = self.get_transfer_limits(token);
+            if limits.inflow_cap > 0 {
+                let inflow = self
+                    .inflows
+                    .get(token)
+                    .unwrap_or_default()
+                    .add(
+                        amount,
+                        limits.inflow_cap,
+                        limits.window,
+                        self.env().block_timestamp(),
+                    )
+                    .ok_or(MostError::InflowCapExceeded)?;
+                self.inflows.insert(token, &inflow);
+            }
+
+            Ok(())
+        }
+
+        fn data(&self) -> Result<Data, MostError> {
+            self.data.get().ok_or(MostError::CorruptedStorage)
+        }
+
+        fn ownable_data(&self) -> Result<Ownable2StepData, Ownable2StepError> {
+            self.ownable_data
+                .get()
+                .ok_or(Ownable2StepError::Custom("CorruptedStorage".into()))
+        }
+    }
+
+    impl Ownable2Step for Most {
+        #[ink(message)]
+        fn get_owner(&self) -> Ownable2StepResult<AccountId> {
+            self.ownable_data()?.get_owner()
+        }
+
+        #[ink(message)]
+        fn get_pending_owner(&self) -> Ownable2StepResult<AccountId> {
+            self.ownable_data()?.get_pending_owner()
+        }
+
+        #[ink(message)]
+        fn transfer_ownership(&mut self, new_owner: AccountId) -> Ownable2StepResult<()> {
+            let mut ownable_data = self.ownable_data()?;
+            ownable_data.transfer_ownership(self.env().caller(), new_owner)?;
+            self.ownable_data.set(&ownable_data);
+            self.env()
+                .emit_event(TransferOwnershipInitiated { new_owner });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn accept_ownership(&mut self) -> Ownable2StepResult<()> {
+            let new_owner = self.env().caller();
+            let mut ownable_data = self.ownable_data()?;
+            ownable_data.accept_ownership(new_owner)?;
+            self.ownable_data.set(&ownable_data);
+            self.env()
+                .emit_event(TransferOwnershipAccepted { new_owner });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn ensure_owner(&self) -> Ownable2StepResult<()> {
+            self.ownable_data()?.ensure_owner(self.env().caller())
+        }
+    }
+
+    impl AccessControl for Most {
+        #[ink(message)]
+        fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.roles.has_role(role, account)
+        }
+
+        /// Has to be scheduled with `schedule_operation` once the operation delay is set, revoking a role stays instant
+        #[ink(message)]
+        fn grant_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            let admin = <Self as Ownable2Step>::get_owner(self)
+                .map_err(|_| AccessControlError::CallerNotAdmin(caller))?;
+            if caller == admin && self.get_operation_delay() > 0 {
+                return Err(AccessControlError::TimelockRequired);
+            }
+
+            self._grant_role(caller, admin, role, account)
+        }
+
+        #[ink(message)]
+        fn revoke_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            let admin = <Self as Ownable2Step>::get_owner(self)
+                .map_err(|_| AccessControlError::CallerNotAdmin(caller))?;
+
+            self.roles.revoke_role(caller, admin, role, account)?;
+            self.env().emit_event(RoleRevoked {
+                role,
+                account,
+                caller,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn renounce_role(&mut self, role: Role) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            self.roles.renounce_role(caller, role)?;
+            self.env().emit_event(RoleRevoked {
+                role,
+                account: caller,
+                caller,
+            });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ink::env::{
+            test::{self, default_accounts, set_caller},
+            DefaultEnvironment, Environment,
+        };
+
+        use super::*;
+
+        const THRESHOLD: u128 = 3;
+        const MAX_POCKET_MONEY: Balance = 1000000000000;
+        const RELAY_GAS_USAGE: u128 = 50000;
+        const MIN_FEE: Balance = 1000000000000;
+        const MAX_FEE: Balance = 100000000000000;
+        const DEFAULT_FEE: Balance = 30000000000000;
+        const GAS_ORACLE_MAX_AGE: u64 = 86400000;
+        const ORACLE_CALL_GAS_LIMIT: u64 = 2000000000;
+        const BASE_FEE_BUFFER_PERCENTAGE: u128 = 20;
+        const ETH_GAS_USAGE: u128 = 60_000;
+
+        type DefEnv = DefaultEnvironment;
+        type AccountId = <DefEnv as Environment>::AccountId;
+
+        fn guardian_accounts() -> Vec<AccountId> {
+            let accounts = default_accounts::<DefEnv>();
+            vec![
+                accounts.bob,
+                accounts.charlie,
+                accounts.django,
+                accounts.eve,
+                accounts.frank,
+            ]
+        }
+
+        #[ink::test]
+        fn new_fails_on_zero_threshold() {
+            let alice = default_accounts::<DefEnv>().alice;
+            set_caller::<DefEnv>(alice);
+
+            assert_eq!(
+                Most::new(
+                    guardian_accounts(),
+                    0,
+                    MAX_POCKET_MONEY,
+                    RELAY_GAS_USAGE,
+                    MIN_FEE,
+                    MAX_FEE,
+                    DEFAULT_FEE,
+                    GAS_ORACLE_MAX_AGE,
+                    ORACLE_CALL_GAS_LIMIT,
+                    BASE_FEE_BUFFER_PERCENTAGE,
+                    None,
+                    alice,
+                    ETH_GAS_USAGE
+                )
+                .expect_err("Threshold is zero, instantiation should fail."),
+                MostError::InvalidThreshold
+            );
+        }
+
+        #[ink::test]
+        fn new_fails_on_threshold_large_than_guardians() {
+            let alice = default_accounts::<DefEnv>().alice;
+            set_caller::<DefEnv>(alice);
+
+            assert_eq!(
+                Most::new(
+                    guardian_accounts(),
+                    (guardian_accounts().len() + 1) as u128,
+                    MAX_POCKET_MONEY,
+                    RELAY_GAS_USAGE,
+                    MIN_FEE,
+                    MAX_FEE,
+                    DEFAULT_FEE,
+                    GAS_ORACLE_MAX_AGE,
+                    ORACLE_CALL_GAS_LIMIT,
+                    BASE_FEE_BUFFER_PERCENTAGE,
+                    None,
+                    alice,
+                    ETH_GAS_USAGE
+                )
+                .expect_err("Threshold is larger than guardians, instantiation should fail."),
+                MostError::InvalidThreshold
+            );
+        }
+
+        #[ink::test]
+        fn new_sets_caller_as_owner() {
+            let alice = default_accounts::<DefEnv>().alice;
+            set_caller::<DefEnv>(alice);
+
+            let most = Most::new(
+                guardian_accounts(),
+                THRESHOLD,
+                MAX_POCKET_MONEY,
+                RELAY_GAS_USAGE,
+                MIN_FEE,
+                MAX_FEE,
+                DEFAULT_FEE,
+                GAS_ORACLE_MAX_AGE,
+                ORACLE_CALL_GAS_LIMIT,
+                BASE_FEE_BUFFER_PERCENTAGE,
+                None,
+                alice,
+                ETH_GAS_USAGE,
+            )
+            .expect("Threshold is valid.");
+
+            assert_eq!(most.ensure_owner(), Ok(()));
+            set_caller::<DefEnv>(guardian_accounts()[0]);
+            assert_eq!(
+                most.ensure_owner(),
+                Err(Ownable2StepError::CallerNotOwner(guardian_accounts()[0]))
+            );
+        }
+
+        #[ink::test]
+        fn new_sets_correct_guardians() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+
+            let most = Most::new(
+                guardian_accounts(),
+                THRESHOLD,
+                MAX_POCKET_MONEY,
+                RELAY_GAS_USAGE,
+                MIN_FEE,
+                MAX_FEE,
+                DEFAULT_FEE,
+                GAS_ORACLE_MAX_AGE,
+                ORACLE_CALL_GAS_LIMIT,
+                BASE_FEE_BUFFER_PERCENTAGE,
+                None,
+                accounts.alice,
+                ETH_GAS_USAGE,
+            )
+            .expect("Threshold is valid.");
+
+            for account in guardian_accounts() {
+                assert!(most.is_in_committee(most.get_current_committee_id().unwrap(), account));
+            }
+            assert!(!most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.alice));
+        }
+
+        #[ink::test]
+        fn set_owner_works() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+
+            let mut most = Most::new(
+                guardian_accounts(),
+                THRESHOLD,
+                MAX_POCKET_MONEY,
+                RELAY_GAS_USAGE,
+                MIN_FEE,
+                MAX_FEE,
+                DEFAULT_FEE,
+                GAS_ORACLE_MAX_AGE,
+                ORACLE_CALL_GAS_LIMIT,
+                BASE_FEE_BUFFER_PERCENTAGE,
+                None,
+                accounts.alice,
+                ETH_GAS_USAGE,
+            )
+            .expect("Threshold is valid.");
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(
+                most.ensure_owner(),
+                Err(Ownable2StepError::CallerNotOwner(accounts.bob))
+            );
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(most.ensure_owner(), Ok(()));
+            assert_eq!(most.transfer_ownership(accounts.bob), Ok(()));
+            // check that bob has to accept before being granted ownership
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(
+                most.ensure_owner(),
+                Err(Ownable2StepError::CallerNotOwner(accounts.bob))
+            );
+            // check that only bob can accept the pending new ownership
+            set_caller::<DefEnv>(accounts.charlie);
+            assert_eq!(
+                most.accept_ownership(),
+                Err(Ownable2StepError::CallerNotPendingOwner(accounts.charlie))
+            );
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(most.accept_ownership(), Ok(()));
+            assert_eq!(most.ensure_owner(), Ok(()));
+        }
+
+        #[ink::test]
+        fn add_guardian_works() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = Most::new(
+                guardian_accounts(),
+                THRESHOLD,
+                MAX_POCKET_MONEY,
+                RELAY_GAS_USAGE,
+                MIN_FEE,
+                MAX_FEE,
+                DEFAULT_FEE,
+                GAS_ORACLE_MAX_AGE,
+                ORACLE_CALL_GAS_LIMIT,
+                BASE_FEE_BUFFER_PERCENTAGE,
+                None,
+                accounts.alice,
+                ETH_GAS_USAGE,
+            )
+            .expect("Threshold is valid.");
+
+            assert!(!most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.alice));
+            assert_eq!(most.committee_sizes.get(0), Some(5));
+            assert_eq!(most.set_committee(vec![accounts.alice], 1), Ok(()));
+            assert_eq!(most.committee_sizes.get(1), Some(1));
+            assert!(most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.alice));
+        }
+
+        #[ink::test]
+        fn remove_guardian_works() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = Most::new(
+                guardian_accounts(),
+                THRESHOLD,
+                MAX_POCKET_MONEY,
+                RELAY_GAS_USAGE,
+                MIN_FEE,
+                MAX_FEE,
+                DEFAULT_FEE,
+                GAS_ORACLE_MAX_AGE,
+                ORACLE_CALL_GAS_LIMIT,
+                BASE_FEE_BUFFER_PERCENTAGE,
+                None,
+                accounts.alice,
+                ETH_GAS_USAGE,
+            )
+            .expect("Threshold is valid.");
+
+            assert!(most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.bob));
+            assert_eq!(most.committee_sizes.get(0), Some(5));
+            assert_eq!(most.set_committee(vec![accounts.alice], 1), Ok(()));
+            assert_eq!(most.committee_sizes.get(1), Some(1));
+            assert!(!most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.bob));
+        }
+
+        #[ink::test]
+        fn set_committee_keeps_committee_history() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = Most::new(
+                guardian_accounts(),
+                THRESHOLD,
+                MAX_POCKET_MONEY,
+                RELAY_GAS_USAGE,
+                MIN_FEE,
+                MAX_FEE,
+                DEFAULT_FEE,
+                GAS_ORACLE_MAX_AGE,
+                ORACLE_CALL_GAS_LIMIT,
+                BASE_FEE_BUFFER_PERCENTAGE,
+                None,
+                accounts.alice,
+                ETH_GAS_USAGE,
+            )
+            .expect("Threshold is valid.");
+
+            ink::env::test::advance_block::<DefEnv>();
+            ink::env::test::advance_block::<DefEnv>();
+            let events_before = ink::env::test::recorded_events().count();
+            assert_eq!(
+                most.set_committee(vec![accounts.bob, accounts.alice], 2),
+                Ok(())
+            );
+            assert_eq!(ink::env::test::recorded_events().count(), events_before + 1);
+
+            assert_eq!(most.get_committee(0), Ok(guardian_accounts()));
+            assert_eq!(most.get_signature_threshold(0), Ok(THRESHOLD));
+            assert_eq!(most.get_committee_activation_block(0), Ok(0));
+            assert_eq!(
+                most.get_committee(1),
+                Ok(vec![accounts.bob, accounts.alice])
+            );
+            assert_eq!(most.get_signature_threshold(1), Ok(2));
+            assert_eq!(most.get_committee_activation_block(1), Ok(2));
+            assert_eq!(most.get_committee(2), Err(MostError::NoSuchCommittee));
+        }
+
+        #[ink::test]
+        fn owner_calling_set_max_pocket_money_test() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = Most::new(
+                guardian_accounts(),
+                THRESHOLD,
+                MAX_POCKET_MONEY,
+                RELAY_GAS_USAGE,
+                MIN_FEE,
+                MAX_FEE,
+                DEFAULT_FEE,
+                GAS_ORACLE_MAX_AGE,
+                ORACLE_CALL_GAS_LIMIT,
+                BASE_FEE_BUFFER_PERCENTAGE,
+                None,
+                accounts.alice,
+                ETH_GAS_USAGE,
+            )
+            .expect("Threshold is valid.");
+
+            assert_eq!(most.get_max_pocket_money(), Ok(MAX_POCKET_MONEY));
+            assert_eq!(most.set_max_pocket_money(420), Ok(()));
+            assert_eq!(most.get_max_pocket_money(), Ok(420));
+        }
+
+        #[ink::test]
+        fn not_owner_calling_set_max_pocket_money() {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.bob);
+            let mut most = Most::new(
+                guardian_accounts(),
+                THRESHOLD,
+                MAX_POCKET_MONEY,
+                RELAY_GAS_USAGE,
+                MIN_FEE,
+                MAX_FEE,
+                DEFAULT_FEE,
+                GAS_ORACLE_MAX_AGE,
+                ORACLE_CALL_GAS_LIMIT,
+                BASE_FEE_BUFFER_PERCENTAGE,
+                None,
+                accounts.alice,
+                ETH_GAS_USAGE,
+            )
+            .expect("Threshold is valid.");
+
+            assert_eq!(most.get_max_pocket_money(), Ok(MAX_POCKET_MONEY));
+            assert_eq!(
+                most.set_max_pocket_money(420),
+                Err(MostError::Ownable(Ownable2StepError::CallerNotOwner(
+                    accounts.bob
+                )))
+            );
+            assert_eq!(most.get_max_pocket_money(), Ok(MAX_POCKET_MONEY));
+        }
+
+        const TOKEN: [u8; 32] = [7; 32];
+        const RECEIVER: [u8; 32] = [8; 32];
+        const WINDOW: u64 = 1000;
 
-            // Check if MOST has mint permission to the PSP22 token
-            if local_token {
-                self.local_token.insert::<AccountId, ()>(from.into(), &());
-            } else {
-                let psp22_address: AccountId = from.into();
-                let psp22: ink::contract_ref!(Mintable) = psp22_address.into();
-                if psp22.minter() != self.env().account_id() {
-                    return Err(MostError::NoMintPermission);
-                }
-            }
+        /// Unhalted `Most` owned by alice, with a local token `TOKEN`.
+        fn most_with_local_token() -> Most {
+            let accounts = default_accounts::<DefEnv>();
+            set_caller::<DefEnv>(accounts.alice);
+            let mut most = Most::new(
+                guardian_accounts(),
+                THRESHOLD,
+                MAX_POCKET_MONEY,
+                RELAY_GAS_USAGE,
+                MIN_FEE,
+                MAX_FEE,
+                DEFAULT_FEE,
+                GAS_ORACLE_MAX_AGE,
+                ORACLE_CALL_GAS_LIMIT,
+                BASE_FEE_BUFFER_PERCENTAGE,
+                None,
+                accounts.alice,
+                ETH_GAS_USAGE,
+            )
+            .expect("Threshold is valid.");
 
-            self.supported_pairs.insert(from, &to);
-            Ok(())
+            assert_eq!(most.set_wazero(accounts.frank), Ok(()));
+            assert_eq!(most.add_pair(TOKEN, [9; 32], true), Ok(()));
+            assert_eq!(most.set_halted(false), Ok(()));
+            most
         }
 
-        /// Set weth(azero) psp22 token contract
-        ///
-        /// Can only be called by the contracts owner
-        #[ink(message)]
-        pub fn set_weth(&mut self, weth_address: AccountId) -> Result<(), MostError> {
-            self.ensure_owner()?;
-            self.ensure_halted()?;
-            self.weth.set(&weth_address);
-            Ok(())
+        fn limits(min_amount: u128, max_amount: u128, cap: u128) -> TransferLimits {
+            TransferLimits {
+                min_amount,
+                max_amount,
+                outflow_cap: cap,
+                inflow_cap: cap,
+                window: WINDOW,
+            }
         }
 
-        /// Set wazero(azero) psp22 token contract
-        ///
-        /// Can only be called by the contracts owner
-        #[ink(message)]
-        pub fn set_wazero(&mut self, wazero_address: AccountId) -> Result<(), MostError> {
-            self.ensure_owner()?;
-            self.ensure_halted()?;
-            self.wazero.set(&wazero_address);
-            Ok(())
-        }
+        #[ink::test]
+        fn set_transfer_limits_works() {
+            let accounts = default_accounts::<DefEnv>();
+            let mut most = most_with_local_token();
 
-        /// Sets address of the account that receives rewards on behalf of the committee member.
-        ///
-        /// Can only be called by an account that was a committee member in `committee_id`.
-        /// All the unpaid rewards will then be paid out to this account address when `payout_request` is called.
-        /// Comittee members that have set the `payout_account` do not need to call this method again when the committee changes, unless they want to change the payouts account.
-        #[ink(message)]
-        pub fn set_payout_account(
-            &mut self,
-            committee_id: CommitteeId,
-            account: AccountId,
-        ) -> Result<(), MostError> {
-            let caller = self.env().caller();
-            self.only_committee_member(committee_id, caller)?;
-            self.payout_accounts.insert(caller, &account);
+            assert_eq!(most.get_transfer_limits(TOKEN), TransferLimits::default());
+            assert_eq!(
+                most.set_transfer_limits(TOKEN, limits(10, 100, 1000)),
+                Ok(())
+            );
+            assert_eq!(most.get_transfer_limits(TOKEN), limits(10, 100, 1000));
 
-            self.env().emit_event(PayoutAccountSet {
-                member: caller,
-                account,
-            });
+            assert_eq!(
+                most.set_transfer_limits(TOKEN, limits(100, 10, 1000)),
+                Err(MostError::InvalidTransferLimits)
+            );
+            assert_eq!(
+                most.set_transfer_limits(
+                    TOKEN,
+                    TransferLimits {
+                        window: 0,
+                        ..limits(10, 100, 1000)
+                    }
+                ),
+                Err(MostError::InvalidTransferLimits)
+            );
 
-            Ok(())
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(
+                most.set_transfer_limits(TOKEN, TransferLimits::default()),
+                Err(MostError::Ownable(Ownable2StepError::CallerNotOwner(
+                    accounts.bob
+                )))
+            );
+            assert_eq!(most.get_transfer_limits(TOKEN), limits(10, 100, 1000));
         }
 
-        /// Sets address of the gas price oracle
-        ///
-        /// Can only be called by the contracts owner
-        #[ink(message)]
-        pub fn set_gas_price_oracle(
-            &mut self,
-            gas_price_oracle: AccountId,
-        ) -> Result<(), MostError> {
-            self.ensure_owner()?;
-            let mut data = self.data()?;
-            data.gas_price_oracle = Some(gas_price_oracle);
-            self.data.set(&data);
-            Ok(())
-        }
+        #[ink::test]
+        fn send_request_enforces_transfer_limits() {
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_transfer_limits(TOKEN, limits(10, 100, 50)), Ok(()));
 
-        /// Sets an `eth_transfer_gas_usage`.
-        ///
-        /// Can only be called by the contracts owner
-        #[ink(message)]
-        pub fn set_eth_transfer_gas_usage(
-            &mut self,
-            new_eth_transfer_gas_usage: u128,
-        ) -> Result<(), crate::most::MostError> {
-            self.ensure_owner()?;
-            self.eth_transfer_gas_usage.set(&new_eth_transfer_gas_usage);
-            Ok(())
+            assert_eq!(
+                most.send_request(TOKEN, 9, RECEIVER, None),
+                Err(MostError::AmountBelowMinimum)
+            );
+            assert_eq!(
+                most.send_request(TOKEN, 101, RECEIVER, None),
+                Err(MostError::AmountAboveMaximum)
+            );
+            assert_eq!(
+                most.send_request(TOKEN, 51, RECEIVER, None),
+                Err(MostError::OutflowCapExceeded)
+            );
+            assert_eq!(most.get_outflow(TOKEN), 0);
         }
 
-        /// Change the committee and increase committe id
-        /// Can only be called by the contracts owner
-        ///
-        /// Changing the entire set is the ONLY way of upgrading the committee
-        #[ink(message)]
-        pub fn set_committee(
-            &mut self,
-            committee: Vec<AccountId>,
-            signature_threshold: u128,
-        ) -> Result<(), MostError> {
-            self.ensure_owner()?;
-            self.ensure_halted()?;
-            Self::check_committee(&committee, signature_threshold)?;
+        #[ink::test]
+        fn released_outflow_frees_the_cap() {
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_transfer_limits(TOKEN, limits(0, 0, 100)), Ok(()));
 
-            let mut data = self.data()?;
+            assert_eq!(most.record_outflow(TOKEN, 80), Ok(()));
+            assert_eq!(
+                most.record_outflow(TOKEN, 30),
+                Err(MostError::OutflowCapExceeded)
+            );
 
-            let committee_id = data.committee_id + 1;
-            self.committee_sizes
-                .insert(committee_id, &(committee.len() as u128));
-            let mut committee_set = Mapping::new();
-            committee.into_iter().for_each(|account| {
-                committee_set.insert((committee_id, account), &());
-            });
+            most.release_outflow(TOKEN, 50);
+            assert_eq!(most.get_outflow(TOKEN), 30);
+            assert_eq!(most.record_outflow(TOKEN, 30), Ok(()));
 
-            self.committees = committee_set;
-            data.committee_id = committee_id;
+            most.release_outflow(TOKEN, 100);
+            assert_eq!(most.get_outflow(TOKEN), 0);
+        }
 
-            self.data.set(&data);
+        #[ink::test]
+        fn quote_transfer_matches_send_request() {
+            let accounts = default_accounts::<DefEnv>();
+            let mut most = most_with_local_token();
+            let fee = most.get_base_fee().expect("Base fee is set.");
 
-            self.signature_thresholds
-                .insert(committee_id, &signature_threshold);
+            assert_eq!(
+                most.quote_transfer(TOKEN, 10),
+                Ok(TransferQuote {
+                    dest_token_address: [9; 32],
+                    fee,
+                    dest_amount: 10,
+                    fee_valid_until: None,
+                })
+            );
+            assert_eq!(
+                most.quote_transfer([5; 32], 10),
+                Err(MostError::UnsupportedPair)
+            );
+            assert_eq!(
+                most.quote_transfer(TOKEN, 0),
+                Err(MostError::ZeroTransferAmount)
+            );
 
-            Ok(())
-        }
+            assert_eq!(most.set_transfer_limits(TOKEN, limits(10, 100, 50)), Ok(()));
+            for (amount, error) in [
+                (9, MostError::AmountBelowMinimum),
+                (101, MostError::AmountAboveMaximum),
+                (51, MostError::OutflowCapExceeded),
+            ] {
+                assert_eq!(
+                    most.send_request(TOKEN, amount, RECEIVER, None),
+                    most.quote_transfer(TOKEN, amount).map(|_| ())
+                );
+                assert_eq!(most.quote_transfer(TOKEN, amount), Err(error));
+            }
+            assert_eq!(most.record_outflow(TOKEN, 40), Ok(()));
+            assert_eq!(
+                most.quote_transfer(TOKEN, 11),
+                Err(MostError::OutflowCapExceeded)
+            );
+            assert!(most.quote_transfer(TOKEN, 10).is_ok());
 
-        /// Halt/resume the bridge contract
-        ///
-        /// Can only be called by the contracts owner
-        #[ink(message)]
-        pub fn set_halted(&mut self, new_state: bool) -> Result<(), MostError> {
-            self.ensure_owner()?;
+            let pause = PairPause {
+                outgoing: true,
+                incoming: false,
+            };
+            assert_eq!(most.set_pair_pause(TOKEN, pause), Ok(()));
+            assert_eq!(most.quote_transfer(TOKEN, 10), Err(MostError::PairPaused));
+            assert_eq!(most.set_pair_pause(TOKEN, PairPause::default()), Ok(()));
 
-            let mut data = self.data()?;
-            let previous_state = data.is_halted;
+            assert_eq!(most.set_halted(true), Ok(()));
+            assert_eq!(
+                most.add_pair(*accounts.frank.as_ref(), [6; 32], true),
+                Ok(())
+            );
+            assert_eq!(
+                most.quote_transfer(*accounts.frank.as_ref(), 10)
+                    .map(|quote| (quote.dest_token_address, quote.dest_amount)),
+                Ok(([6; 32], 10 * RATIO))
+            );
+            assert_eq!(most.set_halted(false), Ok(()));
 
-            if new_state != previous_state {
-                data.is_halted = new_state;
+            assert_eq!(
+                most.send_request(TOKEN, 10, RECEIVER, Some(fee - 1)),
+                Err(MostError::FeeAboveMax)
+            );
+        }
 
-                self.data.set(&data);
-                self.env().emit_event(HaltedStateChanged {
-                    previous_state,
-                    new_state,
-                    caller: self.env().caller(),
-                });
+        #[ink::test]
+        fn receive_request_enforces_inflow_cap() {
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_transfer_limits(TOKEN, limits(0, 0, 100)), Ok(()));
+
+            let amount = 101;
+            let request_hash = hash_request_data(0, TOKEN.into(), amount, RECEIVER.into(), 0);
+            let guardians = guardian_accounts();
+            for guardian in &guardians[..THRESHOLD as usize - 1] {
+                set_caller::<DefEnv>(*guardian);
+                assert_eq!(
+                    most.receive_request(request_hash, 0, TOKEN, amount, RECEIVER, 0),
+                    Ok(())
+                );
             }
 
-            Ok(())
+            // the vote reaching the quorum succeeds and parks the request
+            set_caller::<DefEnv>(guardians[THRESHOLD as usize - 1]);
+            assert_eq!(
+                most.receive_request(request_hash, 0, TOKEN, amount, RECEIVER, 0),
+                Ok(())
+            );
+            assert!(matches!(
+                most.request_status(request_hash),
+                RequestStatus::Delayed { executable_at: 0 }
+            ));
+            assert_eq!(
+                most.execute_delayed_request(request_hash),
+                Err(MostError::InflowCapExceeded)
+            );
+            assert!(matches!(
+                most.request_status(request_hash),
+                RequestStatus::Delayed { .. }
+            ));
+            assert_eq!(most.get_inflow(TOKEN), 0);
         }
 
-        /// Transfer PSP22 tokens from the bridge contract to a given account.
-        ///
-        /// Can only be called by the contracts owner
-        #[ink(message)]
-        pub fn recover_psp22(
-            &mut self,
-            token: AccountId,
-            receiver: AccountId,
-            amount: u128,
-        ) -> Result<(), MostError> {
-            self.ensure_owner()?;
+        #[ink::test]
+        fn request_over_inflow_cap_waits_for_window() {
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_transfer_limits(TOKEN, limits(0, 0, 100)), Ok(()));
+            most.inflows.insert(
+                TOKEN,
+                &Flow {
+                    amount: 100,
+                    updated_at: 0,
+                },
+            );
 
-            let mut token: ink::contract_ref!(PSP22) = token.into();
-            token.transfer(receiver, amount, vec![])?;
-            Ok(())
+            let request_hash = reach_quorum(&mut most, 25);
+            assert!(matches!(
+                most.request_status(request_hash),
+                RequestStatus::Delayed { executable_at: 0 }
+            ));
+            assert_eq!(
+                most.execute_delayed_request(request_hash),
+                Err(MostError::InflowCapExceeded)
+            );
+
+            ink::env::test::set_block_timestamp::<DefEnv>(WINDOW / 4);
+            assert!(most.inflow_fits(TOKEN, 25));
+            assert!(!most.inflow_fits(TOKEN, 26));
         }
 
-        /// Transfer AZERO tokens from the bridge contract to a given account.
-        ///
-        /// Can only be called by the contracts owner
-        #[ink(message)]
-        pub fn recover_azero(
-            &mut self,
-            receiver: AccountId,
-            amount: u128,
-        ) -> Result<(), MostError> {
-            self.ensure_owner()?;
+        #[ink::test]
+        fn native_azero_limits_are_in_wazero_units() {
+            let accounts = default_accounts::<DefEnv>();
+            let wazero = *accounts.frank.as_ref();
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_transfer_limits(wazero, limits(0, 0, 100)), Ok(()));
+            assert_eq!(local_amount(NATIVE_MARKER_ADDRESS, 100 * RATIO), 100);
+            assert_eq!(local_amount(TOKEN, 100 * RATIO), 100 * RATIO);
+
+            let amount = 101 * RATIO;
+            let request_hash =
+                hash_request_data(0, NATIVE_MARKER_ADDRESS.into(), amount, RECEIVER.into(), 0);
+            for guardian in &guardian_accounts()[..THRESHOLD as usize] {
+                set_caller::<DefEnv>(*guardian);
+                assert_eq!(
+                    most.receive_request(
+                        request_hash,
+                        0,
+                        NATIVE_MARKER_ADDRESS,
+                        amount,
+                        RECEIVER,
+                        0
+                    ),
+                    Ok(())
+                );
+            }
+            assert_eq!(
+                most.execute_delayed_request(request_hash),
+                Err(MostError::InflowCapExceeded)
+            );
 
-            self.env().transfer(receiver, amount)?;
-            Ok(())
+            most.inflows.insert(
+                wazero,
+                &Flow {
+                    amount: 50,
+                    updated_at: 0,
+                },
+            );
+            assert!(most.inflow_fits(wazero, local_amount(NATIVE_MARKER_ADDRESS, 50 * RATIO)));
+            assert!(!most.inflow_fits(wazero, local_amount(NATIVE_MARKER_ADDRESS, 51 * RATIO)));
         }
 
-        // ---  helper functions
+        #[ink::test]
+        fn flow_frees_up_over_window() {
+            let flow = Flow::default().add(100, 100, WINDOW, 0).unwrap();
 
-        fn ensure_halted(&self) -> Result<(), MostError> {
-            match self.is_halted()? {
-                true => Ok(()),
-                false => Err(MostError::HaltRequired),
-            }
+            assert_eq!(flow.add(1, 100, WINDOW, 0), None);
+            assert_eq!(flow.current(100, WINDOW, WINDOW / 4), 75);
+            assert_eq!(
+                flow.add(25, 100, WINDOW, WINDOW / 4),
+                Some(Flow {
+                    amount: 100,
+                    updated_at: WINDOW / 4
+                })
+            );
+            assert_eq!(flow.current(100, WINDOW, WINDOW), 0);
         }
 
-        fn ensure_not_halted(&self) -> Result<(), MostError> {
-            match self.is_halted()? {
-                true => Err(MostError::IsHalted),
-                false => Ok(()),
+        /// Votes for a request of `amount` of `TOKEN` with the first `THRESHOLD` guardians.
+        fn reach_quorum(most: &mut Most, amount: u128) -> HashedRequest {
+            let request_hash = hash_request_data(0, TOKEN.into(), amount, RECEIVER.into(), 0);
+            for guardian in &guardian_accounts()[..THRESHOLD as usize] {
+                set_caller::<DefEnv>(*guardian);
+                assert_eq!(
+                    most.receive_request(request_hash, 0, TOKEN, amount, RECEIVER, 0),
+                    Ok(())
+                );
             }
+            request_hash
         }
 
-        fn check_committee(committee: &[AccountId], threshold: u128) -> Result<(), MostError> {
-            if threshold == 0 || committee.len().lt(&(threshold as usize)) {
-                return Err(MostError::InvalidThreshold);
-            }
+        #[ink::test]
+        fn receive_requests_returns_result_per_request() {
+            let accounts = default_accounts::<DefEnv>();
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_large_transfer_threshold(TOKEN, 1), Ok(()));
+
+            let amount = 10;
+            let request = RequestData {
+                request_hash: hash_request_data(0, TOKEN.into(), amount, RECEIVER.into(), 0),
+                committee_id: 0,
+                dest_token_address: TOKEN,
+                amount,
+                dest_receiver_address: RECEIVER,
+                request_nonce: 0,
+            };
+            let forged = RequestData {
+                amount: amount + 1,
+                ..request
+            };
 
-            for i in 0..committee.len() {
-                for j in i + 1..committee.len() {
-                    if committee[i] == committee[j] {
-                        return Err(MostError::DuplicateCommitteeMember);
-                    }
-                }
+            for guardian in &guardian_accounts()[..THRESHOLD as usize] {
+                set_caller::<DefEnv>(*guardian);
+                assert_eq!(
+                    most.receive_requests(vec![request, forged, request]),
+                    Ok(vec![Ok(()), Err(MostError::HashDoesNotMatchData), Ok(())])
+                );
             }
-            Ok(())
-        }
+            assert!(matches!(
+                most.request_status(request.request_hash),
+                RequestStatus::Delayed { .. }
+            ));
 
-        /// Mints the specified amount of token to the designated account
-        ///
-        /// Most contract needs to have a Minter role on the token contract
-        fn mint_to(&self, token: AccountId, to: AccountId, amount: u128) -> Result<(), PSP22Error> {
-            let mut psp22: ink::contract_ref!(Mintable) = token.into();
-            psp22.mint(to, amount)
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(
+                most.receive_requests(vec![request]),
+                Ok(vec![Err(MostError::NotInCommittee)])
+            );
+            assert_eq!(most.set_halted(true), Ok(()));
+            assert_eq!(
+                most.receive_requests(vec![request]),
+                Err(MostError::IsHalted)
+            );
         }
 
-        /// Transfers the specified amount of token to the designated account
-        fn transfer(
-            &self,
-            token: AccountId,
-            to: AccountId,
-            amount: u128,
-        ) -> Result<(), PSP22Error> {
-            let mut psp22: ink::contract_ref!(PSP22) = token.into();
-            psp22.transfer(to, amount, vec![])
-        }
+        #[ink::test]
+        fn receive_requests_reports_errors_at_the_quorum_without_recording_the_vote() {
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_large_transfer_threshold(TOKEN, 1), Ok(()));
+            // delaying the request overflows its executable_at
+            assert_eq!(most.set_large_transfer_delay(u64::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<DefEnv>(1);
+
+            let amount = 10;
+            let request = RequestData {
+                request_hash: hash_request_data(0, TOKEN.into(), amount, RECEIVER.into(), 0),
+                committee_id: 0,
+                dest_token_address: TOKEN,
+                amount,
+                dest_receiver_address: RECEIVER,
+                request_nonce: 0,
+            };
+            let other = RequestData {
+                request_hash: hash_request_data(0, TOKEN.into(), amount, RECEIVER.into(), 1),
+                request_nonce: 1,
+                ..request
+            };
 
-        /// Unwraps the specified amount of AZERO locked in the bridge contract and transfers it to the designated account
-        fn unwrap_azero_to(&self, to: AccountId, amount: u128) -> Result<(), MostError> {
-            let wrapped_azero_address = self.wazero.get().ok_or(MostError::WrappedAzeroNotSet)?;
-            let mut wrapped_azero: ink::contract_ref!(WrappedAZERO) = wrapped_azero_address.into();
+            let guardians = guardian_accounts();
+            for guardian in &guardians[..THRESHOLD as usize - 1] {
+                set_caller::<DefEnv>(*guardian);
+                assert_eq!(
+                    most.receive_request(request.request_hash, 0, TOKEN, amount, RECEIVER, 0),
+                    Ok(())
+                );
+            }
 
-            wrapped_azero.withdraw(amount)?;
-            self.env().transfer(to, amount)?;
-            Ok(())
+            set_caller::<DefEnv>(guardians[THRESHOLD as usize - 1]);
+            assert_eq!(
+                most.receive_requests(vec![request, other]),
+                Ok(vec![Err(MostError::Arithmetic), Ok(())])
+            );
+            assert!(
+                !most.has_signed_request(guardians[THRESHOLD as usize - 1], request.request_hash)
+            );
+            assert!(most.has_signed_request(guardians[THRESHOLD as usize - 1], other.request_hash));
+            assert!(matches!(
+                most.request_status(request.request_hash),
+                RequestStatus::Pending {
+                    collected_signatures: 2
+                }
+            ));
+            assert_eq!(
+                most.receive_request(request.request_hash, 0, TOKEN, amount, RECEIVER, 0),
+                Err(MostError::Arithmetic)
+            );
         }
 
-        /// Burn the specified amount of token from the designated account
-        ///
-        /// Most contract needs to have a Burner role on the token contract
-        fn burn_from(
-            &self,
-            token: AccountId,
-            from: AccountId,
-            amount: u128,
-        ) -> Result<(), PSP22Error> {
-            self.transfer_from(token, from, amount)?;
+        #[ink::test]
+        fn signers_are_kept_in_a_bitmap_until_the_quorum() {
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_large_transfer_threshold(TOKEN, 1), Ok(()));
+            let guardians = guardian_accounts();
+            // a member without a recorded position signs in `signatures`
+            most.committee_positions.remove((0, guardians[0]));
+
+            let request_hash = hash_request_data(0, TOKEN.into(), 10, RECEIVER.into(), 0);
+            for guardian in &guardians[..THRESHOLD as usize - 1] {
+                set_caller::<DefEnv>(*guardian);
+                assert_eq!(
+                    most.receive_request(request_hash, 0, TOKEN, 10, RECEIVER, 0),
+                    Ok(())
+                );
+            }
 
-            let mut psp22_burnable: ink::contract_ref!(Burnable) = token.into();
-            psp22_burnable.burn(amount)
-        }
+            for (position, guardian) in guardians.iter().enumerate() {
+                let signed = position < THRESHOLD as usize - 1;
+                assert_eq!(most.has_signed_request(*guardian, request_hash), signed);
+                assert_eq!(
+                    most.signatures.contains((request_hash, *guardian)),
+                    position == 0
+                );
+                assert_eq!(most.needs_signature(request_hash, *guardian, 0), !signed);
+            }
+            assert_eq!(
+                most.request_signers.get(request_hash),
+                Some(RequestSigners {
+                    committee_id: 0,
+                    positions: (1 << (THRESHOLD - 1)) - 2,
+                })
+            );
 
-        /// Transfers the specified amount of token from the designated account
-        fn transfer_from(
-            &self,
-            token: AccountId,
-            from: AccountId,
-            amount: u128,
-        ) -> Result<(), PSP22Error> {
-            let mut psp22: ink::contract_ref!(PSP22) = token.into();
-            psp22.transfer_from(from, self.env().account_id(), amount, vec![])
-        }
+            set_caller::<DefEnv>(guardians[THRESHOLD as usize - 1]);
+            assert_eq!(
+                most.receive_request(request_hash, 0, TOKEN, 10, RECEIVER, 0),
+                Ok(())
+            );
+            assert!(most.processed_requests.contains(request_hash));
+            assert_eq!(most.request_signers.get(request_hash), None);
+            for guardian in &guardians {
+                assert!(!most.signatures.contains((request_hash, *guardian)));
+                assert!(!most.needs_signature(request_hash, *guardian, 0));
+            }
 
-        fn data(&self) -> Result<Data, MostError> {
-            self.data.get().ok_or(MostError::CorruptedStorage)
+            // signatures recorded before the bitmaps are still honored
+            let legacy_hash = hash_request_data(0, TOKEN.into(), 10, RECEIVER.into(), 1);
+            most.signatures.insert((legacy_hash, guardians[0]), &());
+            assert!(most.has_signed_request(guardians[0], legacy_hash));
+            assert!(!most.needs_signature(legacy_hash, guardians[0], 0));
+            assert!(most.needs_signature(legacy_hash, guardians[1], 0));
         }
 
-        fn ownable_data(&self) -> Result<Ownable2StepData, Ownable2StepError> {
-            self.ownable_data
-                .get()
-                .ok_or(Ownable2StepError::Custom("CorruptedStorage".into()))
-        }
-    }
+        #[ink::test]
+        fn request_domain_binds_hashes_from_its_nonce_on() {
+            let mut most = most_with_local_token();
+            let domain = RequestDomain {
+                src_chain_id: 1,
+                src_contract: [3; 32],
+                from_nonce: 5,
+            };
+            assert_eq!(
+                most.set_request_domain(domain),
+                Err(MostError::HaltRequired)
+            );
+            assert_eq!(most.set_halted(true), Ok(()));
+            assert_eq!(most.set_request_domain(domain), Ok(()));
+            assert_eq!(
+                most.set_request_domain(domain),
+                Err(MostError::RequestDomainAlreadySet)
+            );
+            assert_eq!(most.set_halted(false), Ok(()));
+            assert_eq!(most.get_request_domain(), Some(domain));
 
-    impl Ownable2Step for Most {
-        #[ink(message)]
-        fn get_owner(&self) -> Ownable2StepResult<AccountId> {
-            self.ownable_data()?.get_owner()
+            let separator =
+                request_domain_separator(1, [3; 32], *test::callee::<DefEnv>().as_ref());
+            set_caller::<DefEnv>(guardian_accounts()[0]);
+            for nonce in [4, 5] {
+                let legacy_hash = hash_request_data(0, TOKEN.into(), 10, RECEIVER.into(), nonce);
+                let v1_hash =
+                    hash_request_data_v1(separator, 0, TOKEN.into(), 10, RECEIVER.into(), nonce);
+                let (valid, invalid) = match nonce < domain.from_nonce {
+                    true => (legacy_hash, v1_hash),
+                    false => (v1_hash, legacy_hash),
+                };
+                assert_eq!(
+                    most.receive_request(invalid, 0, TOKEN, 10, RECEIVER, nonce),
+                    Err(MostError::HashDoesNotMatchData)
+                );
+                assert_eq!(
+                    most.receive_request(valid, 0, TOKEN, 10, RECEIVER, nonce),
+                    Ok(())
+                );
+            }
         }
 
-        #[ink(message)]
-        fn get_pending_owner(&self) -> Ownable2StepResult<AccountId> {
-            self.ownable_data()?.get_pending_owner()
+        #[ink::test]
+        fn request_domain_starts_above_processed_nonces() {
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_large_transfer_threshold(TOKEN, 1), Ok(()));
+            assert_eq!(most.get_highest_processed_nonce(), None);
+
+            for nonce in [3, 1] {
+                let request_hash = hash_request_data(0, TOKEN.into(), 10, RECEIVER.into(), nonce);
+                for guardian in &guardian_accounts()[..THRESHOLD as usize] {
+                    set_caller::<DefEnv>(*guardian);
+                    assert_eq!(
+                        most.receive_request(request_hash, 0, TOKEN, 10, RECEIVER, nonce),
+                        Ok(())
+                    );
+                }
+            }
+            assert_eq!(most.get_highest_processed_nonce(), Some(3));
+
+            set_caller::<DefEnv>(default_accounts::<DefEnv>().alice);
+            assert_eq!(most.set_halted(true), Ok(()));
+            for from_nonce in [1, 3] {
+                assert_eq!(
+                    most.set_request_domain(RequestDomain {
+                        src_chain_id: 1,
+                        src_contract: [3; 32],
+                        from_nonce,
+                    }),
+                    Err(MostError::NonceAlreadyProcessed)
+                );
+            }
+            assert_eq!(
+                most.set_request_domain(RequestDomain {
+                    src_chain_id: 1,
+                    src_contract: [3; 32],
+                    from_nonce: 4,
+                }),
+                Ok(())
+            );
         }
 
-        #[ink(message)]
-        fn transfer_ownership(&mut self, new_owner: AccountId) -> Ownable2StepResult<()> {
-            let mut ownable_data = self.ownable_data()?;
-            ownable_data.transfer_ownership(self.env().caller(), new_owner)?;
-            self.ownable_data.set(&ownable_data);
-            self.env()
-                .emit_event(TransferOwnershipInitiated { new_owner });
-            Ok(())
-        }
+        #[ink::test]
+        fn large_request_is_delayed() {
+            let accounts = default_accounts::<DefEnv>();
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_large_transfer_threshold(TOKEN, 100), Ok(()));
+            assert_eq!(most.set_large_transfer_delay(WINDOW), Ok(()));
 
-        #[ink(message)]
-        fn accept_ownership(&mut self) -> Ownable2StepResult<()> {
-            let new_owner = self.env().caller();
-            let mut ownable_data = self.ownable_data()?;
-            ownable_data.accept_ownership(new_owner)?;
-            self.ownable_data.set(&ownable_data);
-            self.env()
-                .emit_event(TransferOwnershipAccepted { new_owner });
-            Ok(())
-        }
+            let request_hash = reach_quorum(&mut most, 101);
 
-        #[ink(message)]
-        fn ensure_owner(&self) -> Ownable2StepResult<()> {
-            self.ownable_data()?.ensure_owner(self.env().caller())
+            assert!(matches!(
+                most.request_status(request_hash),
+                RequestStatus::Delayed {
+                    executable_at: WINDOW
+                }
+            ));
+            assert_eq!(
+                most.execute_delayed_request(request_hash),
+                Err(MostError::DelayNotElapsed)
+            );
+
+            // votes cast after the quorum don't affect the delayed request
+            set_caller::<DefEnv>(guardian_accounts()[THRESHOLD as usize]);
+            assert_eq!(
+                most.receive_request(request_hash, 0, TOKEN, 101, RECEIVER, 0),
+                Ok(())
+            );
+            assert!(!most.needs_signature(
+                request_hash,
+                guardian_accounts()[THRESHOLD as usize + 1],
+                0
+            ));
+            assert!(matches!(
+                most.request_status(request_hash),
+                RequestStatus::Delayed { .. }
+            ));
+
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(
+                most.cancel_delayed_request(request_hash),
+                Err(MostError::HaltRequired)
+            );
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use ink::env::{
-            test::{default_accounts, set_caller},
-            DefaultEnvironment, Environment,
-        };
+        #[ink::test]
+        fn advisory_cancels_delayed_request() {
+            let accounts = default_accounts::<DefEnv>();
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_large_transfer_threshold(TOKEN, 100), Ok(()));
+            assert_eq!(most.set_large_transfer_delay(WINDOW), Ok(()));
+            let request_hash = reach_quorum(&mut most, 101);
 
-        use super::*;
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(most.set_halted(true), Ok(()));
+            assert_eq!(most.set_advisory(accounts.django, true), Ok(()));
 
-        const THRESHOLD: u128 = 3;
-        const MAX_POCKET_MONEY: Balance = 1000000000000;
-        const RELAY_GAS_USAGE: u128 = 50000;
-        const MIN_FEE: Balance = 1000000000000;
-        const MAX_FEE: Balance = 100000000000000;
-        const DEFAULT_FEE: Balance = 30000000000000;
-        const GAS_ORACLE_MAX_AGE: u64 = 86400000;
-        const ORACLE_CALL_GAS_LIMIT: u64 = 2000000000;
-        const BASE_FEE_BUFFER_PERCENTAGE: u128 = 20;
-        const ETH_GAS_USAGE: u128 = 60_000;
+            set_caller::<DefEnv>(accounts.charlie);
+            assert_eq!(
+                most.cancel_delayed_request(request_hash),
+                Err(MostError::Ownable(Ownable2StepError::CallerNotOwner(
+                    accounts.charlie
+                )))
+            );
 
-        type DefEnv = DefaultEnvironment;
-        type AccountId = <DefEnv as Environment>::AccountId;
+            set_caller::<DefEnv>(accounts.django);
+            assert_eq!(most.cancel_delayed_request(request_hash), Ok(()));
+            assert!(matches!(
+                most.request_status(request_hash),
+                RequestStatus::Cancelled
+            ));
 
-        fn guardian_accounts() -> Vec<AccountId> {
-            let accounts = default_accounts::<DefEnv>();
-            vec![
-                accounts.bob,
-                accounts.charlie,
-                accounts.django,
-                accounts.eve,
-                accounts.frank,
-            ]
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(most.set_halted(false), Ok(()));
+            ink::env::test::set_block_timestamp::<DefEnv>(WINDOW);
+            assert_eq!(
+                most.execute_delayed_request(request_hash),
+                Err(MostError::RequestNotDelayed)
+            );
+        }
+
+        fn record_outgoing_request(most: &mut Most, request_nonce: u128) -> OutgoingRequest {
+            let request = OutgoingRequest {
+                committee_id: 0,
+                sender: default_accounts::<DefEnv>().alice,
+                src_token_address: TOKEN.into(),
+                amount: 100,
+                native_azero: false,
+            };
+            let request_hash = most.hash_outgoing_request(&request);
+            most.outgoing_requests.insert(request_nonce, &request_hash);
+            request
         }
 
         #[ink::test]
-        fn new_fails_on_zero_threshold() {
-            let alice = default_accounts::<DefEnv>().alice;
-            set_caller::<DefEnv>(alice);
+        fn refund_collects_committee_votes() {
+            let accounts = default_accounts::<DefEnv>();
+            let mut most = most_with_local_token();
+            let guardians = guardian_accounts();
 
+            set_caller::<DefEnv>(guardians[0]);
+            let request = record_outgoing_request(&mut most, 0);
             assert_eq!(
-                Most::new(
-                    guardian_accounts(),
+                most.refund_request(1, request),
+                Err(MostError::UnknownRequest)
+            );
+            assert_eq!(
+                most.refund_request(
                     0,
-                    MAX_POCKET_MONEY,
-                    RELAY_GAS_USAGE,
-                    MIN_FEE,
-                    MAX_FEE,
-                    DEFAULT_FEE,
-                    GAS_ORACLE_MAX_AGE,
-                    ORACLE_CALL_GAS_LIMIT,
-                    BASE_FEE_BUFFER_PERCENTAGE,
-                    None,
-                    alice,
-                    ETH_GAS_USAGE
-                )
-                .expect_err("Threshold is zero, instantiation should fail."),
-                MostError::InvalidThreshold
+                    OutgoingRequest {
+                        amount: 101,
+                        ..request
+                    }
+                ),
+                Err(MostError::HashDoesNotMatchData)
             );
-        }
-
-        #[ink::test]
-        fn new_fails_on_threshold_large_than_guardians() {
-            let alice = default_accounts::<DefEnv>().alice;
-            set_caller::<DefEnv>(alice);
 
+            assert_eq!(most.refund_request(0, request), Ok(()));
             assert_eq!(
-                Most::new(
-                    guardian_accounts(),
-                    (guardian_accounts().len() + 1) as u128,
-                    MAX_POCKET_MONEY,
-                    RELAY_GAS_USAGE,
-                    MIN_FEE,
-                    MAX_FEE,
-                    DEFAULT_FEE,
-                    GAS_ORACLE_MAX_AGE,
-                    ORACLE_CALL_GAS_LIMIT,
-                    BASE_FEE_BUFFER_PERCENTAGE,
-                    None,
-                    alice,
-                    ETH_GAS_USAGE
-                )
-                .expect_err("Threshold is larger than guardians, instantiation should fail."),
-                MostError::InvalidThreshold
+                most.refund_request(0, request),
+                Err(MostError::RequestAlreadySigned)
+            );
+            set_caller::<DefEnv>(guardians[1]);
+            assert_eq!(most.refund_request(0, request), Ok(()));
+            assert_eq!(
+                most.pending_refunds.get(0),
+                Some(Request { signature_count: 2 })
+            );
+
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(
+                most.refund_request(0, request),
+                Err(MostError::NotInCommittee)
+            );
+
+            most.refunded_requests.insert(0, &());
+            assert!(most.is_refunded(0));
+            set_caller::<DefEnv>(guardians[2]);
+            assert_eq!(
+                most.refund_request(0, request),
+                Err(MostError::RequestAlreadyRefunded)
             );
         }
 
         #[ink::test]
-        fn new_sets_caller_as_owner() {
-            let alice = default_accounts::<DefEnv>().alice;
-            set_caller::<DefEnv>(alice);
+        fn refund_only_counts_votes_of_the_request_committee() {
+            let accounts = default_accounts::<DefEnv>();
+            let mut most = most_with_local_token();
+            let request = record_outgoing_request(&mut most, 0);
 
-            let most = Most::new(
-                guardian_accounts(),
-                THRESHOLD,
-                MAX_POCKET_MONEY,
-                RELAY_GAS_USAGE,
-                MIN_FEE,
-                MAX_FEE,
-                DEFAULT_FEE,
-                GAS_ORACLE_MAX_AGE,
-                ORACLE_CALL_GAS_LIMIT,
-                BASE_FEE_BUFFER_PERCENTAGE,
-                None,
-                alice,
-                ETH_GAS_USAGE,
-            )
-            .expect("Threshold is valid.");
+            assert_eq!(most.set_halted(true), Ok(()));
+            assert_eq!(most.set_committee(vec![accounts.alice], 1), Ok(()));
+            assert_eq!(most.set_halted(false), Ok(()));
+
+            // a quorum of another committee can't refund the request
+            assert_eq!(
+                most.refund_request(
+                    0,
+                    OutgoingRequest {
+                        committee_id: 1,
+                        ..request
+                    }
+                ),
+                Err(MostError::HashDoesNotMatchData)
+            );
+            assert_eq!(
+                most.refund_request(0, request),
+                Err(MostError::NotInCommittee)
+            );
+            assert_eq!(most.pending_refunds.get(0), None);
 
-            assert_eq!(most.ensure_owner(), Ok(()));
             set_caller::<DefEnv>(guardian_accounts()[0]);
+            assert_eq!(most.refund_request(0, request), Ok(()));
             assert_eq!(
-                most.ensure_owner(),
-                Err(Ownable2StepError::CallerNotOwner(guardian_accounts()[0]))
+                most.pending_refunds.get(0),
+                Some(Request { signature_count: 1 })
             );
         }
 
+        type Event = <Most as ink::reflect::ContractEventBase>::Type;
+
+        fn last_event() -> Event {
+            let event = ink::env::test::recorded_events()
+                .last()
+                .expect("An event was emitted.");
+            <Event as scale::Decode>::decode(&mut &event.data[..]).expect("Event decodes.")
+        }
+
         #[ink::test]
-        fn new_sets_correct_guardians() {
+        fn admin_setters_emit_old_and_new_values() {
             let accounts = default_accounts::<DefEnv>();
-            set_caller::<DefEnv>(accounts.alice);
+            let mut most = most_with_local_token();
+
+            assert_eq!(most.set_max_pocket_money(7), Ok(()));
+            match last_event() {
+                Event::MaxPocketMoneyChanged(event) => assert_eq!(
+                    event,
+                    MaxPocketMoneyChanged {
+                        previous_max_pocket_money: MAX_POCKET_MONEY,
+                        new_max_pocket_money: 7,
+                        caller: accounts.alice,
+                    }
+                ),
+                _ => panic!("Expected MaxPocketMoneyChanged"),
+            }
 
-            let most = Most::new(
-                guardian_accounts(),
-                THRESHOLD,
-                MAX_POCKET_MONEY,
-                RELAY_GAS_USAGE,
-                MIN_FEE,
-                MAX_FEE,
-                DEFAULT_FEE,
-                GAS_ORACLE_MAX_AGE,
-                ORACLE_CALL_GAS_LIMIT,
-                BASE_FEE_BUFFER_PERCENTAGE,
-                None,
-                accounts.alice,
-                ETH_GAS_USAGE,
-            )
-            .expect("Threshold is valid.");
+            assert_eq!(most.set_large_transfer_delay(WINDOW), Ok(()));
+            assert_eq!(most.set_large_transfer_delay(2 * WINDOW), Ok(()));
+            match last_event() {
+                Event::LargeTransferDelayChanged(event) => assert_eq!(
+                    event,
+                    LargeTransferDelayChanged {
+                        previous_delay: WINDOW,
+                        new_delay: 2 * WINDOW,
+                        caller: accounts.alice,
+                    }
+                ),
+                _ => panic!("Expected LargeTransferDelayChanged"),
+            }
 
-            for account in guardian_accounts() {
-                assert!(most.is_in_committee(most.get_current_committee_id().unwrap(), account));
+            assert_eq!(most.set_halted(true), Ok(()));
+            assert_eq!(most.remove_pair(TOKEN), Ok(()));
+            match last_event() {
+                Event::SupportedPairChanged(event) => assert_eq!(
+                    event,
+                    SupportedPairChanged {
+                        from: TOKEN,
+                        previous_to: Some([9; 32]),
+                        new_to: None,
+                        local_token: true,
+                        caller: accounts.alice,
+                    }
+                ),
+                _ => panic!("Expected SupportedPairChanged"),
             }
-            assert!(!most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.alice));
         }
 
         #[ink::test]
-        fn set_owner_works() {
+        fn timelock_delays_owner_operations() {
             let accounts = default_accounts::<DefEnv>();
-            set_caller::<DefEnv>(accounts.alice);
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_halted(true), Ok(()));
 
-            let mut most = Most::new(
-                guardian_accounts(),
-                THRESHOLD,
-                MAX_POCKET_MONEY,
-                RELAY_GAS_USAGE,
-                MIN_FEE,
-                MAX_FEE,
-                DEFAULT_FEE,
-                GAS_ORACLE_MAX_AGE,
-                ORACLE_CALL_GAS_LIMIT,
-                BASE_FEE_BUFFER_PERCENTAGE,
-                None,
-                accounts.alice,
-                ETH_GAS_USAGE,
-            )
-            .expect("Threshold is valid.");
-            set_caller::<DefEnv>(accounts.bob);
             assert_eq!(
-                most.ensure_owner(),
-                Err(Ownable2StepError::CallerNotOwner(accounts.bob))
+                most.set_operation_delay(MIN_OPERATION_DELAY - 1),
+                Err(MostError::InvalidOperationDelay)
             );
-            set_caller::<DefEnv>(accounts.alice);
-            assert_eq!(most.ensure_owner(), Ok(()));
-            assert_eq!(most.transfer_ownership(accounts.bob), Ok(()));
-            // check that bob has to accept before being granted ownership
-            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(most.set_operation_delay(MIN_OPERATION_DELAY), Ok(()));
             assert_eq!(
-                most.ensure_owner(),
-                Err(Ownable2StepError::CallerNotOwner(accounts.bob))
+                most.set_operation_delay(2 * MIN_OPERATION_DELAY),
+                Err(MostError::TimelockRequired)
             );
-            // check that only bob can accept the pending new ownership
-            set_caller::<DefEnv>(accounts.charlie);
             assert_eq!(
-                most.accept_ownership(),
-                Err(Ownable2StepError::CallerNotPendingOwner(accounts.charlie))
+                most.add_pair([10; 32], [11; 32], true),
+                Err(MostError::TimelockRequired)
             );
-            set_caller::<DefEnv>(accounts.bob);
-            assert_eq!(most.accept_ownership(), Ok(()));
-            assert_eq!(most.ensure_owner(), Ok(()));
-        }
 
-        #[ink::test]
-        fn add_guardian_works() {
-            let accounts = default_accounts::<DefEnv>();
+            let operation = AdminOperation::AddPair {
+                from: [10; 32],
+                to: [11; 32],
+                local_token: true,
+            };
+            assert_eq!(most.schedule_operation(operation.clone()), Ok(0));
+            assert_eq!(
+                most.get_scheduled_operation(0),
+                Some(ScheduledOperation {
+                    operation,
+                    executable_at: MIN_OPERATION_DELAY,
+                })
+            );
+            assert_eq!(most.execute_operation(0), Err(MostError::DelayNotElapsed));
+
+            ink::env::test::set_block_timestamp::<DefEnv>(MIN_OPERATION_DELAY);
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(
+                most.execute_operation(0),
+                Err(MostError::Ownable(Ownable2StepError::CallerNotOwner(
+                    accounts.bob
+                )))
+            );
             set_caller::<DefEnv>(accounts.alice);
-            let mut most = Most::new(
-                guardian_accounts(),
-                THRESHOLD,
-                MAX_POCKET_MONEY,
-                RELAY_GAS_USAGE,
-                MIN_FEE,
-                MAX_FEE,
-                DEFAULT_FEE,
-                GAS_ORACLE_MAX_AGE,
-                ORACLE_CALL_GAS_LIMIT,
-                BASE_FEE_BUFFER_PERCENTAGE,
-                None,
-                accounts.alice,
-                ETH_GAS_USAGE,
-            )
-            .expect("Threshold is valid.");
+            assert_eq!(most.execute_operation(0), Ok(()));
+            assert_eq!(most.get_supported_pair([10; 32]), Some([11; 32]));
+            assert_eq!(most.execute_operation(0), Err(MostError::UnknownOperation));
 
-            assert!(!most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.alice));
-            assert_eq!(most.committee_sizes.get(0), Some(5));
-            assert_eq!(most.set_committee(vec![accounts.alice], 1), Ok(()));
-            assert_eq!(most.committee_sizes.get(1), Some(1));
-            assert!(most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.alice));
+            assert_eq!(
+                most.schedule_operation(AdminOperation::SetOperationDelay {
+                    delay: 2 * MIN_OPERATION_DELAY
+                }),
+                Ok(1)
+            );
+            assert_eq!(most.cancel_operation(1), Ok(()));
+            assert_eq!(most.get_scheduled_operation(1), None);
+            assert_eq!(most.execute_operation(1), Err(MostError::UnknownOperation));
+            assert_eq!(most.get_operation_delay(), MIN_OPERATION_DELAY);
         }
 
         #[ink::test]
-        fn remove_guardian_works() {
+        fn timelock_covers_the_critical_setters() {
             let accounts = default_accounts::<DefEnv>();
-            set_caller::<DefEnv>(accounts.alice);
-            let mut most = Most::new(
-                guardian_accounts(),
-                THRESHOLD,
-                MAX_POCKET_MONEY,
-                RELAY_GAS_USAGE,
-                MIN_FEE,
-                MAX_FEE,
-                DEFAULT_FEE,
-                GAS_ORACLE_MAX_AGE,
-                ORACLE_CALL_GAS_LIMIT,
-                BASE_FEE_BUFFER_PERCENTAGE,
-                None,
-                accounts.alice,
-                ETH_GAS_USAGE,
-            )
-            .expect("Threshold is valid.");
+            let mut most = most_with_local_token();
+            assert_eq!(most.set_advisory(accounts.django, true), Ok(()));
+            assert_eq!(most.grant_role(Role::Pauser, accounts.bob), Ok(()));
+            assert_eq!(most.set_halted(true), Ok(()));
+            assert_eq!(most.set_operation_delay(MIN_OPERATION_DELAY), Ok(()));
+
+            for result in [
+                most.remove_pair(TOKEN),
+                most.set_transfer_limits(TOKEN, limits(0, 0, 100)),
+                most.set_large_transfer_threshold(TOKEN, 1),
+                most.set_large_transfer_delay(WINDOW),
+                most.set_advisory(accounts.eve, true),
+                most.set_weth(accounts.eve),
+                most.set_wazero(accounts.eve),
+                most.set_gas_price_oracle(accounts.eve),
+            ] {
+                assert_eq!(result, Err(MostError::TimelockRequired));
+            }
+            assert_eq!(
+                most.grant_role(Role::FeeManager, accounts.charlie),
+                Err(AccessControlError::TimelockRequired)
+            );
 
-            assert!(most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.bob));
-            assert_eq!(most.committee_sizes.get(0), Some(5));
-            assert_eq!(most.set_committee(vec![accounts.alice], 1), Ok(()));
-            assert_eq!(most.committee_sizes.get(1), Some(1));
-            assert!(!most.is_in_committee(most.get_current_committee_id().unwrap(), accounts.bob));
+            // taking power away stays instant
+            assert_eq!(most.set_advisory(accounts.django, false), Ok(()));
+            assert_eq!(most.revoke_role(Role::Pauser, accounts.bob), Ok(()));
+
+            for operation in [
+                AdminOperation::SetTransferLimits {
+                    token: TOKEN,
+                    limits: limits(0, 0, 100),
+                },
+                AdminOperation::GrantRole {
+                    role: Role::FeeManager,
+                    account: accounts.charlie,
+                },
+                AdminOperation::AddAdvisory {
+                    account: accounts.eve,
+                },
+                AdminOperation::RemovePair { from: TOKEN },
+            ] {
+                assert!(most.schedule_operation(operation).is_ok());
+            }
+            ink::env::test::set_block_timestamp::<DefEnv>(MIN_OPERATION_DELAY);
+            for operation_id in 0..4 {
+                assert_eq!(most.execute_operation(operation_id), Ok(()));
+            }
+            assert_eq!(most.get_transfer_limits(TOKEN), limits(0, 0, 100));
+            assert!(most.has_role(Role::FeeManager, accounts.charlie));
+            assert!(most.is_advisory(accounts.eve));
+            assert_eq!(most.get_supported_pair(TOKEN), None);
         }
 
         #[ink::test]
-        fn owner_calling_set_max_pocket_money_test() {
+        fn roles_delegate_halting_and_fees() {
             let accounts = default_accounts::<DefEnv>();
+            let mut most = most_with_local_token();
+
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(
+                most.grant_role(Role::Pauser, accounts.bob),
+                Err(AccessControlError::CallerNotAdmin(accounts.bob))
+            );
+            assert!(most.set_halted(true).is_err());
+
             set_caller::<DefEnv>(accounts.alice);
-            let mut most = Most::new(
-                guardian_accounts(),
-                THRESHOLD,
-                MAX_POCKET_MONEY,
-                RELAY_GAS_USAGE,
-                MIN_FEE,
-                MAX_FEE,
-                DEFAULT_FEE,
-                GAS_ORACLE_MAX_AGE,
-                ORACLE_CALL_GAS_LIMIT,
-                BASE_FEE_BUFFER_PERCENTAGE,
-                None,
-                accounts.alice,
-                ETH_GAS_USAGE,
-            )
-            .expect("Threshold is valid.");
+            assert_eq!(most.grant_role(Role::Pauser, accounts.bob), Ok(()));
+            assert_eq!(most.grant_role(Role::FeeManager, accounts.charlie), Ok(()));
+            assert!(most.has_role(Role::Pauser, accounts.bob));
+            assert!(!most.has_role(Role::FeeManager, accounts.bob));
 
-            assert_eq!(most.get_max_pocket_money(), Ok(MAX_POCKET_MONEY));
-            assert_eq!(most.set_max_pocket_money(420), Ok(()));
-            assert_eq!(most.get_max_pocket_money(), Ok(420));
+            set_caller::<DefEnv>(accounts.bob);
+            assert_eq!(most.set_halted(true), Ok(()));
+            assert!(most.set_halted(false).is_err());
+            assert!(most
+                .set_base_fee_constraints(
+                    RELAY_GAS_USAGE,
+                    MIN_FEE,
+                    MAX_FEE,
+                    DEFAULT_FEE,
+                    GAS_ORACLE_MAX_AGE,
+                    ORACLE_CALL_GAS_LIMIT,
+                    BASE_FEE_BUFFER_PERCENTAGE,
+                )
+                .is_err());
+
+            set_caller::<DefEnv>(accounts.charlie);
+            assert_eq!(
+                most.set_base_fee_constraints(
+                    RELAY_GAS_USAGE,
+                    MIN_FEE,
+                    MAX_FEE,
+                    DEFAULT_FEE,
+                    GAS_ORACLE_MAX_AGE,
+                    ORACLE_CALL_GAS_LIMIT,
+                    BASE_FEE_BUFFER_PERCENTAGE,
+                ),
+                Ok(())
+            );
+            assert!(most.set_halted(true).is_err());
+            assert_eq!(most.renounce_role(Role::FeeManager), Ok(()));
+            assert!(most
+                .set_base_fee_constraints(
+                    RELAY_GAS_USAGE,
+                    MIN_FEE,
+                    MAX_FEE,
+                    DEFAULT_FEE,
+                    GAS_ORACLE_MAX_AGE,
+                    ORACLE_CALL_GAS_LIMIT,
+                    BASE_FEE_BUFFER_PERCENTAGE,
+                )
+                .is_err());
+            assert_eq!(
+                most.renounce_role(Role::FeeManager),
+                Err(AccessControlError::MissingRole(
+                    accounts.charlie,
+                    Role::FeeManager
+                ))
+            );
+
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(most.revoke_role(Role::Pauser, accounts.bob), Ok(()));
+            assert!(!most.has_role(Role::Pauser, accounts.bob));
+            assert_eq!(most.set_halted(false), Ok(()));
         }
 
         #[ink::test]
-        fn not_owner_calling_set_max_pocket_money() {
+        fn paused_pair_stops_sending_and_parks_incoming_requests() {
             let accounts = default_accounts::<DefEnv>();
+            let mut most = most_with_local_token();
+            assert_eq!(most.grant_role(Role::Pauser, accounts.bob), Ok(()));
+            let paused = PairPause {
+                outgoing: true,
+                incoming: true,
+            };
+
             set_caller::<DefEnv>(accounts.bob);
-            let mut most = Most::new(
-                guardian_accounts(),
-                THRESHOLD,
-                MAX_POCKET_MONEY,
-                RELAY_GAS_USAGE,
-                MIN_FEE,
-                MAX_FEE,
-                DEFAULT_FEE,
-                GAS_ORACLE_MAX_AGE,
-                ORACLE_CALL_GAS_LIMIT,
-                BASE_FEE_BUFFER_PERCENTAGE,
-                None,
-                accounts.alice,
-                ETH_GAS_USAGE,
-            )
-            .expect("Threshold is valid.");
+            assert_eq!(most.set_pair_pause(TOKEN, paused), Ok(()));
+            assert!(most.set_pair_pause(TOKEN, PairPause::default()).is_err());
+            assert_eq!(
+                most.send_request(TOKEN, 10, RECEIVER, None),
+                Err(MostError::PairPaused)
+            );
 
-            assert_eq!(most.get_max_pocket_money(), Ok(MAX_POCKET_MONEY));
+            let request_hash = reach_quorum(&mut most, 10);
+            assert!(matches!(
+                most.request_status(request_hash),
+                RequestStatus::Delayed { executable_at: 0 }
+            ));
             assert_eq!(
-                most.set_max_pocket_money(420),
-                Err(MostError::Ownable(Ownable2StepError::CallerNotOwner(
-                    accounts.bob
-                )))
+                most.execute_delayed_request(request_hash),
+                Err(MostError::PairPaused)
+            );
+
+            set_caller::<DefEnv>(accounts.alice);
+            assert_eq!(most.is_halted(), Ok(false));
+            assert_eq!(most.remove_pair(TOKEN), Ok(()));
+            assert_eq!(most.set_pair_pause(TOKEN, PairPause::default()), Ok(()));
+            assert_eq!(most.get_pair_pause(TOKEN), PairPause::default());
+            assert_eq!(
+                most.add_pair(TOKEN, [9; 32], true),
+                Err(MostError::HaltRequired)
             );
-            assert_eq!(most.get_max_pocket_money(), Ok(MAX_POCKET_MONEY));
         }
     }
 }
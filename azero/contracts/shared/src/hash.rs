@@ -1,8 +1,13 @@
-use ink::env::{hash::Keccak256, hash_bytes};
-use ink::primitives::AccountId;
+use ink::{
+    env::{hash::Keccak256, hash_bytes},
+    primitives::AccountId,
+};
 
 use crate::Keccak256HashOutput;
 
+/// Tag of the version 1 request hashes, which bind a request to the deployments it goes between
+pub const REQUEST_HASH_V1_TAG: &[u8] = b"MOST_REQUEST_V1";
+
 pub fn keccak256(input: &[u8]) -> Keccak256HashOutput {
     let mut output = Keccak256HashOutput::default();
     hash_bytes::<Keccak256>(input, &mut output);
@@ -26,3 +31,108 @@ pub fn hash_request_data(
     .concat();
     keccak256(&request_data)
 }
+
+/// Separates the version 1 request hashes of a source and a destination deployment from those of
+/// any other pair of deployments. Contract addresses shorter than 32 bytes are left-padded with
+/// zeroes.
+pub fn request_domain_separator(
+    src_chain_id: u128,
+    src_contract: [u8; 32],
+    dest_contract: [u8; 32],
+) -> Keccak256HashOutput {
+    let domain = [
+        REQUEST_HASH_V1_TAG,
+        &uint256(src_chain_id),
+        &src_contract,
+        &dest_contract,
+    ]
+    .concat();
+    keccak256(&domain)
+}
+
+/// Version 1 of the request hash. Unlike `hash_request_data`, it encodes numbers the same way as
+/// `abi.encodePacked` of `uint256` values in Solidity, so both chains share the format.
+pub fn hash_request_data_v1(
+    domain_separator: Keccak256HashOutput,
+    committee_id: u128,
+    token_address: AccountId,
+    amount: u128,
+    receiver_address: AccountId,
+    request_nonce: u128,
+) -> Keccak256HashOutput {
+    let request_data = [
+        &domain_separator,
+        &uint256(committee_id),
+        AsRef::<[u8]>::as_ref(&token_address),
+        &uint256(amount),
+        AsRef::<[u8]>::as_ref(&receiver_address),
+        &uint256(request_nonce),
+    ]
+    .concat();
+    keccak256(&request_data)
+}
+
+/// Big-endian 32-byte word of `value`
+fn uint256(value: u128) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{string::String, vec::Vec};
+
+    use serde_json::Value;
+
+    use super::*;
+
+    const VECTORS: &str = include_str!("../../../../test-vectors/request_hash_v1.json");
+
+    fn bytes32(vector: &Value, field: &str) -> [u8; 32] {
+        let hex_string = vector[field].as_str().expect("Field is a string.");
+        hex::decode(hex_string.trim_start_matches("0x"))
+            .expect("Field is hex encoded.")
+            .try_into()
+            .expect("Field has 32 bytes.")
+    }
+
+    fn number(vector: &Value, field: &str) -> u128 {
+        let number = vector[field].as_str().expect("Field is a string.");
+        number.parse().expect("Field is a number.")
+    }
+
+    #[test]
+    fn hash_request_data_v1_matches_test_vectors() {
+        let vectors: Vec<Value> = serde_json::from_str(VECTORS).expect("Vectors are valid JSON.");
+        assert!(!vectors.is_empty());
+
+        for vector in &vectors {
+            let description = String::from(vector["description"].as_str().unwrap_or_default());
+            let domain_separator = request_domain_separator(
+                number(vector, "src_chain_id"),
+                bytes32(vector, "src_contract"),
+                bytes32(vector, "dest_contract"),
+            );
+            assert_eq!(
+                domain_separator,
+                bytes32(vector, "domain_separator"),
+                "{description}"
+            );
+            assert_eq!(
+                hash_request_data_v1(
+                    domain_separator,
+                    number(vector, "committee_id"),
+                    bytes32(vector, "dest_token_address").into(),
+                    number(vector, "amount"),
+                    bytes32(vector, "dest_receiver_address").into(),
+                    number(vector, "request_nonce"),
+                ),
+                bytes32(vector, "request_hash"),
+                "{description}"
+            );
+        }
+    }
+}
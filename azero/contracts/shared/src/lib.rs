@@ -4,6 +4,9 @@ mod hash;
 mod helpers;
 mod types;
 
-pub use hash::{keccak256, hash_request_data};
+pub use hash::{
+    hash_request_data, hash_request_data_v1, keccak256, request_domain_separator,
+    REQUEST_HASH_V1_TAG,
+};
 pub use helpers::concat_u8_arrays;
 pub use types::Keccak256HashOutput;
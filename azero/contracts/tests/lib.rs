@@ -1343,7 +1343,7 @@ mod e2e {
             client,
             caller,
             most,
-            |most| most.send_request(*token.as_ref(), amount, receiver_address),
+            |most| most.send_request(*token.as_ref(), amount, receiver_address, None),
             Some(base_fee),
         )
         .await
@@ -1361,7 +1361,7 @@ mod e2e {
             client,
             caller,
             most,
-            |most| most.send_request_native_ether(amount, receiver_address),
+            |most| most.send_request_native_ether(amount, receiver_address, None),
             Some(base_fee),
         )
         .await
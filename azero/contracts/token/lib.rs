@@ -4,6 +4,9 @@ pub use self::token::TokenRef;
 
 #[ink::contract]
 pub mod token {
+    use access_control::{
+        AccessControl, AccessControlData, AccessControlError, AccessControlResult, Role,
+    };
     use ink::prelude::{string::String, vec::Vec};
     use ownable2step::*;
     use psp22::{PSP22Data, PSP22Error, PSP22Event, PSP22Metadata, PSP22};
@@ -45,6 +48,38 @@ pub mod token {
         pub new_owner: AccountId,
     }
 
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct HaltedStateChanged {
+        pub previous_state: bool,
+        pub new_state: bool,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RoleGranted {
+        pub role: Role,
+        #[ink(topic)]
+        pub account: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
+    #[ink(event)]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "std", derive(Eq, PartialEq))]
+    pub struct RoleRevoked {
+        pub role: Role,
+        #[ink(topic)]
+        pub account: AccountId,
+        #[ink(topic)]
+        pub caller: AccountId,
+    }
+
     #[ink(storage)]
     pub struct Token {
         data: PSP22Data,
@@ -53,6 +88,8 @@ pub mod token {
         symbol: Option<String>,
         decimals: u8,
         minter_burner: AccountId,
+        roles: AccessControlData,
+        is_halted: bool,
     }
 
     impl Token {
@@ -75,6 +112,8 @@ pub mod token {
                 symbol,
                 decimals,
                 minter_burner,
+                roles: AccessControlData::new(),
+                is_halted: false,
             };
 
             contract.emit_events(events);
@@ -91,6 +130,50 @@ pub mod token {
             Ok(())
         }
 
+        /// Halts or resumes minting and burning, e.g. while the bridge is investigated after an incident
+        ///
+        /// Can be halted by the owner or an account with the `Pauser` role, but only resumed by the owner
+        #[ink(message)]
+        pub fn set_halted(&mut self, new_state: bool) -> Result<(), PSP22Error> {
+            if new_state {
+                self.ensure_owner_or_role(Role::Pauser)?;
+            } else {
+                self.ensure_owner()?;
+            }
+
+            let previous_state = self.is_halted;
+            if new_state != previous_state {
+                self.is_halted = new_state;
+                self.env().emit_event(HaltedStateChanged {
+                    previous_state,
+                    new_state,
+                    caller: self.env().caller(),
+                });
+            }
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_halted(&self) -> bool {
+            self.is_halted
+        }
+
+        fn ensure_owner_or_role(&self, role: Role) -> Result<(), PSP22Error> {
+            if self.has_role(role, self.env().caller()) {
+                return Ok(());
+            }
+            self.ensure_owner()
+        }
+
+        fn ensure_not_halted(&self) -> Result<(), PSP22Error> {
+            if self.is_halted {
+                Err(PSP22Error::Custom(String::from("Token is halted.")))
+            } else {
+                Ok(())
+            }
+        }
+
         fn ensure_owner(&self) -> Result<(), PSP22Error> {
             <Self as Ownable2Step>::ensure_owner(self)
                 .map_err(|_| PSP22Error::Custom(String::from("Caller has to be the owner.")))
@@ -234,6 +317,7 @@ pub mod token {
         #[ink(message)]
         fn mint(&mut self, to: AccountId, value: u128) -> Result<(), PSP22Error> {
             self.ensure_minter()?;
+            self.ensure_not_halted()?;
             let events = self.data.mint(to, value)?;
             self.emit_events(events);
             Ok(())
@@ -249,6 +333,7 @@ pub mod token {
         #[ink(message)]
         fn burn(&mut self, value: u128) -> Result<(), PSP22Error> {
             self.ensure_burner()?;
+            self.ensure_not_halted()?;
             let events = self.data.burn(self.env().caller(), value)?;
             self.emit_events(events);
             Ok(())
@@ -295,6 +380,55 @@ pub mod token {
         }
     }
 
+    impl AccessControl for Token {
+        #[ink(message)]
+        fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.roles.has_role(role, account)
+        }
+
+        #[ink(message)]
+        fn grant_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            let admin = <Self as Ownable2Step>::get_owner(self)
+                .map_err(|_| AccessControlError::CallerNotAdmin(caller))?;
+
+            self.roles.grant_role(caller, admin, role, account)?;
+            self.env().emit_event(RoleGranted {
+                role,
+                account,
+                caller,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn revoke_role(&mut self, role: Role, account: AccountId) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            let admin = <Self as Ownable2Step>::get_owner(self)
+                .map_err(|_| AccessControlError::CallerNotAdmin(caller))?;
+
+            self.roles.revoke_role(caller, admin, role, account)?;
+            self.env().emit_event(RoleRevoked {
+                role,
+                account,
+                caller,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn renounce_role(&mut self, role: Role) -> AccessControlResult<()> {
+            let caller = self.env().caller();
+            self.roles.renounce_role(caller, role)?;
+            self.env().emit_event(RoleRevoked {
+                role,
+                account: caller,
+                caller,
+            });
+            Ok(())
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use ink::env::{test::*, DefaultEnvironment as E};
@@ -414,6 +548,39 @@ pub mod token {
             );
         }
 
+        #[ink::test]
+        fn pauser_can_halt_but_not_resume() {
+            let mut token = init_contract(INIT_SUPPLY_TEST);
+            let alice = default_accounts::<E>().alice;
+            let bob = default_accounts::<E>().bob;
+            let charlie = default_accounts::<E>().charlie;
+
+            set_caller::<E>(bob);
+            assert!(token.set_halted(true).is_err());
+
+            set_caller::<E>(alice);
+            assert_eq!(token.grant_role(Role::Pauser, bob), Ok(()));
+            set_caller::<E>(bob);
+            assert_eq!(token.set_halted(true), Ok(()));
+            assert!(token.is_halted());
+            assert!(token.set_halted(false).is_err());
+            assert!(token.set_minter_burner(bob).is_err());
+
+            let halted = Err(PSP22Error::Custom(String::from("Token is halted.")));
+            set_caller::<E>(charlie);
+            assert_eq!(token.mint(charlie, 100), halted);
+            assert_eq!(token.burn(100), halted);
+
+            set_caller::<E>(alice);
+            assert_eq!(token.set_halted(false), Ok(()));
+            set_caller::<E>(charlie);
+            assert_eq!(token.mint(charlie, 100), Ok(()));
+
+            set_caller::<E>(bob);
+            assert_eq!(token.renounce_role(Role::Pauser), Ok(()));
+            assert!(token.set_halted(true).is_err());
+        }
+
         fn init_contract(init_supply: u128) -> Token {
             set_caller::<E>(default_accounts::<E>().alice);
             Token::new(
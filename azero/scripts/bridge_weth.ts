@@ -102,6 +102,7 @@ async function main(): Promise<void> {
     srcTokenAddress,
     amount,
     destReceiverAddress,
+    null,
     { value: fee },
   );
 
@@ -281,6 +281,7 @@ impl Client {
         let send_request_args = [
             transfer_amount.to_string(),
             azero::bytes32_to_string(&self.eth_account_address_bytes),
+            "None".to_string(),
         ];
         let send_request_info = self
             .most_azero
@@ -303,6 +304,7 @@ impl Client {
         let send_request_args = [
             transfer_amount.to_string(),
             azero::bytes32_to_string(&self.eth_account_address_bytes),
+            "None".to_string(),
         ];
         let send_request_info = self
             .most_azero
@@ -331,6 +333,7 @@ impl Client {
             azero::bytes32_to_string(&contract_address_bytes),
             transfer_amount.to_string(),
             azero::bytes32_to_string(&self.eth_account_address_bytes),
+            "None".to_string(),
         ];
         let send_request_info = self
             .most_azero
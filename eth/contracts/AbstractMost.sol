@@ -25,6 +25,21 @@ abstract contract AbstractMost is
     /// and simple fallback function execution, yet still protecting against reentrancy attack.
     uint256 public constant GAS_LIMIT = 3500;
 
+    /// @dev Tag of the version 1 request hashes, which bind a request to the deployments it goes between
+    string public constant REQUEST_HASH_V1_TAG = "MOST_REQUEST_V1";
+
+    /// @dev keccak256(abi.encode(uint256(keccak256("most.storage.RequestDomain")) - 1)) & ~bytes32(uint256(0xff))
+    bytes32 private constant REQUEST_DOMAIN_STORAGE_LOCATION =
+        0x4145e98f65738886b19690827e97471c191ce43db1d9f84ef9d115b1544b7200;
+
+    /// @dev keccak256(abi.encode(uint256(keccak256("most.storage.ProcessedNonces")) - 1)) & ~bytes32(uint256(0xff))
+    bytes32 private constant PROCESSED_NONCES_STORAGE_LOCATION =
+        0xb347f8be75260b73b26633bb0b0de5fa6f9d038ecee3bbfdfc99c7a0af0ccc00;
+
+    /// @dev keccak256(abi.encode(uint256(keccak256("most.storage.CancelledRequests")) - 1)) & ~bytes32(uint256(0xff))
+    bytes32 private constant CANCELLED_REQUESTS_STORAGE_LOCATION =
+        0x4f91710bab8c3221a0ea0dbb18a3721f5df4ff6d751df4c484b7e30acc5e7000;
+
     uint256 public requestNonce;
     uint256 public committeeId;
     address payable public wethAddress;
@@ -45,6 +60,30 @@ abstract contract AbstractMost is
         mapping(address => bool) signatures;
     }
 
+    /// @notice Source of the incoming requests, bound into the hashes of the requests from `fromNonce` on
+    /// @dev `srcContract` is zero until the domain is set
+    /// @custom:storage-location erc7201:most.storage.RequestDomain
+    struct RequestDomain {
+        uint256 srcChainId;
+        bytes32 srcContract;
+        uint256 fromNonce;
+    }
+
+    /// @notice Highest nonce of the incoming requests that reached the quorum, the request domain can only apply above it
+    /// @dev `any` is false until a request reaches the quorum
+    /// @custom:storage-location erc7201:most.storage.ProcessedNonces
+    struct ProcessedNonces {
+        bool any;
+        uint256 highest;
+    }
+
+    /// @notice Incoming requests cancelled by the committee they were sent to, so that they can be refunded on the source chain
+    /// @custom:storage-location erc7201:most.storage.CancelledRequests
+    struct CancelledRequests {
+        mapping(bytes32 requestHash => Request) pending;
+        mapping(bytes32 requestHash => bool) cancelled;
+    }
+
     event CrosschainTransferRequest(
         uint256 indexed committeeId,
         bytes32 indexed destTokenAddress,
@@ -57,6 +96,10 @@ abstract contract AbstractMost is
 
     event RequestProcessed(bytes32 requestHash);
 
+    event CancellationSigned(bytes32 requestHash, address signer);
+
+    event RequestCancelled(bytes32 requestHash);
+
     /// @notice Emitted when guardian signs a request that has already been processed
     event ProcessedRequestSigned(bytes32 requestHash, address signer);
 
@@ -68,6 +111,12 @@ abstract contract AbstractMost is
 
     event CommitteeUpdated(uint256 newCommitteeId);
 
+    event RequestDomainSet(
+        uint256 srcChainId,
+        bytes32 srcContract,
+        uint256 fromNonce
+    );
+
     modifier _onlyCommitteeMember(uint256 _committeeId) {
         if (!isInCommittee(_committeeId, msg.sender)) revert NotInCommittee();
         _;
@@ -86,6 +135,10 @@ abstract contract AbstractMost is
     error ZeroAddress();
     error AzeroAddressNotSet();
     error LimitExceeded();
+    error RequestDomainAlreadySet();
+    error NonceAlreadyProcessed();
+    error RequestAlreadyProcessed();
+    error CancellationAlreadySigned();
 
     function __AbstractMost_init(
         address[] calldata _committee,
@@ -307,14 +360,12 @@ abstract contract AbstractMost is
             return;
         }
 
-        bytes32 requestHash = keccak256(
-            abi.encodePacked(
-                _committeeId,
-                destTokenAddress,
-                amount,
-                destReceiverAddress,
-                _requestNonce
-            )
+        bytes32 requestHash = hashRequestData(
+            _committeeId,
+            destTokenAddress,
+            amount,
+            destReceiverAddress,
+            _requestNonce
         );
 
         Request storage request = pendingRequests[requestHash];
@@ -331,6 +382,12 @@ abstract contract AbstractMost is
         emit RequestSigned(requestHash, msg.sender);
 
         if (request.signatureCount >= signatureThreshold[_committeeId]) {
+            ProcessedNonces storage processed = _getProcessedNonces();
+            if (!processed.any || _requestNonce > processed.highest) {
+                processed.any = true;
+                processed.highest = _requestNonce;
+            }
+
             onReceiveRequestThresholdMet(
                 requestHash,
                 destTokenAddress,
@@ -340,6 +397,60 @@ abstract contract AbstractMost is
         }
     }
 
+    /// @notice Aggregates guardian signatures for cancelling an incoming request that can never be processed
+    /// @dev Once the cancellation reaches the quorum of the committee of the request, the request is marked
+    /// as processed without paying anything out, and it can be refunded on the source chain.
+    /// Fails for requests that were already processed, as they must not be refunded.
+    function cancelRequest(
+        bytes32 _requestHash,
+        uint256 _committeeId,
+        bytes32 destTokenAddress,
+        uint256 amount,
+        bytes32 destReceiverAddress,
+        uint256 _requestNonce
+    ) external _onlyCommitteeMember(_committeeId) {
+        if (processedRequests[_requestHash]) revert RequestAlreadyProcessed();
+
+        bytes32 requestHash = hashRequestData(
+            _committeeId,
+            destTokenAddress,
+            amount,
+            destReceiverAddress,
+            _requestNonce
+        );
+        if (_requestHash != requestHash) revert DataHashMismatch();
+
+        CancelledRequests storage $ = _getCancelledRequests();
+        Request storage cancellation = $.pending[requestHash];
+        if (cancellation.signatures[msg.sender])
+            revert CancellationAlreadySigned();
+
+        cancellation.signatures[msg.sender] = true;
+        ++cancellation.signatureCount;
+
+        emit CancellationSigned(requestHash, msg.sender);
+
+        if (cancellation.signatureCount >= signatureThreshold[_committeeId]) {
+            processedRequests[requestHash] = true;
+            delete pendingRequests[requestHash];
+            delete $.pending[requestHash];
+            $.cancelled[requestHash] = true;
+
+            ProcessedNonces storage processed = _getProcessedNonces();
+            if (!processed.any || _requestNonce > processed.highest) {
+                processed.any = true;
+                processed.highest = _requestNonce;
+            }
+
+            emit RequestCancelled(requestHash);
+        }
+    }
+
+    /// @notice Whether the incoming request was cancelled, in which case it can be refunded on the source chain
+    function isCancelled(bytes32 requestHash) external view returns (bool) {
+        return _getCancelledRequests().cancelled[requestHash];
+    }
+
     function pause() external onlyOwner {
         _pause();
     }
@@ -399,6 +510,120 @@ abstract contract AbstractMost is
         delete supportedPairs[from];
     }
 
+    /// @notice Binds the hashes of the incoming requests from `fromNonce` on to their source and to this contract,
+    /// so that they can't be replayed on another deployment.
+    /// @dev Can only be set once, as the requests around a change could be processed twice otherwise.
+    /// For the same reason `fromNonce` has to be above the nonce of every request that already reached the quorum.
+    /// Only the requests processed since the contract tracks their nonces are checked, see `processedNonces`.
+    function setRequestDomain(
+        uint256 srcChainId,
+        bytes32 srcContract,
+        uint256 fromNonce
+    ) external onlyOwner whenPaused {
+        if (srcContract == bytes32(0)) revert ZeroAddress();
+        RequestDomain storage domain = _getRequestDomain();
+        if (domain.srcContract != bytes32(0)) revert RequestDomainAlreadySet();
+        ProcessedNonces storage processed = _getProcessedNonces();
+        if (processed.any && fromNonce <= processed.highest)
+            revert NonceAlreadyProcessed();
+
+        domain.srcChainId = srcChainId;
+        domain.srcContract = srcContract;
+        domain.fromNonce = fromNonce;
+
+        emit RequestDomainSet(srcChainId, srcContract, fromNonce);
+    }
+
+    function requestDomain() external view returns (RequestDomain memory) {
+        return _getRequestDomain();
+    }
+
+    function processedNonces()
+        external
+        view
+        returns (ProcessedNonces memory)
+    {
+        return _getProcessedNonces();
+    }
+
+    /// @notice Hashes a request with the version its nonce requires.
+    function hashRequestData(
+        uint256 _committeeId,
+        bytes32 destTokenAddress,
+        uint256 amount,
+        bytes32 destReceiverAddress,
+        uint256 _requestNonce
+    ) public view returns (bytes32) {
+        RequestDomain storage domain = _getRequestDomain();
+        if (
+            domain.srcContract == bytes32(0) || _requestNonce < domain.fromNonce
+        ) {
+            return
+                keccak256(
+                    abi.encodePacked(
+                        _committeeId,
+                        destTokenAddress,
+                        amount,
+                        destReceiverAddress,
+                        _requestNonce
+                    )
+                );
+        }
+
+        return
+            hashRequestDataV1(
+                requestDomainSeparator(
+                    domain.srcChainId,
+                    domain.srcContract,
+                    addressToBytes32(address(this))
+                ),
+                _committeeId,
+                destTokenAddress,
+                amount,
+                destReceiverAddress,
+                _requestNonce
+            );
+    }
+
+    /// @notice Separates the version 1 request hashes of a source and a destination deployment
+    /// from those of any other pair of deployments.
+    function requestDomainSeparator(
+        uint256 srcChainId,
+        bytes32 srcContract,
+        bytes32 destContract
+    ) public pure returns (bytes32) {
+        return
+            keccak256(
+                abi.encodePacked(
+                    REQUEST_HASH_V1_TAG,
+                    srcChainId,
+                    srcContract,
+                    destContract
+                )
+            );
+    }
+
+    function hashRequestDataV1(
+        bytes32 domainSeparator,
+        uint256 _committeeId,
+        bytes32 destTokenAddress,
+        uint256 amount,
+        bytes32 destReceiverAddress,
+        uint256 _requestNonce
+    ) public pure returns (bytes32) {
+        return
+            keccak256(
+                abi.encodePacked(
+                    domainSeparator,
+                    _committeeId,
+                    destTokenAddress,
+                    amount,
+                    destReceiverAddress,
+                    _requestNonce
+                )
+            );
+    }
+
     function hasSignedRequest(
         address guardian,
         bytes32 hash
@@ -430,6 +655,36 @@ abstract contract AbstractMost is
         return committee[keccak256(abi.encodePacked(_committeeId, account))];
     }
 
+    function _getRequestDomain()
+        private
+        pure
+        returns (RequestDomain storage $)
+    {
+        assembly {
+            $.slot := REQUEST_DOMAIN_STORAGE_LOCATION
+        }
+    }
+
+    function _getProcessedNonces()
+        private
+        pure
+        returns (ProcessedNonces storage $)
+    {
+        assembly {
+            $.slot := PROCESSED_NONCES_STORAGE_LOCATION
+        }
+    }
+
+    function _getCancelledRequests()
+        private
+        pure
+        returns (CancelledRequests storage $)
+    {
+        assembly {
+            $.slot := CANCELLED_REQUESTS_STORAGE_LOCATION
+        }
+    }
+
     function bytes32ToAddress(bytes32 data) internal pure returns (address) {
         return address(uint160(uint256(data)));
     }
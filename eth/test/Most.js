@@ -11,6 +11,9 @@ const {
   getRandomAlephAccount,
   ethToWei,
 } = require("./TestUtils");
+const REQUEST_HASH_V1_VECTORS = require(
+  "../../test-vectors/request_hash_v1.json",
+);
 
 const TOKEN_AMOUNT = 1000;
 const ALEPH_ACCOUNT = getRandomAlephAccount(3);
@@ -700,6 +703,294 @@ describe("Most", function () {
     });
   });
 
+  describe("cancelRequest", function () {
+    it("Cancels a request at the quorum so it is never processed", async () => {
+      const { most, token, tokenAddressBytes32, mostAddress } =
+        await loadFixture(deployEightGuardianMostFixture);
+      const accounts = await ethers.getSigners();
+      const ethAddress = addressToBytes32(accounts[10].address);
+      const requestHash = ethers.solidityPackedKeccak256(
+        ["uint256", "bytes32", "uint256", "bytes32", "uint256"],
+        [0, tokenAddressBytes32, TOKEN_AMOUNT, ethAddress, 0],
+      );
+
+      // Provide funds for Most
+      await token.transfer(mostAddress, TOKEN_AMOUNT * 2);
+
+      for (let i = 1; i < 5; i++) {
+        await expect(
+          most
+            .connect(accounts[i])
+            .cancelRequest(
+              requestHash,
+              0,
+              tokenAddressBytes32,
+              TOKEN_AMOUNT,
+              ethAddress,
+              0,
+            ),
+        )
+          .to.emit(most, "CancellationSigned")
+          .withArgs(requestHash, accounts[i].address);
+      }
+      expect(await most.isCancelled(requestHash)).to.be.false;
+
+      await expect(
+        most
+          .connect(accounts[5])
+          .cancelRequest(
+            requestHash,
+            0,
+            tokenAddressBytes32,
+            TOKEN_AMOUNT,
+            ethAddress,
+            0,
+          ),
+      )
+        .to.emit(most, "RequestCancelled")
+        .withArgs(requestHash);
+      expect(await most.isCancelled(requestHash)).to.be.true;
+      expect(await most.processedRequests(requestHash)).to.be.true;
+
+      for (let i = 1; i < 7; i++) {
+        await expect(
+          most
+            .connect(accounts[i])
+            .receiveRequest(
+              requestHash,
+              0,
+              tokenAddressBytes32,
+              TOKEN_AMOUNT,
+              ethAddress,
+              0,
+            ),
+        )
+          .to.emit(most, "ProcessedRequestSigned")
+          .withArgs(requestHash, accounts[i].address);
+      }
+      expect(await token.balanceOf(accounts[10].address)).to.equal(0);
+    });
+
+    it("Reverts for processed requests", async () => {
+      const { most, token, tokenAddressBytes32, mostAddress } =
+        await loadFixture(deployEightGuardianMostFixture);
+      const accounts = await ethers.getSigners();
+      const ethAddress = addressToBytes32(accounts[10].address);
+      const requestHash = ethers.solidityPackedKeccak256(
+        ["uint256", "bytes32", "uint256", "bytes32", "uint256"],
+        [0, tokenAddressBytes32, TOKEN_AMOUNT, ethAddress, 0],
+      );
+
+      // Provide funds for Most
+      await token.transfer(mostAddress, TOKEN_AMOUNT * 2);
+
+      for (let i = 1; i < 6; i++) {
+        await most
+          .connect(accounts[i])
+          .receiveRequest(
+            requestHash,
+            0,
+            tokenAddressBytes32,
+            TOKEN_AMOUNT,
+            ethAddress,
+            0,
+          );
+      }
+
+      await expect(
+        most
+          .connect(accounts[1])
+          .cancelRequest(
+            requestHash,
+            0,
+            tokenAddressBytes32,
+            TOKEN_AMOUNT,
+            ethAddress,
+            0,
+          ),
+      ).to.be.revertedWithCustomError(most, "RequestAlreadyProcessed");
+    });
+
+    it("Reverts if caller is not a guardian", async () => {
+      const { most, tokenAddressBytes32 } = await loadFixture(
+        deployEightGuardianMostFixture,
+      );
+      const accounts = await ethers.getSigners();
+      const ethAddress = addressToBytes32(accounts[10].address);
+      const requestHash = ethers.solidityPackedKeccak256(
+        ["uint256", "bytes32", "uint256", "bytes32", "uint256"],
+        [0, tokenAddressBytes32, TOKEN_AMOUNT, ethAddress, 0],
+      );
+
+      await expect(
+        most
+          .connect(accounts[0])
+          .cancelRequest(
+            requestHash,
+            0,
+            tokenAddressBytes32,
+            TOKEN_AMOUNT,
+            ethAddress,
+            0,
+          ),
+      ).to.be.revertedWithCustomError(most, "NotInCommittee");
+    });
+  });
+
+  describe("Request domain", function () {
+    it("Hashes match the shared test vectors", async () => {
+      const { most } = await loadFixture(deployEightGuardianMostFixture);
+
+      for (const vector of REQUEST_HASH_V1_VECTORS) {
+        const domainSeparator = await most.requestDomainSeparator(
+          vector.src_chain_id,
+          vector.src_contract,
+          vector.dest_contract,
+        );
+        expect(domainSeparator, vector.description).to.equal(
+          vector.domain_separator,
+        );
+        expect(
+          await most.hashRequestDataV1(
+            domainSeparator,
+            vector.committee_id,
+            vector.dest_token_address,
+            vector.amount,
+            vector.dest_receiver_address,
+            vector.request_nonce,
+          ),
+          vector.description,
+        ).to.equal(vector.request_hash);
+      }
+    });
+
+    it("Can only be set once", async () => {
+      const { most } = await loadFixture(deployEightGuardianMostFixture);
+
+      await expect(
+        most.setRequestDomain(1, ALEPH_ACCOUNT, 5),
+      ).to.be.revertedWithCustomError(most, "ExpectedPause");
+
+      await most.pause();
+      await expect(most.setRequestDomain(1, ALEPH_ACCOUNT, 5))
+        .to.emit(most, "RequestDomainSet")
+        .withArgs(1, ALEPH_ACCOUNT, 5);
+      await expect(
+        most.setRequestDomain(2, ALEPH_ACCOUNT, 5),
+      ).to.be.revertedWithCustomError(most, "RequestDomainAlreadySet");
+      const domain = await most.requestDomain();
+      expect(domain.srcChainId).to.equal(1);
+      expect(domain.srcContract).to.equal(ALEPH_ACCOUNT);
+      expect(domain.fromNonce).to.equal(5);
+    });
+
+    it("Can only start above the processed nonces", async () => {
+      const { most, token, tokenAddressBytes32 } = await loadFixture(
+        deployEightGuardianMostFixture,
+      );
+      const accounts = await ethers.getSigners();
+      const ethAddress = addressToBytes32(accounts[10].address);
+
+      // Provide funds for Most
+      await token.transfer(await most.getAddress(), TOKEN_AMOUNT * 2);
+
+      for (const nonce of [3, 1]) {
+        const requestHash = ethers.solidityPackedKeccak256(
+          ["uint256", "bytes32", "uint256", "bytes32", "uint256"],
+          [0, tokenAddressBytes32, TOKEN_AMOUNT, ethAddress, nonce],
+        );
+        for (let i = 1; i < 6; i++) {
+          await most
+            .connect(accounts[i])
+            .receiveRequest(
+              requestHash,
+              0,
+              tokenAddressBytes32,
+              TOKEN_AMOUNT,
+              ethAddress,
+              nonce,
+            );
+        }
+      }
+      const processed = await most.processedNonces();
+      expect(processed.any).to.be.true;
+      expect(processed.highest).to.equal(3);
+
+      await most.pause();
+      for (const fromNonce of [1, 3]) {
+        await expect(
+          most.setRequestDomain(1, ALEPH_ACCOUNT, fromNonce),
+        ).to.be.revertedWithCustomError(most, "NonceAlreadyProcessed");
+      }
+      await expect(most.setRequestDomain(1, ALEPH_ACCOUNT, 4))
+        .to.emit(most, "RequestDomainSet")
+        .withArgs(1, ALEPH_ACCOUNT, 4);
+    });
+
+    it("Binds the requests from its nonce on", async () => {
+      const { most, tokenAddressBytes32, mostAddress } = await loadFixture(
+        deployEightGuardianMostFixture,
+      );
+      const accounts = await ethers.getSigners();
+      const ethAddress = addressToBytes32(accounts[10].address);
+
+      await most.pause();
+      await most.setRequestDomain(1, ALEPH_ACCOUNT, 5);
+      await most.unpause();
+
+      const domainSeparator = ethers.solidityPackedKeccak256(
+        ["string", "uint256", "bytes32", "bytes32"],
+        ["MOST_REQUEST_V1", 1, ALEPH_ACCOUNT, addressToBytes32(mostAddress)],
+      );
+      for (const nonce of [4, 5]) {
+        const legacyHash = ethers.solidityPackedKeccak256(
+          ["uint256", "bytes32", "uint256", "bytes32", "uint256"],
+          [0, tokenAddressBytes32, TOKEN_AMOUNT, ethAddress, nonce],
+        );
+        const v1Hash = ethers.solidityPackedKeccak256(
+          ["bytes32", "uint256", "bytes32", "uint256", "bytes32", "uint256"],
+          [
+            domainSeparator,
+            0,
+            tokenAddressBytes32,
+            TOKEN_AMOUNT,
+            ethAddress,
+            nonce,
+          ],
+        );
+        const [valid, invalid] =
+          nonce < 5 ? [legacyHash, v1Hash] : [v1Hash, legacyHash];
+
+        await expect(
+          most
+            .connect(accounts[1])
+            .receiveRequest(
+              invalid,
+              0,
+              tokenAddressBytes32,
+              TOKEN_AMOUNT,
+              ethAddress,
+              nonce,
+            ),
+        ).to.be.revertedWithCustomError(most, "DataHashMismatch");
+        await expect(
+          most
+            .connect(accounts[1])
+            .receiveRequest(
+              valid,
+              0,
+              tokenAddressBytes32,
+              TOKEN_AMOUNT,
+              ethAddress,
+              nonce,
+            ),
+        )
+          .to.emit(most, "RequestSigned")
+          .withArgs(valid, accounts[1].address);
+      }
+    });
+  });
+
   describe("Upgrade", function () {
     it("Most contract can be upgraded", async () => {
       exec(`cp ./contracts/Most.sol ./contracts/MostV2.sol`, (error) => {
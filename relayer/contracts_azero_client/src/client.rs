@@ -1,9 +1,9 @@
-use std::{
-    sync::atomic::{AtomicU64, Ordering},
-    time::Duration,
-};
+mod contract_storage;
+mod finalized_events;
+
+use std::{future::Future, time::Duration};
 
-use log::trace;
+use log::{trace, warn};
 use pallet_contracts::ContractExecResult;
 use parity_scale_codec::Decode;
 use subxt::{
@@ -11,23 +11,95 @@ use subxt::{
         legacy::LegacyRpcMethods,
         rpc::reconnecting_rpc_client::{Client as RpcClient, ExponentialBackoff},
     },
+    blocks::ExtrinsicEvents,
     config::DefaultExtrinsicParamsBuilder,
     dynamic::Value,
     error::RpcError,
     ext::scale_value::value,
     runtime_api::RuntimeApi,
-    tx::{PartialExtrinsic, Payload, SubmittableExtrinsic},
+    tx::{PartialExtrinsic, Payload, SubmittableExtrinsic, TxProgress},
     utils::MultiAddress,
     Error, OnlineClient, PolkadotConfig,
 };
+use tokio::sync::Mutex;
 
 use crate::{
     translate_events, AccountId, Balance, BlockHash, ContractCallArgs, ContractEvent,
-    ContractInstance, EventRecord, MultiSignature, Signer, TxInfo, Weight,
+    ContractInstance, EventRecord, MultiSignature, Signer, Weight,
 };
 
 const LOG_TARGET: &str = "AzeroClient";
 
+/// How many times the nonce is resynchronized with the chain before a submission is given up.
+const MAX_NONCE_RESYNCS: usize = 3;
+
+/// Message of the transaction pool error for a nonce already used by a transaction in the pool.
+const NONCE_IN_POOL_ERROR: &str = "Priority is too low";
+
+/// Messages of the transaction pool errors which mean the nonce we used is out of sync with the
+/// chain: `Stale`, `Future` and another transaction with the same nonce already in the pool.
+const NONCE_ERRORS: [&str; 3] = [
+    "Transaction is outdated",
+    "Transaction will be valid in the future",
+    NONCE_IN_POOL_ERROR,
+];
+
+fn is_nonce_error(error: &Error) -> bool {
+    let Error::Rpc(error) = error else {
+        return false;
+    };
+    let error = error.to_string();
+
+    NONCE_ERRORS.iter().any(|message| error.contains(message))
+}
+
+/// The nonce to retry with after the pool rejected `rejected` with `error`. `next_index` is the
+/// next nonce of the account, counting its transactions in the pool. A nonce that is already used
+/// by a transaction in the pool is never retried.
+fn resynced_nonce(rejected: u64, error: &Error, next_index: u64) -> u64 {
+    if error.to_string().contains(NONCE_IN_POOL_ERROR) {
+        next_index.max(rejected + 1)
+    } else {
+        next_index
+    }
+}
+
+/// Submits with `nonce`, which is advanced past the accepted one. While the pool rejects the
+/// nonce, it is resynchronized from `next_index` and the submission retried, at most
+/// `MAX_NONCE_RESYNCS` times.
+async fn submit_with_resync<T, Submit, SubmitFuture, NextIndex, NextIndexFuture>(
+    nonce: &mut u64,
+    mut submit: Submit,
+    mut next_index: NextIndex,
+) -> ClientResult<T>
+where
+    Submit: FnMut(u64) -> SubmitFuture,
+    SubmitFuture: Future<Output = ClientResult<T>>,
+    NextIndex: FnMut() -> NextIndexFuture,
+    NextIndexFuture: Future<Output = ClientResult<u64>>,
+{
+    let mut resyncs = 0;
+
+    loop {
+        match submit(*nonce).await {
+            Ok(submitted) => {
+                *nonce += 1;
+                return Ok(submitted);
+            }
+            Err(ClientError::Subxt(why)) if is_nonce_error(&why) && resyncs < MAX_NONCE_RESYNCS => {
+                resyncs += 1;
+                let resynced = resynced_nonce(*nonce, &why, next_index().await?);
+                warn!(
+                    target: LOG_TARGET,
+                    "Nonce {} rejected: {}, resyncing to {}", *nonce, why, resynced
+                );
+                *nonce = resynced;
+            }
+            Err(why) => return Err(why),
+        }
+    }
+}
+
 fn get_args_for_runtime_call(args: ContractCallArgs) -> Vec<Value> {
     let gas_limit = match args.gas_limit {
         Some(w) => Value::unnamed_variant(
@@ -69,6 +141,24 @@ fn get_args_for_rpc_call(
     ]
 }
 
+/// How the calls of a batch submitted with `ClientWithSigner::contract_call_batch` are dispatched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchMode {
+    /// `Utility::batch_all`, if any call fails the whole extrinsic fails and nothing is applied.
+    All,
+    /// `Utility::force_batch`, calls that fail are skipped and the others are applied.
+    Force,
+}
+
+/// A contract call submitted as part of a batch.
+#[derive(Debug, Clone)]
+pub struct BatchedCall {
+    pub contract_address: AccountId,
+    pub value: Balance,
+    pub weight: Weight,
+    pub call_data: Vec<u8>,
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum ClientError {
     #[error("RpcError: {0}")]
@@ -77,6 +167,12 @@ pub enum ClientError {
     Subxt(#[from] Error),
     #[error("No block with number: {0}")]
     NoBlockWithNumber(u32),
+    #[error("No contract at: {0}")]
+    NoContractAt(AccountId),
+    #[error("DecodeError: {0}")]
+    Decode(#[from] parity_scale_codec::Error),
+    #[error("Signer error: {0}")]
+    Signer(String),
     #[error("InternalError")]
     Internal,
 }
@@ -109,6 +205,7 @@ impl ClientConfig {
 pub struct Client {
     inner: OnlineClient<PolkadotConfig>,
     methods: LegacyRpcMethods<PolkadotConfig>,
+    rpc: subxt::backend::rpc::RpcClient,
 }
 
 impl Client {
@@ -118,9 +215,14 @@ impl Client {
             .build(config.ws_address())
             .await?;
         let methods = LegacyRpcMethods::new(subxt::backend::rpc::RpcClient::new(rpc.clone()));
-        let inner = OnlineClient::from_rpc_client(rpc).await?;
+        let inner = OnlineClient::from_rpc_client(rpc.clone()).await?;
+        let rpc = subxt::backend::rpc::RpcClient::new(rpc);
 
-        Ok(Self { inner, methods })
+        Ok(Self {
+            inner,
+            methods,
+            rpc,
+        })
     }
 
     pub async fn get_finalized_block_hash(&self) -> ClientResult<BlockHash> {
@@ -135,6 +237,11 @@ impl Client {
             .map(|d| d.block.header.number))
     }
 
+    /// The next nonce of `account`, counting its transactions in the pool.
+    async fn account_next_index(&self, account: &AccountId) -> ClientResult<u64> {
+        Ok(self.methods.system_account_next_index(account).await?)
+    }
+
     pub async fn with_signer<S: Signer>(&self, signer: S) -> ClientResult<ClientWithSigner<S>> {
         ClientWithSigner::new(self.clone(), signer).await
     }
@@ -194,24 +301,28 @@ impl Client {
 pub struct ClientWithSigner<S: Signer> {
     client: Client,
     signer: S,
-    nonce: AtomicU64,
+    /// The next nonce to use. It stays locked from signing until the extrinsic is accepted by the
+    /// pool, so that concurrent submissions get consecutive nonces and a failed one releases its
+    /// nonce to the next.
+    nonce: Mutex<u64>,
 }
 
 impl<S: Signer> ClientWithSigner<S> {
     pub async fn new(client: Client, signer: S) -> ClientResult<Self> {
-        let nonce = client.inner.tx().account_nonce(signer.account_id()).await?;
+        let nonce = client.account_next_index(signer.account_id()).await?;
 
         Ok(Self {
             client,
             signer,
-            nonce: AtomicU64::new(nonce),
+            nonce: Mutex::new(nonce),
         })
     }
+
     fn get_tx<Call: Payload>(
         &self,
         call: &Call,
+        nonce: u64,
     ) -> ClientResult<PartialExtrinsic<PolkadotConfig, OnlineClient<PolkadotConfig>>> {
-        let nonce = self.nonce.load(Ordering::Relaxed);
         let params = DefaultExtrinsicParamsBuilder::default()
             .nonce(nonce)
             .build();
@@ -225,11 +336,15 @@ impl<S: Signer> ClientWithSigner<S> {
         Ok(tx)
     }
 
-    async fn sign_call<Call: Payload>(&self, call: &Call) -> ClientResult<MultiSignature> {
+    async fn sign_call<Call: Payload>(
+        &self,
+        call: &Call,
+        nonce: u64,
+    ) -> ClientResult<MultiSignature> {
         // PartialExtrinsic is not Send when Call is of type DynamicPayload,
         // so it cant live past any await.
         let payload = {
-            let tx = self.get_tx(call)?;
+            let tx = self.get_tx(call, nonce)?;
             tx.signer_payload()
         };
 
@@ -237,37 +352,51 @@ impl<S: Signer> ClientWithSigner<S> {
             .signer
             .sign(&payload)
             .await
-            .map_err(|_| ClientError::Internal)?;
+            .map_err(|why| ClientError::Signer(format!("{why:?}")))?;
 
         Ok(signature)
     }
 
     async fn get_submittable<Call: Payload + Send + Sync>(
         &self,
-        call: Call,
+        call: &Call,
+        nonce: u64,
     ) -> ClientResult<SubmittableExtrinsic<PolkadotConfig, OnlineClient<PolkadotConfig>>> {
-        let signature = self.sign_call(&call).await?;
+        let signature = self.sign_call(call, nonce).await?;
         let address = MultiAddress::Id(self.signer.account_id().clone());
 
-        let extr = self.get_tx(&call)?;
+        let extr = self.get_tx(call, nonce)?;
 
         Ok(extr.sign_with_address_and_signature(&address, &signature))
     }
 
+    /// Signs `call` with the next nonce and submits it. If the pool rejects the nonce, e.g. because
+    /// a previous submission was dropped or another tool uses the same account, the nonce is
+    /// resynchronized with the chain and the pool, and the submission retried.
+    async fn submit<Call: Payload + Send + Sync>(
+        &self,
+        call: &Call,
+    ) -> ClientResult<TxProgress<PolkadotConfig, OnlineClient<PolkadotConfig>>> {
+        let mut nonce = self.nonce.lock().await;
+
+        submit_with_resync(
+            &mut nonce,
+            |nonce| async move {
+                let tx = self.get_submittable(call, nonce).await?;
+                Ok(tx.submit_and_watch().await?)
+            },
+            || self.client.account_next_index(self.signer.account_id()),
+        )
+        .await
+    }
+
     async fn send_tx_with_params<Call: Payload + Send + Sync>(
         &self,
         tx: Call,
-    ) -> ClientResult<TxInfo> {
-        let tx = self.get_submittable(tx).await?;
-        self.inc_nonce();
+    ) -> ClientResult<ExtrinsicEvents<PolkadotConfig>> {
+        let events = self.submit(&tx).await?.wait_for_finalized_success().await?;
 
-        let events = tx
-            .submit_and_watch()
-            .await?
-            .wait_for_finalized_success()
-            .await?;
-
-        Ok(events.into())
+        Ok(events)
     }
 
     pub async fn contract_call(
@@ -276,7 +405,7 @@ impl<S: Signer> ClientWithSigner<S> {
         value: Balance,
         weight: Weight,
         call_data: Vec<u8>,
-    ) -> ClientResult<TxInfo> {
+    ) -> ClientResult<ExtrinsicEvents<PolkadotConfig>> {
         let args = get_args_for_rpc_call(weight, contract_address, value, call_data);
 
         let payload = subxt::tx::dynamic("Contracts", "call", args);
@@ -284,8 +413,34 @@ impl<S: Signer> ClientWithSigner<S> {
         self.send_tx_with_params(payload).await
     }
 
-    fn inc_nonce(&self) -> u64 {
-        self.nonce.fetch_add(1, Ordering::Relaxed)
+    /// Submits the contract calls in a single `Utility` batch extrinsic. Each call is followed by
+    /// an `ItemCompleted` or, with `BatchMode::Force`, an `ItemFailed` event.
+    pub async fn contract_call_batch(
+        &self,
+        calls: Vec<BatchedCall>,
+        mode: BatchMode,
+    ) -> ClientResult<ExtrinsicEvents<PolkadotConfig>> {
+        let calls = calls
+            .into_iter()
+            .map(|call| {
+                let args = get_args_for_rpc_call(
+                    call.weight,
+                    call.contract_address,
+                    call.value,
+                    call.call_data,
+                );
+                Value::unnamed_variant("Contracts", vec![Value::unnamed_variant("call", args)])
+            })
+            .collect::<Vec<_>>();
+
+        let function = match mode {
+            BatchMode::All => "batch_all",
+            BatchMode::Force => "force_batch",
+        };
+        let payload =
+            subxt::tx::dynamic("Utility", function, vec![Value::unnamed_composite(calls)]);
+
+        self.send_tx_with_params(payload).await
     }
 
     pub fn client(&self) -> &Client {
@@ -296,3 +451,115 @@ impl<S: Signer> ClientWithSigner<S> {
         self.signer.account_id()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, io};
+
+    use futures::executor::block_on;
+
+    use super::*;
+
+    fn pool_error(message: &str) -> Error {
+        Error::Rpc(RpcError::ClientError(Box::new(io::Error::other(format!(
+            "Invalid Transaction: {message}"
+        )))))
+    }
+
+    #[test]
+    fn test_is_nonce_error() {
+        for message in NONCE_ERRORS {
+            assert!(is_nonce_error(&pool_error(message)));
+        }
+
+        assert!(!is_nonce_error(&pool_error("Inability to pay some fees")));
+        assert!(!is_nonce_error(&Error::Other(
+            "Transaction is outdated".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_resynced_nonce() {
+        // stale and future nonces follow the pool, in either direction
+        assert_eq!(
+            resynced_nonce(3, &pool_error("Transaction is outdated"), 5),
+            5
+        );
+        assert_eq!(
+            resynced_nonce(7, &pool_error("Transaction will be valid in the future"), 5),
+            5
+        );
+        // a nonce taken by a transaction in the pool is never reused
+        assert_eq!(resynced_nonce(5, &pool_error(NONCE_IN_POOL_ERROR), 5), 6);
+        assert_eq!(resynced_nonce(5, &pool_error(NONCE_IN_POOL_ERROR), 3), 6);
+        assert_eq!(resynced_nonce(5, &pool_error(NONCE_IN_POOL_ERROR), 8), 8);
+    }
+
+    /// Pool accepting only `expected`, with its next index at `next_index`.
+    fn submit_to_pool(
+        expected: u64,
+        next_index: u64,
+        attempts: &RefCell<Vec<u64>>,
+        nonce: &mut u64,
+    ) -> ClientResult<u64> {
+        block_on(submit_with_resync(
+            nonce,
+            |nonce| {
+                attempts.borrow_mut().push(nonce);
+                async move {
+                    match nonce {
+                        nonce if nonce == expected => Ok(nonce),
+                        nonce if nonce < expected => {
+                            Err(pool_error("Transaction is outdated").into())
+                        }
+                        _ => Err(pool_error("Transaction will be valid in the future").into()),
+                    }
+                }
+            },
+            || async move { Ok(next_index) },
+        ))
+    }
+
+    #[test]
+    fn test_submit_resyncs_rejected_nonce() {
+        let attempts = RefCell::new(Vec::new());
+        let mut nonce = 2;
+
+        let submitted = submit_to_pool(5, 5, &attempts, &mut nonce);
+
+        assert_eq!(submitted.ok(), Some(5));
+        assert_eq!(nonce, 6);
+        assert_eq!(attempts.into_inner(), vec![2, 5]);
+    }
+
+    #[test]
+    fn test_submit_gives_up_after_max_resyncs() {
+        let attempts = RefCell::new(Vec::new());
+        let mut nonce = 2;
+
+        let submitted = submit_to_pool(5, 4, &attempts, &mut nonce);
+
+        assert!(matches!(submitted, Err(ClientError::Subxt(why)) if is_nonce_error(&why)));
+        assert_eq!(nonce, 4);
+        assert_eq!(attempts.into_inner().len(), MAX_NONCE_RESYNCS + 1);
+    }
+
+    #[test]
+    fn test_submit_does_not_resync_on_other_errors() {
+        let attempts = RefCell::new(Vec::new());
+        let mut nonce = 2;
+
+        let submitted: ClientResult<()> = block_on(submit_with_resync(
+            &mut nonce,
+            |nonce| {
+                attempts.borrow_mut().push(nonce);
+                async { Err(ClientError::Signer("unavailable".to_string())) }
+            },
+            || async { Ok(0) },
+        ));
+
+        assert!(matches!(submitted, Err(ClientError::Signer(_))));
+        assert_eq!(nonce, 2);
+        assert_eq!(attempts.into_inner(), vec![2]);
+    }
+}
@@ -0,0 +1,75 @@
+use parity_scale_codec::Decode;
+use subxt::{
+    backend::{legacy::rpc_methods::Bytes, rpc::rpc_params},
+    dynamic::Value,
+};
+
+use super::{Client, ClientError, ClientResult};
+use crate::{AccountId, BlockHash, StorageKey};
+
+/// Prefix of the keys of default child tries, which contract storage lives in.
+const DEFAULT_CHILD_STORAGE_KEY_PREFIX: &[u8] = b":child_storage:default:";
+
+impl Client {
+    /// The id of the child trie holding the storage of the contract at `address`, at the given
+    /// block or the latest one.
+    pub async fn contract_trie_id(
+        &self,
+        address: &AccountId,
+        at: Option<BlockHash>,
+    ) -> ClientResult<Vec<u8>> {
+        let query = subxt::dynamic::storage(
+            "Contracts",
+            "ContractInfoOf",
+            vec![Value::from_bytes(address)],
+        );
+        let storage = match at {
+            Some(at) => self.inner.storage().at(at),
+            None => self.inner.storage().at_latest().await?,
+        };
+        let info = storage
+            .fetch(&query)
+            .await?
+            .ok_or_else(|| ClientError::NoContractAt(address.clone()))?;
+
+        // `trie_id` is the first field of `ContractInfo`, the rest can be skipped.
+        Ok(Vec::<u8>::decode(&mut info.encoded())?)
+    }
+
+    /// Reads the raw value stored under `key` by the contract at `address`, at the given block or
+    /// the latest one.
+    pub async fn contract_storage_raw(
+        &self,
+        address: &AccountId,
+        key: &StorageKey,
+        at: Option<BlockHash>,
+    ) -> ClientResult<Option<Vec<u8>>> {
+        let trie_id = self.contract_trie_id(address, at).await?;
+        let child_storage_key = [DEFAULT_CHILD_STORAGE_KEY_PREFIX, &trie_id].concat();
+
+        let value: Option<Bytes> = self
+            .rpc
+            .request(
+                "childstate_getStorage",
+                rpc_params![Bytes(child_storage_key), Bytes(key.hashed()), at],
+            )
+            .await?;
+
+        Ok(value.map(|value| value.0))
+    }
+
+    /// Reads and decodes the value stored under `key` by the contract at `address`, at the given
+    /// block or the latest one. `Mapping` entries and `Lazy` fields that were never set are `None`.
+    pub async fn contract_storage<T: Decode>(
+        &self,
+        address: &AccountId,
+        key: &StorageKey,
+        at: Option<BlockHash>,
+    ) -> ClientResult<Option<T>> {
+        self.contract_storage_raw(address, key, at)
+            .await?
+            .map(|value| T::decode(&mut value.as_slice()))
+            .transpose()
+            .map_err(ClientError::from)
+    }
+}
@@ -0,0 +1,345 @@
+use std::{cmp::min, future::Future, ops::RangeInclusive};
+
+use async_trait::async_trait;
+use futures::{stream, stream::BoxStream, Stream, StreamExt, TryFutureExt, TryStreamExt};
+use log::warn;
+use subxt::{error::RpcError, Error};
+
+use super::{Client, ClientError, ClientResult, LOG_TARGET};
+use crate::{ContractEvent, ContractInstance};
+
+/// How many blocks are fetched at the same time while catching up.
+const BACKFILL_CONCURRENCY: usize = 16;
+/// Upper bound on the number of blocks handed out at once, so that a long backfill doesn't delay
+/// the first events.
+const MAX_BLOCKS_PER_RANGE: u32 = 1000;
+
+type FinalizedHeads = BoxStream<'static, Result<u32, Error>>;
+
+impl Client {
+    /// Streams the events emitted by `contracts` in every finalized block, starting at
+    /// `from_block`, in block order and without gaps. Blocks that are already finalized are
+    /// fetched concurrently, after which the stream follows the finalized heads subscription.
+    /// The subscription is renewed whenever the connection is reestablished, which is retried
+    /// according to `ClientConfig`.
+    pub fn finalized_contract_events<'a>(
+        &'a self,
+        from_block: u32,
+        contracts: &'a [&'a ContractInstance],
+    ) -> impl Stream<Item = ClientResult<(u32, Vec<ContractEvent>)>> + 'a {
+        finalized_blocks(self, from_block, move |block_number| {
+            self.fetch_events_from_contracts(block_number, contracts)
+        })
+    }
+}
+
+/// What `FinalizedBlocks` needs to know about the chain.
+#[async_trait]
+trait FinalizedChain {
+    async fn finalized_block_number(&self) -> ClientResult<u32>;
+
+    /// Numbers of the finalized heads, as they are announced.
+    async fn subscribe_finalized_heads(&self) -> ClientResult<FinalizedHeads>;
+}
+
+#[async_trait]
+impl FinalizedChain for Client {
+    async fn finalized_block_number(&self) -> ClientResult<u32> {
+        let hash = self.get_finalized_block_hash().await?;
+
+        self.get_block_number(hash)
+            .await?
+            .ok_or(ClientError::Internal)
+    }
+
+    async fn subscribe_finalized_heads(&self) -> ClientResult<FinalizedHeads> {
+        Ok(self
+            .methods
+            .chain_subscribe_finalized_heads()
+            .await?
+            .map_ok(|header| header.number)
+            .boxed())
+    }
+}
+
+/// Streams the result of `fetch` for every finalized block of `chain`, starting at `from_block`,
+/// in block order and without gaps.
+fn finalized_blocks<'a, C, T, Fetch, FetchFuture>(
+    chain: &'a C,
+    from_block: u32,
+    fetch: Fetch,
+) -> impl Stream<Item = ClientResult<(u32, T)>> + 'a
+where
+    C: FinalizedChain + Sync,
+    T: 'a,
+    Fetch: Fn(u32) -> FetchFuture + Copy + 'a,
+    FetchFuture: Future<Output = ClientResult<T>> + 'a,
+{
+    stream::try_unfold(
+        FinalizedBlocks::new(from_block),
+        move |mut blocks| async move {
+            let range = blocks.next_range(chain).await?;
+            Ok(Some((range, blocks)))
+        },
+    )
+    .map_ok(move |range| {
+        stream::iter(range)
+            .map(move |block_number| {
+                fetch(block_number).map_ok(move |fetched| (block_number, fetched))
+            })
+            .buffered(BACKFILL_CONCURRENCY)
+    })
+    .try_flatten()
+}
+
+/// Hands out ranges of finalized blocks, in order and without gaps.
+struct FinalizedBlocks {
+    next_block: u32,
+    finalized: Option<u32>,
+    heads: Option<FinalizedHeads>,
+}
+
+impl FinalizedBlocks {
+    fn new(from_block: u32) -> Self {
+        Self {
+            next_block: from_block,
+            finalized: None,
+            heads: None,
+        }
+    }
+
+    /// Waits until at least one block that wasn't handed out yet is finalized.
+    async fn next_range<C: FinalizedChain + Sync>(
+        &mut self,
+        chain: &C,
+    ) -> ClientResult<RangeInclusive<u32>> {
+        loop {
+            let finalized = match self.finalized {
+                Some(finalized) => finalized,
+                None => chain.finalized_block_number().await?,
+            };
+            self.finalized = Some(finalized);
+
+            if self.next_block <= finalized {
+                let range = self.next_block
+                    ..=min(
+                        finalized,
+                        self.next_block.saturating_add(MAX_BLOCKS_PER_RANGE - 1),
+                    );
+                self.next_block = range.end() + 1;
+
+                return Ok(range);
+            }
+
+            self.finalized = Some(self.next_finalized_head(chain).await?);
+        }
+    }
+
+    /// Heads may be skipped, e.g. while reconnecting, which is fine as the blocks are handed out
+    /// by number.
+    async fn next_finalized_head<C: FinalizedChain + Sync>(
+        &mut self,
+        chain: &C,
+    ) -> ClientResult<u32> {
+        loop {
+            let mut heads = match self.heads.take() {
+                Some(heads) => heads,
+                None => chain.subscribe_finalized_heads().await?,
+            };
+
+            match heads.next().await {
+                Some(Ok(number)) => {
+                    self.heads = Some(heads);
+                    return Ok(number);
+                }
+                Some(Err(Error::Rpc(RpcError::DisconnectedWillReconnect(why)))) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Finalized heads subscription interrupted, resubscribing: {}", why
+                    );
+                }
+                Some(Err(why)) => return Err(why.into()),
+                None => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Finalized heads subscription ended, resubscribing"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        pin::Pin,
+        sync::Mutex,
+        task::{Context, Poll},
+    };
+
+    use futures::executor::block_on;
+
+    use super::*;
+
+    /// A chain finalized up to `finalized`, whose subscriptions announce the given heads.
+    struct FakeChain {
+        finalized: u32,
+        subscriptions: Mutex<VecDeque<Vec<Result<u32, Error>>>>,
+        subscribed: Mutex<usize>,
+    }
+
+    impl FakeChain {
+        fn new(finalized: u32, subscriptions: Vec<Vec<Result<u32, Error>>>) -> Self {
+            Self {
+                finalized,
+                subscriptions: Mutex::new(subscriptions.into()),
+                subscribed: Mutex::new(0),
+            }
+        }
+
+        fn subscribed(&self) -> usize {
+            *self.subscribed.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl FinalizedChain for FakeChain {
+        async fn finalized_block_number(&self) -> ClientResult<u32> {
+            Ok(self.finalized)
+        }
+
+        async fn subscribe_finalized_heads(&self) -> ClientResult<FinalizedHeads> {
+            *self.subscribed.lock().unwrap() += 1;
+            let heads = self
+                .subscriptions
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("no more subscriptions");
+            Ok(stream::iter(heads).boxed())
+        }
+    }
+
+    fn disconnected() -> Result<u32, Error> {
+        Err(Error::Rpc(RpcError::DisconnectedWillReconnect(
+            "connection lost".into(),
+        )))
+    }
+
+    /// Returns `Pending` `times` times before completing.
+    struct Yield {
+        times: u32,
+    }
+
+    impl Future for Yield {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.times == 0 {
+                return Poll::Ready(());
+            }
+            self.times -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    fn block_numbers(chain: &FakeChain, from_block: u32, count: usize) -> Vec<u32> {
+        block_on(
+            finalized_blocks(
+                chain,
+                from_block,
+                |block_number| async move { Ok(block_number) },
+            )
+            .take(count)
+            .map(|block| block.expect("no errors").1)
+            .collect(),
+        )
+    }
+
+    #[test]
+    fn test_backfill_is_split_into_ranges() {
+        let chain = FakeChain::new(2500, vec![vec![Ok(2502)]]);
+        let mut blocks = FinalizedBlocks::new(1);
+
+        assert_eq!(block_on(blocks.next_range(&chain)).unwrap(), 1..=1000);
+        assert_eq!(block_on(blocks.next_range(&chain)).unwrap(), 1001..=2000);
+        assert_eq!(block_on(blocks.next_range(&chain)).unwrap(), 2001..=2500);
+        assert_eq!(chain.subscribed(), 0);
+
+        assert_eq!(block_on(blocks.next_range(&chain)).unwrap(), 2501..=2502);
+        assert_eq!(chain.subscribed(), 1);
+    }
+
+    #[test]
+    fn test_heads_behind_the_next_block_are_skipped() {
+        let chain = FakeChain::new(10, vec![vec![Ok(9), Ok(10), Ok(12)]]);
+        let mut blocks = FinalizedBlocks::new(11);
+
+        assert_eq!(block_on(blocks.next_range(&chain)).unwrap(), 11..=12);
+    }
+
+    #[test]
+    fn test_backfill_hands_off_to_subscription_in_order() {
+        let chain = FakeChain::new(40, vec![vec![Ok(45), Ok(50)]]);
+        let completed = Mutex::new(Vec::new());
+
+        // earlier blocks take longer to fetch, so they complete out of order
+        let blocks: Vec<u32> = block_on(
+            finalized_blocks(&chain, 1, |block_number| {
+                let completed = &completed;
+                async move {
+                    Yield {
+                        times: 50 - block_number,
+                    }
+                    .await;
+                    completed.lock().unwrap().push(block_number);
+                    Ok(block_number)
+                }
+            })
+            .take(50)
+            .map(|block| block.expect("no errors").1)
+            .collect(),
+        );
+
+        assert_eq!(blocks, (1..=50).collect::<Vec<_>>());
+        let completed = completed.into_inner().unwrap();
+        assert_ne!(completed, blocks);
+        // a range is finished before the next one is fetched
+        assert!(completed[..40].iter().all(|block| *block <= 40));
+    }
+
+    #[test]
+    fn test_resubscribes_after_reconnect() {
+        let chain = FakeChain::new(
+            10,
+            vec![vec![Ok(12), disconnected()], vec![Ok(13)], vec![Ok(15)]],
+        );
+
+        assert_eq!(block_numbers(&chain, 1, 15), (1..=15).collect::<Vec<_>>());
+        // once after the reconnect, once after the second subscription ended
+        assert_eq!(chain.subscribed(), 3);
+    }
+
+    #[test]
+    fn test_subscription_errors_end_the_stream() {
+        let chain = FakeChain::new(
+            2,
+            vec![vec![Err(Error::Other("subscription failed".into()))]],
+        );
+
+        let blocks: Vec<_> = block_on(
+            finalized_blocks(&chain, 1, |block_number| async move { Ok(block_number) }).collect(),
+        );
+
+        assert_eq!(blocks.len(), 3);
+        assert!(matches!(blocks[0], Ok((1, 1))));
+        assert!(matches!(blocks[1], Ok((2, 2))));
+        assert!(matches!(
+            blocks[2],
+            Err(ClientError::Subxt(Error::Other(_)))
+        ));
+    }
+}
@@ -1,18 +1,23 @@
 mod convertible_value;
 mod events;
 
-use std::fmt::{Debug, Formatter};
+use std::{
+    cmp::min,
+    fmt::{Debug, Formatter},
+};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use contract_transcode::{ContractMessageTranscoder, Value};
 pub use convertible_value::ConvertibleValue;
 pub use events::translate_events;
-use log::info;
+use log::{info, trace, warn};
 use pallet_contracts::ContractExecResult;
+use parity_scale_codec::{Decode, DecodeAll};
+use subxt::{blocks::ExtrinsicEvents, events::EventDetails, PolkadotConfig};
 
 use crate::{
-    client::ClientWithSigner, AccountId, Balance, BlockHash, Client, ContractCallArgs, EventRecord,
-    Signer, Weight,
+    client::ClientWithSigner, AccountId, Balance, BatchMode, BatchedCall, BlockHash, Client,
+    ContractCallArgs, ContractEvent, EventRecord, Signer, StorageKey, TxInfo, Weight,
 };
 /// Represents a contract instantiated on the chain.
 pub struct ContractInstance {
@@ -50,9 +55,26 @@ impl ReadonlyCallParams {
 #[derive(Debug, Clone, Default)]
 pub struct ExecCallParams {
     value: Balance,
-    max_gas: Option<Weight>,
+    gas: GasLimit,
 }
 
+/// How the gas limit of a call is chosen.
+#[derive(Debug, Clone, Default)]
+enum GasLimit {
+    /// The `gas_required` reported by the dry-run.
+    #[default]
+    Estimated,
+    Fixed(Weight),
+    /// The `gas_required` reported by the dry-run, scaled by `multiplier` and capped by `cap`.
+    Scaled {
+        multiplier: f64,
+        cap: Weight,
+    },
+}
+
+/// Above this share of the cap, in percent, estimated gas limits are logged as a warning.
+const GAS_CAP_WARNING_THRESHOLD: u64 = 90;
+
 impl ExecCallParams {
     /// Creates a new instance of `ExecCallParams`.
     pub fn new() -> Self {
@@ -66,9 +88,85 @@ impl ExecCallParams {
 
     /// Sets the `gas_limit` in the call.
     pub fn gas_limit(mut self, max_gas: Weight) -> Self {
-        self.max_gas = Some(max_gas);
+        self.gas = GasLimit::Fixed(max_gas);
         self
     }
+
+    /// Sets the `gas_limit` in the call to the gas required by its dry-run times `multiplier`,
+    /// but no more than `cap`. The dry-run itself is limited by `cap`.
+    pub fn estimated_gas_limit(mut self, multiplier: f64, cap: Weight) -> Self {
+        self.gas = GasLimit::Scaled { multiplier, cap };
+        self
+    }
+
+    fn dry_run_gas_limit(&self) -> Option<Weight> {
+        match &self.gas {
+            GasLimit::Estimated => None,
+            GasLimit::Fixed(limit) | GasLimit::Scaled { cap: limit, .. } => Some(limit.clone()),
+        }
+    }
+
+    fn gas_limit_for(&self, message: &str, required: Weight) -> Weight {
+        match &self.gas {
+            GasLimit::Estimated => required,
+            GasLimit::Fixed(limit) => limit.clone(),
+            GasLimit::Scaled { multiplier, cap } => {
+                // Float to int casts saturate.
+                let scaled = Weight::new(
+                    (required.ref_time as f64 * multiplier) as u64,
+                    (required.proof_size as f64 * multiplier) as u64,
+                );
+                if is_close_to_cap(&scaled, cap) {
+                    warn!(
+                        target: "aleph_client::contract",
+                        "Estimated gas for {} ({:?}) is close to the cap ({:?})", message, scaled, cap
+                    );
+                }
+
+                Weight::new(
+                    min(scaled.ref_time, cap.ref_time),
+                    min(scaled.proof_size, cap.proof_size),
+                )
+            }
+        }
+    }
+}
+
+/// Whether `gas` is above `GAS_CAP_WARNING_THRESHOLD` percent of `cap` in either dimension.
+fn is_close_to_cap(gas: &Weight, cap: &Weight) -> bool {
+    gas.ref_time > cap.ref_time / 100 * GAS_CAP_WARNING_THRESHOLD
+        || gas.proof_size > cap.proof_size / 100 * GAS_CAP_WARNING_THRESHOLD
+}
+
+/// Outcome of a contract call that was submitted to chain.
+#[derive(Debug, Clone)]
+pub struct ExecOutcome<T = ()> {
+    /// Where the call was included and how much it cost.
+    pub tx_info: TxInfo,
+    /// Events emitted by the called contract, other contracts' events are skipped.
+    pub events: Vec<ContractEvent>,
+    /// The value returned by the dry-run of the call.
+    pub result: T,
+}
+
+/// A contract call that was dry-run and is ready to be submitted, see `exec_batch`.
+#[derive(Debug, Clone)]
+pub struct PreparedCall<T = ()> {
+    /// The label of the called message.
+    pub message: String,
+    /// The value returned by the dry-run of the call.
+    pub result: T,
+    call: BatchedCall,
+}
+
+/// The dry-run of a call reverted, so the call was not submitted. Returned (wrapped in an
+/// `anyhow::Error`) by `exec_encoded` and `prepare_encoded`, with `result` being the decoded
+/// return value, e.g. an `Err` holding the contract's error.
+#[derive(Debug, thiserror::Error)]
+#[error("Dry-run call to {message} reverted, decoded result: {result:?}")]
+pub struct Reverted<T: Debug> {
+    pub message: String,
+    pub result: T,
 }
 
 impl ContractInstance {
@@ -118,13 +216,44 @@ impl ContractInstance {
         ConvertibleValue(decoded).try_into()?
     }
 
+    /// Reads the value of a read-only call via RPC, with the call data already encoded. This is what
+    /// the bindings generated with `ink_abigen!` use, `E` being the `LangError` of the contract.
+    pub async fn read_encoded<T: Decode, E: Decode + Debug>(
+        &self,
+        conn: &Client,
+        message: &str,
+        data: Vec<u8>,
+        params: ReadonlyCallParams,
+    ) -> Result<T> {
+        let sender = params.sender.unwrap_or(self.address.clone());
+
+        let result = self
+            .dry_run_encoded(conn, data, sender, 0, None, params.at)
+            .await?
+            .result
+            .map_err(|e| anyhow!("Contract exec failed {:?}", e))?;
+
+        decode_message_result::<T, E>(message, &result.data)
+    }
+
+    /// Reads and decodes the value stored under `key` directly from the contract's storage, without
+    /// calling any message. See `Client::contract_storage`.
+    pub async fn storage<T: Decode>(
+        &self,
+        conn: &Client,
+        key: &StorageKey,
+        at: Option<BlockHash>,
+    ) -> Result<Option<T>> {
+        Ok(conn.contract_storage(&self.address, key, at).await?)
+    }
+
     /// Executes a 0-argument contract call sending with a given params.
     pub async fn exec0<S: Signer>(
         &self,
         conn: &ClientWithSigner<S>,
         message: &str,
         params: ExecCallParams,
-    ) -> Result<()> {
+    ) -> Result<ExecOutcome> {
         self.exec::<S, String>(conn, message, &[], params).await
     }
 
@@ -135,7 +264,7 @@ impl ContractInstance {
         message: &str,
         args: &[S],
         params: ExecCallParams,
-    ) -> Result<()> {
+    ) -> Result<ExecOutcome> {
         let dry_run_result = self
             .exec_dry_run(
                 conn.client(),
@@ -147,18 +276,158 @@ impl ContractInstance {
             .await?;
 
         let data = self.encode(message, args)?;
-        conn.contract_call(
-            self.address.clone(),
-            params.value,
-            params.max_gas.unwrap_or(Weight::new(
-                dry_run_result.gas_required.ref_time(),
-                dry_run_result.gas_required.proof_size(),
-            )),
-            data,
-        )
-        .await?;
+        let events = conn
+            .contract_call(
+                self.address.clone(),
+                params.value,
+                params.gas_limit_for(
+                    message,
+                    Weight::new(
+                        dry_run_result.gas_required.ref_time(),
+                        dry_run_result.gas_required.proof_size(),
+                    ),
+                ),
+                data,
+            )
+            .await?;
 
-        Ok(())
+        Ok(self.outcome(&events, ()))
+    }
+
+    /// Executes a contract call with the call data already encoded, see `read_encoded`. The call
+    /// is only submitted if its dry-run didn't revert, otherwise `Reverted` is returned.
+    pub async fn exec_encoded<Si, T, E>(
+        &self,
+        conn: &ClientWithSigner<Si>,
+        message: &str,
+        data: Vec<u8>,
+        params: ExecCallParams,
+    ) -> Result<ExecOutcome<T>>
+    where
+        Si: Signer,
+        T: Decode + Debug + Send + Sync + 'static,
+        E: Decode + Debug,
+    {
+        let call = self
+            .prepare_encoded::<Si, T, E>(conn, message, data, params)
+            .await?;
+
+        let events = conn
+            .contract_call(
+                self.address.clone(),
+                call.call.value,
+                call.call.weight,
+                call.call.call_data,
+            )
+            .await?;
+
+        Ok(self.outcome(&events, call.result))
+    }
+
+    /// Dry-runs a contract call with the call data already encoded, so that it can be submitted
+    /// along with others by `exec_batch`. Returns `Reverted` if the dry-run reverted.
+    pub async fn prepare_encoded<Si, T, E>(
+        &self,
+        conn: &ClientWithSigner<Si>,
+        message: &str,
+        data: Vec<u8>,
+        params: ExecCallParams,
+    ) -> Result<PreparedCall<T>>
+    where
+        Si: Signer,
+        T: Decode + Debug + Send + Sync + 'static,
+        E: Decode + Debug,
+    {
+        let dry_run_result = self
+            .dry_run_encoded(
+                conn.client(),
+                data.clone(),
+                conn.account_id().clone(),
+                params.value,
+                params.dry_run_gas_limit(),
+                None,
+            )
+            .await?;
+
+        let result = dry_run_result
+            .result
+            .map_err(|e| anyhow!("Contract exec failed {:?}", e))?;
+        let decoded = decode_message_result::<T, E>(message, &result.data)?;
+        if result.did_revert() {
+            return Err(Reverted {
+                message: message.to_string(),
+                result: decoded,
+            }
+            .into());
+        }
+
+        Ok(PreparedCall {
+            message: message.to_string(),
+            result: decoded,
+            call: BatchedCall {
+                contract_address: self.address.clone(),
+                value: params.value,
+                weight: params.gas_limit_for(
+                    message,
+                    Weight::new(
+                        dry_run_result.gas_required.ref_time(),
+                        dry_run_result.gas_required.proof_size(),
+                    ),
+                ),
+                call_data: data,
+            },
+        })
+    }
+
+    /// Submits the prepared calls in a single batch extrinsic. The outcomes are in the order of
+    /// `calls`, with `BatchMode::Force` a call that failed doesn't affect the others.
+    pub async fn exec_batch<Si: Signer, T>(
+        &self,
+        conn: &ClientWithSigner<Si>,
+        calls: Vec<PreparedCall<T>>,
+        mode: BatchMode,
+    ) -> Result<Vec<Result<ExecOutcome<T>>>> {
+        let (calls, results): (Vec<_>, Vec<_>) = calls
+            .into_iter()
+            .map(|call| (call.call, (call.message, call.result)))
+            .unzip();
+        let count = calls.len();
+
+        let events = conn.contract_call_batch(calls, mode).await?;
+        let tx_info = TxInfo::from(&events);
+
+        let mut items = Vec::with_capacity(count);
+        let mut item_events = Vec::new();
+        for event in events.iter() {
+            let event = event?;
+            match (event.pallet_name(), event.variant_name()) {
+                ("Utility", "ItemCompleted") => items.push(Ok(std::mem::take(&mut item_events))),
+                ("Utility", "ItemFailed") => {
+                    item_events.clear();
+                    items.push(Err(event.field_values()?.to_string()));
+                }
+                _ => item_events.push(event),
+            }
+        }
+        if items.len() != count {
+            bail!(
+                "Expected {count} batch items, but the batch reported {}",
+                items.len()
+            );
+        }
+
+        Ok(items
+            .into_iter()
+            .zip(results)
+            .map(|(item, (message, result))| match item {
+                Ok(events) => Ok(ExecOutcome {
+                    tx_info,
+                    events: self.translate(events.into_iter().map(Ok::<_, subxt::Error>)),
+                    result,
+                }),
+                Err(why) => Err(anyhow!("Call to {message} failed in the batch: {why}")),
+            })
+            .collect())
     }
 
     /// Dry-runs contract call with the given params. Useful to measure gas or to check if
@@ -177,7 +446,7 @@ impl ContractInstance {
             args,
             sender,
             params.value,
-            params.max_gas,
+            params.dry_run_gas_limit(),
             None,
         )
         .await
@@ -195,12 +464,39 @@ impl ContractInstance {
         at: Option<BlockHash>,
     ) -> Result<ContractExecResult<Balance, EventRecord>> {
         let payload = self.encode(message, args)?;
+        let contract_read_result = self
+            .dry_run_encoded(conn, payload, sender, value, gas_limit, at)
+            .await?;
+
+        // For dry run, failed transactions don't return `Err` but `Ok(_)`
+        // and we have to inspect flags manually.
+        if let Ok(res) = &contract_read_result.result {
+            if res.did_revert() {
+                return Err(anyhow!(
+                    "Dry-run call reverted, decoded result: {:?}",
+                    self.decode(message, res.data.clone())
+                ));
+            }
+        }
+
+        Ok(contract_read_result)
+    }
+
+    async fn dry_run_encoded(
+        &self,
+        conn: &Client,
+        data: Vec<u8>,
+        sender: AccountId,
+        value: Balance,
+        gas_limit: Option<Weight>,
+        at: Option<BlockHash>,
+    ) -> Result<ContractExecResult<Balance, EventRecord>> {
         let args = ContractCallArgs {
             origin: sender,
             dest: self.address.clone(),
             value,
             gas_limit,
-            input_data: payload,
+            input_data: data,
             storage_deposit_limit: None,
         };
 
@@ -221,18 +517,33 @@ impl ContractInstance {
             );
         }
 
-        // For dry run, failed transactions don't return `Err` but `Ok(_)`
-        // and we have to inspect flags manually.
-        if let Ok(res) = &contract_read_result.result {
-            if res.did_revert() {
-                return Err(anyhow!(
-                    "Dry-run call reverted, decoded result: {:?}",
-                    self.decode(message, res.data.clone())
-                ));
-            }
+        Ok(contract_read_result)
+    }
+
+    fn outcome<T>(&self, events: &ExtrinsicEvents<PolkadotConfig>, result: T) -> ExecOutcome<T> {
+        ExecOutcome {
+            tx_info: TxInfo::from(events),
+            events: self.translate(events.iter()),
+            result,
         }
+    }
 
-        Ok(contract_read_result)
+    /// Translates the events emitted by this contract, skipping the others.
+    fn translate<Err, E>(&self, events: E) -> Vec<ContractEvent>
+    where
+        Err: std::error::Error + Into<anyhow::Error> + Send + Sync + 'static,
+        E: Iterator<Item = core::result::Result<EventDetails<PolkadotConfig>, Err>>,
+    {
+        translate_events(events, &[self])
+            .into_iter()
+            .filter_map(|event| match event {
+                Ok(event) => Some(event),
+                Err(why) => {
+                    trace!(target: "aleph_client::contract", "Skipping event: {:?}", why);
+                    None
+                }
+            })
+            .collect()
     }
 
     fn encode<S: AsRef<str> + Debug>(&self, message: &str, args: &[S]) -> Result<Vec<u8>> {
@@ -244,6 +555,14 @@ impl ContractInstance {
     }
 }
 
+/// Decodes the `MessageResult` every ink! 4 message returns.
+fn decode_message_result<T: Decode, E: Decode + Debug>(message: &str, data: &[u8]) -> Result<T> {
+    let result = core::result::Result::<T, E>::decode_all(&mut &data[..])
+        .with_context(|| format!("Failed to decode the result of {message}"))?;
+
+    result.map_err(|lang_error| anyhow!("Dispatching {message} failed: {:?}", lang_error))
+}
+
 impl Debug for ContractInstance {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ContractInstance")
@@ -251,3 +570,74 @@ impl Debug for ContractInstance {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scaled(multiplier: f64) -> ExecCallParams {
+        ExecCallParams::new().estimated_gas_limit(multiplier, Weight::new(1000, 100))
+    }
+
+    #[test]
+    fn test_gas_limit_scales_required_gas() {
+        assert_eq!(
+            scaled(1.5).gas_limit_for("test", Weight::new(200, 20)),
+            Weight::new(300, 30)
+        );
+        assert_eq!(
+            scaled(1.0).gas_limit_for("test", Weight::new(200, 20)),
+            Weight::new(200, 20)
+        );
+    }
+
+    #[test]
+    fn test_gas_limit_is_capped_per_dimension() {
+        assert_eq!(
+            scaled(2.0).gas_limit_for("test", Weight::new(600, 20)),
+            Weight::new(1000, 40)
+        );
+        assert_eq!(
+            scaled(2.0).gas_limit_for("test", Weight::new(200, 60)),
+            Weight::new(400, 100)
+        );
+        assert_eq!(
+            scaled(f64::MAX).gas_limit_for("test", Weight::new(u64::MAX, u64::MAX)),
+            Weight::new(1000, 100)
+        );
+    }
+
+    #[test]
+    fn test_other_gas_limits_ignore_the_multiplier() {
+        let required = Weight::new(200, 20);
+        assert_eq!(
+            ExecCallParams::new().gas_limit_for("test", required.clone()),
+            required
+        );
+        assert_eq!(
+            ExecCallParams::new()
+                .gas_limit(Weight::new(5, 5))
+                .gas_limit_for("test", required),
+            Weight::new(5, 5)
+        );
+    }
+
+    #[test]
+    fn test_dry_run_is_limited_by_the_cap() {
+        assert_eq!(
+            scaled(1.5).dry_run_gas_limit(),
+            Some(Weight::new(1000, 100))
+        );
+        assert_eq!(ExecCallParams::new().dry_run_gas_limit(), None);
+    }
+
+    #[test]
+    fn test_warning_threshold() {
+        let cap = Weight::new(1000, 100);
+        assert!(!is_close_to_cap(&Weight::new(900, 90), &cap));
+        assert!(is_close_to_cap(&Weight::new(901, 0), &cap));
+        assert!(is_close_to_cap(&Weight::new(0, 91), &cap));
+        // gas limits that are capped are always close to the cap
+        assert!(is_close_to_cap(&Weight::new(2000, 200), &cap));
+    }
+}
@@ -33,10 +33,12 @@ macro_rules! try_from_flat_value {
 
 try_from_flat_value!(bool, Bool, "boolean");
 try_from_flat_value!(char, Char, "char");
+try_from_flat_value!(u8, UInt, "unsigned integer");
 try_from_flat_value!(u16, UInt, "unsigned integer");
 try_from_flat_value!(u32, UInt, "unsigned integer");
 try_from_flat_value!(u64, UInt, "unsigned integer");
 try_from_flat_value!(u128, UInt, "unsigned integer");
+try_from_flat_value!(i8, Int, "signed integer");
 try_from_flat_value!(i16, Int, "signed integer");
 try_from_flat_value!(i32, Int, "signed integer");
 try_from_flat_value!(i64, Int, "signed integer");
@@ -68,44 +70,12 @@ impl TryFrom<ConvertibleValue> for AccountId {
     }
 }
 
-impl<T> TryFrom<ConvertibleValue> for Result<T>
-where
-    ConvertibleValue: TryInto<T, Error = anyhow::Error>,
-{
-    type Error = anyhow::Error;
-
-    fn try_from(value: ConvertibleValue) -> Result<Result<T>> {
-        if let Value::Tuple(tuple) = &value.0 {
-            match tuple.ident() {
-                Some(x) if x == "Ok" => {
-                    if tuple.values().count() == 1 {
-                        let item =
-                            ConvertibleValue(tuple.values().next().unwrap().clone()).try_into()?;
-                        return Ok(Ok(item));
-                    } else {
-                        bail!("Unexpected number of elements in Ok variant: {:?}", &value);
-                    }
-                }
-                Some(x) if x == "Err" => {
-                    if tuple.values().count() == 1 {
-                        return Ok(Err(anyhow!(value.to_string())));
-                    } else {
-                        bail!("Unexpected number of elements in Err variant: {:?}", &value);
-                    }
-                }
-                _ => (),
-            }
-        }
-
-        bail!("Expected {:?} to be an Ok(_) or Err(_) tuple.", value);
-    }
-}
-
 impl TryFrom<ConvertibleValue> for String {
     type Error = anyhow::Error;
 
     fn try_from(value: ConvertibleValue) -> Result<String> {
         let seq = match value.0 {
+            Value::String(string) => return Ok(string),
             Value::Seq(seq) => seq,
             _ =>  bail!("Failed parsing `ConvertibleValue` to `String`. Expected `Seq(Value::UInt)` but instead got: {:?}", value),
         };
@@ -125,100 +95,71 @@ impl TryFrom<ConvertibleValue> for String {
     }
 }
 
-impl TryFrom<ConvertibleValue> for Option<String> {
+impl<T: TryFrom<ConvertibleValue, Error = anyhow::Error>> TryFrom<ConvertibleValue> for Option<T> {
     type Error = anyhow::Error;
 
-    fn try_from(value: ConvertibleValue) -> Result<Option<String>> {
-        let tuple = match &value.0 {
-            Value::Tuple(tuple) => tuple,
-            _ => bail!("Expected {:?} to be a Some(_) or None Tuple.", &value),
-        };
-
-        match tuple.ident() {
-            Some(x) if x == "Some" => {
-                if tuple.values().count() == 1 {
-                    let item =
-                        ConvertibleValue(tuple.values().next().unwrap().clone()).try_into()?;
-                    Ok(Some(item))
-                } else {
-                    bail!(
-                        "Unexpected number of elements in Some(_) variant: {:?}. Expected one.",
-                        &value
-                    );
-                }
+    fn try_from(value: ConvertibleValue) -> Result<Option<T>> {
+        match value.ident().as_deref() {
+            Some("Some") => {
+                let [item] = value.unnamed_fields::<1>()?;
+                Ok(Some(item.try_into()?))
             }
-            Some(x) if x == "None" => {
-                if tuple.values().count() == 0 {
-                    Ok(None)
-                } else {
-                    bail!(
-                        "Unexpected number of elements in None variant: {:?}. Expected zero.",
-                        &value
-                    );
-                }
+            Some("None") => {
+                let [] = value.unnamed_fields::<0>()?;
+                Ok(None)
             }
-            _ => bail!(
-                "Expected `.ident()` to be `Some` or `None`, got: {:?}",
-                &tuple
-            ),
+            _ => bail!("Expected {:?} to be a Some(_) or None Tuple.", value),
         }
     }
 }
 
-impl TryFrom<ConvertibleValue> for Option<AccountId> {
+impl<T, E> TryFrom<ConvertibleValue> for core::result::Result<T, E>
+where
+    T: TryFrom<ConvertibleValue, Error = anyhow::Error>,
+    E: TryFrom<ConvertibleValue, Error = anyhow::Error>,
+{
     type Error = anyhow::Error;
 
-    fn try_from(value: ConvertibleValue) -> Result<Option<AccountId>> {
-        let tuple = match &value.0 {
-            Value::Tuple(tuple) => tuple,
-            _ => bail!("Expected {:?} to be a Some(_) or None Tuple.", &value),
-        };
-
-        match tuple.ident() {
-            Some(x) if x == "Some" => {
-                if tuple.values().count() == 1 {
-                    let item =
-                        ConvertibleValue(tuple.values().next().unwrap().clone()).try_into()?;
-                    Ok(Some(item))
-                } else {
-                    bail!(
-                        "Unexpected number of elements in Some(_) variant: {:?}. Expected one.",
-                        &value
-                    );
-                }
+    fn try_from(value: ConvertibleValue) -> Result<core::result::Result<T, E>> {
+        match value.ident().as_deref() {
+            Some("Ok") => {
+                let [item] = value.unnamed_fields::<1>()?;
+                Ok(Ok(item.try_into()?))
             }
-            Some(x) if x == "None" => {
-                if tuple.values().count() == 0 {
-                    Ok(None)
-                } else {
-                    bail!(
-                        "Unexpected number of elements in None variant: {:?}. Expected zero.",
-                        &value
-                    );
-                }
+            Some("Err") => {
+                let [error] = value.unnamed_fields::<1>()?;
+                Ok(Err(error.try_into()?))
             }
-            _ => bail!(
-                "Expected `.ident()` to be `Some` or `None`, got: {:?}",
-                &tuple
-            ),
+            _ => bail!("Expected {:?} to be an Ok(_) or Err(_) tuple.", value),
         }
     }
 }
 
+/// Errors that are only displayed, e.g. as the `E` of a `Result<T, anyhow::Error>`.
+impl TryFrom<ConvertibleValue> for anyhow::Error {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ConvertibleValue) -> Result<anyhow::Error> {
+        Ok(anyhow!(value.to_string()))
+    }
+}
+
 impl<Elem: TryFrom<ConvertibleValue, Error = anyhow::Error>> TryFrom<ConvertibleValue>
     for Vec<Elem>
 {
     type Error = anyhow::Error;
 
     fn try_from(value: ConvertibleValue) -> Result<Self> {
-        let seq = match value.0 {
-            Value::Seq(seq) => seq,
+        let elems = match value.0 {
+            Value::Seq(seq) => seq.elems().to_vec(),
+            // Byte sequences and arrays are decoded as hex.
+            Value::Hex(hex) => hex.bytes().iter().map(|byte| Value::UInt(*byte as u128)).collect(),
             _ =>  bail!("Failed parsing `ConvertibleValue` to `Vec<T>`. Expected `Seq(_)` but instead got: {:?}", value),
         };
 
         let mut result = vec![];
-        for element in seq.elems() {
-            result.push(ConvertibleValue(element.clone()).try_into()?);
+        for element in elems {
+            result.push(ConvertibleValue(element).try_into()?);
         }
 
         Ok(result)
@@ -237,12 +178,192 @@ impl<const N: usize, Elem: TryFrom<ConvertibleValue, Error = anyhow::Error> + De
     }
 }
 
+macro_rules! try_from_tuple {
+    ($($elem: ident $field: ident),+) => {
+        impl<$($elem: TryFrom<ConvertibleValue, Error = anyhow::Error>),+> TryFrom<ConvertibleValue>
+            for ($($elem,)+)
+        {
+            type Error = anyhow::Error;
+
+            fn try_from(value: ConvertibleValue) -> Result<Self> {
+                let [$($field),+] = value.unnamed_fields()?;
+                Ok(($($field.try_into()?,)+))
+            }
+        }
+    };
+}
+
+try_from_tuple!(A a);
+try_from_tuple!(A a, B b);
+try_from_tuple!(A a, B b, C c);
+try_from_tuple!(A a, B b, C c, D d);
+try_from_tuple!(A a, B b, C c, D d, E e);
+try_from_tuple!(A a, B b, C c, D d, E e, F f);
+
+impl ConvertibleValue {
+    /// The name of the struct or enum variant the value was decoded from, if any.
+    pub fn ident(&self) -> Option<String> {
+        match &self.0 {
+            Value::Map(map) => map.ident(),
+            Value::Tuple(tuple) => tuple.ident(),
+            _ => None,
+        }
+    }
+
+    /// Converts the field `name` of a struct or an enum variant with named fields.
+    pub fn field<T: TryFrom<ConvertibleValue, Error = anyhow::Error>>(
+        &self,
+        name: &str,
+    ) -> Result<T> {
+        let Value::Map(map) = &self.0 else {
+            bail!("Expected {:?} to have named fields", self.0);
+        };
+        let field = map
+            .get_by_str(name)
+            .ok_or_else(|| anyhow!("Missing field `{name}` in {:?}", self.0))?;
+
+        ConvertibleValue(field.clone())
+            .try_into()
+            .with_context(|| format!("Failed to convert field `{name}` of {:?}", self.0))
+    }
+
+    /// The fields of a tuple, a tuple struct or an enum variant with unnamed fields.
+    pub fn unnamed_fields<const N: usize>(&self) -> Result<[ConvertibleValue; N]> {
+        let Value::Tuple(tuple) = &self.0 else {
+            bail!("Expected {:?} to be a tuple", self.0);
+        };
+
+        tuple
+            .values()
+            .cloned()
+            .map(ConvertibleValue)
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| anyhow!("Expected {:?} to have {N} fields", self.0))
+    }
+}
+
+/// Implements `TryFrom<ConvertibleValue>` for a struct or an enum defined outside of this crate,
+/// e.g. a copy of a contract type. Fields are listed by name, or as `_` for unnamed ones.
+///
+/// ```ignore
+/// convertible_value!(struct Pair { first, second });
+/// convertible_value!(struct Wrapper(_));
+/// convertible_value!(enum RequestStatus { Pending, Signed(_, _), Processed { block } });
+/// ```
+#[macro_export]
+macro_rules! convertible_value {
+    (struct $ty: ident { $($field: ident),* $(,)? }) => {
+        $crate::convertible_value!(@impl $ty, value => {
+            Ok($crate::convertible_value!(@construct value, $ty { $($field),* }))
+        });
+    };
+    (struct $ty: ident ( $($field: tt),* $(,)? )) => {
+        $crate::convertible_value!(@impl $ty, value => {
+            Ok($crate::convertible_value!(@construct value, $ty ( $($field),* )))
+        });
+    };
+    (enum $ty: ident {
+        $($variant: ident $( ( $($unnamed: tt),* $(,)? ) )? $( { $($named: ident),* $(,)? } )?),* $(,)?
+    }) => {
+        $crate::convertible_value!(@impl $ty, value => {
+            match value.ident().as_deref() {
+                $(Some(stringify!($variant)) => Ok($crate::convertible_value!(
+                    @construct value,
+                    $ty::$variant $( ( $($unnamed),* ) )? $( { $($named),* } )?
+                )),)*
+                _ => $crate::anyhow::bail!(
+                    "Expected {:?} to be a variant of {}", value, stringify!($ty)
+                ),
+            }
+        });
+    };
+
+    (@impl $ty: ident, $value: ident => $body: block) => {
+        impl TryFrom<$crate::ConvertibleValue> for $ty {
+            type Error = $crate::anyhow::Error;
+
+            fn try_from($value: $crate::ConvertibleValue) -> $crate::anyhow::Result<$ty> {
+                $body
+            }
+        }
+    };
+    (@construct $value: ident, $($path: ident)::+ { $($field: ident),* }) => {
+        $($path)::+ { $($field: $value.field(stringify!($field))?),* }
+    };
+    (@construct $value: ident, $($path: ident)::+ ( $($field: tt),* )) => {{
+        let mut fields = $value
+            .unnamed_fields::<{ $crate::convertible_value!(@count $($field)*) }>()?
+            .into_iter();
+        $($path)::+ ( $($crate::convertible_value!(@next fields, $field)),* )
+    }};
+    (@construct $value: ident, $($path: ident)::+) => {{
+        $value.unnamed_fields::<0>()?;
+        $($path)::+
+    }};
+    (@count) => { 0 };
+    (@count $head: tt $($tail: tt)*) => { 1 + $crate::convertible_value!(@count $($tail)*) };
+    (@next $fields: ident, $field: tt) => {
+        $fields
+            .next()
+            .expect("the number of fields was checked")
+            .try_into()?
+    };
+}
+
 #[cfg(test)]
 mod tests {
-    use contract_transcode::Value::{Bool, Char, Int, Seq, UInt};
+    use std::str::FromStr;
+
+    use contract_transcode::{
+        Hex, Map, Tuple,
+        Value::{self, Bool, Char, Int, Seq, UInt},
+    };
 
     use crate::contract::ConvertibleValue;
 
+    #[derive(Debug, PartialEq, Eq)]
+    enum RequestStatus {
+        Pending,
+        Signed(u32, u128),
+        Processed { block: u32 },
+    }
+
+    convertible_value!(enum RequestStatus { Pending, Signed(_, _), Processed { block } });
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Pair {
+        first: u128,
+        second: Option<u64>,
+    }
+
+    convertible_value!(struct Pair { first, second });
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum MostError {
+        NotInCommittee,
+        PSP22(String),
+    }
+
+    convertible_value!(
+        enum MostError {
+            NotInCommittee,
+            PSP22(_),
+        }
+    );
+
+    fn variant(name: &str, values: Vec<Value>) -> Value {
+        Value::Tuple(Tuple::new(Some(name), values))
+    }
+
+    fn named(name: &str, fields: Vec<(&str, Value)>) -> Value {
+        let map = fields
+            .into_iter()
+            .map(|(field, value)| (Value::String(field.to_string()), value))
+            .collect();
+        Value::Map(Map::new(Some(name), map))
+    }
+
     #[test]
     fn converts_boolean() {
         let cast: bool = ConvertibleValue(Bool(true))
@@ -324,4 +445,83 @@ mod tests {
 
         assert_eq!(words, cast);
     }
+
+    #[test]
+    fn converts_tuple() {
+        let cv = ConvertibleValue(Value::Tuple(Tuple::new(None, vec![UInt(41), UInt(1)])));
+        let cast: (u128, u64) = cv.try_into().expect("Should cast successfully");
+        assert_eq!((41, 1), cast);
+    }
+
+    #[test]
+    fn converts_option_of_hex_array() {
+        let cv = ConvertibleValue(variant(
+            "Some",
+            vec![Value::Hex(Hex::from_str("0x0102").unwrap())],
+        ));
+        let cast: Option<[u8; 2]> = cv.try_into().expect("Should cast successfully");
+        assert_eq!(Some([1, 2]), cast);
+
+        let cv = ConvertibleValue(variant("None", vec![]));
+        let cast: Option<[u8; 2]> = cv.try_into().expect("Should cast successfully");
+        assert_eq!(None, cast);
+    }
+
+    #[test]
+    fn converts_result_with_decoded_error() {
+        let cv = ConvertibleValue(variant("Ok", vec![UInt(41)]));
+        let cast: Result<u32, MostError> = cv.try_into().expect("Should cast successfully");
+        assert_eq!(Ok(41), cast);
+
+        let cv = ConvertibleValue(variant(
+            "Err",
+            vec![variant("PSP22", vec![Value::String("Custom".to_string())])],
+        ));
+        let cast: Result<u32, MostError> = cv.try_into().expect("Should cast successfully");
+        assert_eq!(Err(MostError::PSP22("Custom".to_string())), cast);
+    }
+
+    #[test]
+    fn converts_enum() {
+        let cast: RequestStatus = ConvertibleValue(variant("Pending", vec![]))
+            .try_into()
+            .expect("Should cast successfully");
+        assert_eq!(RequestStatus::Pending, cast);
+
+        let cast: RequestStatus = ConvertibleValue(variant("Signed", vec![UInt(2), UInt(41)]))
+            .try_into()
+            .expect("Should cast successfully");
+        assert_eq!(RequestStatus::Signed(2, 41), cast);
+
+        let cast: RequestStatus = ConvertibleValue(named("Processed", vec![("block", UInt(41))]))
+            .try_into()
+            .expect("Should cast successfully");
+        assert_eq!(RequestStatus::Processed { block: 41 }, cast);
+    }
+
+    #[test]
+    fn rejects_unknown_variant() {
+        let cast: anyhow::Result<RequestStatus> =
+            ConvertibleValue(variant("Refunded", vec![])).try_into();
+        assert!(cast.is_err());
+    }
+
+    #[test]
+    fn converts_struct() {
+        let cv = ConvertibleValue(named(
+            "Pair",
+            vec![
+                ("first", UInt(41)),
+                ("second", variant("Some", vec![UInt(1)])),
+            ],
+        ));
+        let cast: Pair = cv.try_into().expect("Should cast successfully");
+        assert_eq!(
+            Pair {
+                first: 41,
+                second: Some(1)
+            },
+            cast
+        );
+    }
 }
@@ -42,7 +42,7 @@ fn translate_event(
         .transcoder
         .decode_contract_event(&mut data.as_slice())?;
 
-    build_event(matching_contract.address.clone(), data)
+    build_event(matching_contract.address.clone(), data, event.data.clone())
 }
 
 /// The contract transcoder assumes there is an extra byte (that it discards) indicating the size of the data. However,
@@ -53,7 +53,7 @@ fn zero_prefixed(data: &[u8]) -> Vec<u8> {
     result
 }
 
-fn build_event(address: AccountId, event_data: Value) -> Result<ContractEvent> {
+fn build_event(address: AccountId, event_data: Value, encoded: Vec<u8>) -> Result<ContractEvent> {
     match event_data {
         Value::Map(map) => Ok(ContractEvent {
             contract: address,
@@ -62,6 +62,7 @@ fn build_event(address: AccountId, event_data: Value) -> Result<ContractEvent> {
                 .iter()
                 .map(|(key, value)| (key.to_string(), value.clone()))
                 .collect(),
+            encoded,
         }),
         _ => bail!("Contract event data is not a map"),
     }
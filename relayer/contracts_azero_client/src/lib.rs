@@ -1,14 +1,19 @@
 mod client;
 mod contract;
 mod keypair;
+mod storage;
 mod types;
 
 use std::fmt::Debug;
 
-pub use client::{Client, ClientConfig, ClientError, ClientWithSigner};
+pub use anyhow;
+pub use client::{BatchMode, BatchedCall, Client, ClientConfig, ClientError, ClientWithSigner};
 pub use contract::*;
 pub use contract_transcode;
+pub use ink_abigen::ink_abigen;
 pub use keypair::*;
+pub use parity_scale_codec as codec;
+pub use storage::StorageKey;
 pub use types::*;
 
 #[async_trait::async_trait]
@@ -0,0 +1,59 @@
+use parity_scale_codec::Encode;
+use subxt::ext::sp_core::hashing::blake2_128;
+
+/// Key of a `Lazy` field or a `Mapping` entry in the storage of an ink! contract, e.g. an entry of
+/// `Mapping<K, V, ManualKey<0x5349474E>>` is stored under `StorageKey::mapping(0x5349474E, &k)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageKey(Vec<u8>);
+
+impl StorageKey {
+    /// The key of a `Lazy` field with the given root key.
+    pub fn lazy(root_key: u32) -> Self {
+        Self(root_key.encode())
+    }
+
+    /// The key of the entry under `key` in a `Mapping` with the given root key.
+    pub fn mapping<K: Encode>(root_key: u32, key: &K) -> Self {
+        Self((root_key, key).encode())
+    }
+
+    /// The key as passed by the contract to `seal_get_storage`.
+    pub fn raw(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// The key under which the value is stored in the child trie of the contract, as hashed by
+    /// `pallet_contracts` for variable length keys (`Blake2_128Concat`).
+    pub fn hashed(&self) -> Vec<u8> {
+        let mut hashed = blake2_128(&self.0).to_vec();
+        hashed.extend_from_slice(&self.0);
+        hashed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::StorageKey;
+
+    #[test]
+    fn hashes_lazy_key() {
+        let key = StorageKey::lazy(0x44415441);
+
+        assert_eq!(
+            hex::encode(key.hashed()),
+            "38811da9f5e9a5dd5c381217fcdfa8ff41544144"
+        );
+    }
+
+    #[test]
+    fn hashes_mapping_key() {
+        let key = StorageKey::mapping(0x5349474E, &([1u8; 32], [2u8; 32]));
+
+        assert_eq!(
+            hex::encode(key.hashed()),
+            "ac4826d1aa12784a9909399db19fb2fe4e474953\
+             0101010101010101010101010101010101010101010101010101010101010101\
+             0202020202020202020202020202020202020202020202020202020202020202"
+        );
+    }
+}
@@ -52,6 +52,8 @@ pub struct ContractEvent {
     pub name: Option<String>,
     /// Data contained in the event.
     pub data: HashMap<String, Value>,
+    /// The raw event data, as decoded by the event enums generated with `ink_abigen!`.
+    pub encoded: Vec<u8>,
 }
 
 #[derive(Encode)]
@@ -102,17 +104,40 @@ impl StaticEvent for ContractEmitted {
     const EVENT: &'static str = "ContractEmitted";
 }
 
+/// Event definition from the pallet_transaction_payment
+#[derive(Decode, Encode, DecodeAsType)]
+pub struct TransactionFeePaid {
+    pub who: AccountId,
+    pub actual_fee: Balance,
+    pub tip: Balance,
+}
+
+impl StaticEvent for TransactionFeePaid {
+    const PALLET: &'static str = "TransactionPayment";
+    const EVENT: &'static str = "TransactionFeePaid";
+}
+
 /// Data regarding submitted transaction.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct TxInfo {
+    /// Hash of the block the transaction was included in.
+    pub block_hash: BlockHash,
     /// Hash of the transaction itself.
     pub tx_hash: BlockHash,
+    /// Fee paid for the transaction, if the runtime reported it.
+    pub fee: Option<Balance>,
 }
 
-impl From<ExtrinsicEvents<PolkadotConfig>> for TxInfo {
-    fn from(ee: ExtrinsicEvents<PolkadotConfig>) -> Self {
+impl From<&ExtrinsicEvents<PolkadotConfig>> for TxInfo {
+    fn from(ee: &ExtrinsicEvents<PolkadotConfig>) -> Self {
         Self {
+            block_hash: ee.block_hash(),
             tx_hash: ee.extrinsic_hash(),
+            fee: ee
+                .find_first::<TransactionFeePaid>()
+                .ok()
+                .flatten()
+                .map(|fee_paid| fee_paid.actual_fee),
         }
     }
 }
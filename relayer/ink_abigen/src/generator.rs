@@ -0,0 +1,693 @@
+use std::collections::{BTreeMap, HashSet};
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+
+use crate::metadata::{
+    EventSpec, Field, InkProject, MessageSpec, Primitive, Type, TypeDef, TypeId,
+};
+
+pub type Result<T> = std::result::Result<T, String>;
+
+/// Names taken by the methods every contract struct has.
+const RESERVED_METHODS: [&str; 3] = ["new", "address", "instance"];
+
+/// Generates a module named after `name` (in snake case), containing the contract struct `name`,
+/// the types used by its messages and events, and an enum of its events. The contract struct is
+/// re-exported next to the module.
+pub fn generate(name: &Ident, metadata: &InkProject, source: Option<&str>) -> Result<TokenStream> {
+    let mut generator = Generator::new(metadata);
+    let module = ident(&name.to_string().to_snake_case());
+    let event_enum = ident(&format!("{name}Event"));
+    generator.reserve(&name.to_string());
+    generator.reserve(&event_enum.to_string());
+
+    let mut methods = Vec::new();
+    let mut method_names: HashSet<String> = RESERVED_METHODS
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    for message in &metadata.spec.messages {
+        methods.push(generator.message(message, &mut method_names)?);
+    }
+
+    let events = generator.events(&event_enum, &metadata.spec.events)?;
+    let items = generator.items;
+
+    // Makes the compiler rebuild the bindings whenever the metadata changes.
+    let include = source.map(|path| {
+        quote!(
+            const _: &[u8] = include_bytes!(#path);
+        )
+    });
+
+    Ok(quote! {
+        pub mod #module {
+            #![allow(clippy::all, non_camel_case_types)]
+
+            #include
+
+            #[derive(Debug)]
+            pub struct #name {
+                contract: ::contracts_azero_client::ContractInstance,
+            }
+
+            impl #name {
+                /// Creates bindings for the contract under `address`. The metadata is still
+                /// needed at runtime to translate events with `translate_events`.
+                pub fn new(
+                    address: ::contracts_azero_client::AccountId,
+                    metadata_path: &str,
+                ) -> ::contracts_azero_client::anyhow::Result<Self> {
+                    Ok(Self {
+                        contract: ::contracts_azero_client::ContractInstance::new(
+                            address,
+                            metadata_path,
+                        )?,
+                    })
+                }
+
+                pub fn address(&self) -> &::contracts_azero_client::AccountId {
+                    self.contract.address()
+                }
+
+                pub fn instance(&self) -> &::contracts_azero_client::ContractInstance {
+                    &self.contract
+                }
+
+                #(#methods)*
+            }
+
+            #events
+
+            #(#items)*
+        }
+
+        pub use #module::#name;
+    })
+}
+
+struct Generator<'a> {
+    types: BTreeMap<TypeId, &'a Type>,
+    /// Names of the types that have already been generated, or are being generated.
+    names: BTreeMap<TypeId, Ident>,
+    used_names: HashSet<String>,
+    items: Vec<TokenStream>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(metadata: &'a InkProject) -> Self {
+        Self {
+            types: metadata.types.iter().map(|ty| (ty.id, &ty.ty)).collect(),
+            names: BTreeMap::new(),
+            used_names: HashSet::new(),
+            items: Vec::new(),
+        }
+    }
+
+    fn reserve(&mut self, name: &str) {
+        self.used_names.insert(name.to_string());
+    }
+
+    /// Picks `preferred` if it's free, `preferred` followed by `suffix` otherwise.
+    fn allocate_name(&mut self, preferred: String, suffix: &str) -> Ident {
+        let mut name = preferred.clone();
+        let mut attempt = 1;
+        while self.used_names.contains(&name) {
+            name = match attempt {
+                1 => format!("{preferred}{suffix}"),
+                _ => format!("{preferred}{suffix}{attempt}"),
+            };
+            attempt += 1;
+        }
+        self.reserve(&name);
+
+        ident(&name)
+    }
+
+    fn ty(&self, id: TypeId) -> Result<&'a Type> {
+        self.types
+            .get(&id)
+            .copied()
+            .ok_or_else(|| format!("unknown type id {id}"))
+    }
+
+    fn type_ref(&mut self, id: TypeId) -> Result<TokenStream> {
+        let ty = self.ty(id)?;
+
+        match &ty.def {
+            TypeDef::Primitive(primitive) => primitive_ref(*primitive),
+            TypeDef::Sequence(sequence) => {
+                let inner = self.type_ref(sequence.ty)?;
+                Ok(quote!(::std::vec::Vec<#inner>))
+            }
+            TypeDef::Array(array) => {
+                let inner = self.type_ref(array.ty)?;
+                let len = Literal::usize_unsuffixed(array.len as usize);
+                Ok(quote!([#inner; #len]))
+            }
+            TypeDef::Tuple(ids) => {
+                let inner = ids
+                    .iter()
+                    .map(|id| self.type_ref(*id))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(quote!((#(#inner,)*)))
+            }
+            TypeDef::Compact(_) => Err(format!(
+                "compact type {id} is only supported as a field type"
+            )),
+            TypeDef::BitSequence(_) => Err(format!("bit sequence type {id} is not supported")),
+            TypeDef::Composite(_) | TypeDef::Variant(_) => self.named_type_ref(id, ty),
+        }
+    }
+
+    fn named_type_ref(&mut self, id: TypeId, ty: &'a Type) -> Result<TokenStream> {
+        let path: Vec<&str> = ty.path.iter().map(String::as_str).collect();
+
+        match path.as_slice() {
+            ["Option"] => {
+                let [some] = self.params::<1>(id, ty)?;
+                Ok(quote!(::core::option::Option<#some>))
+            }
+            ["Result"] => {
+                let [ok, err] = self.params::<2>(id, ty)?;
+                Ok(quote!(::core::result::Result<#ok, #err>))
+            }
+            ["ink_primitives", "types", "AccountId"] => {
+                Ok(quote!(::contracts_azero_client::AccountId))
+            }
+            ["ink_primitives", "types", "Hash"] => Ok(quote!(::contracts_azero_client::H256)),
+            _ => {
+                let name = self.define(id, ty)?;
+                Ok(quote!(#name))
+            }
+        }
+    }
+
+    fn params<const N: usize>(&mut self, id: TypeId, ty: &Type) -> Result<[TokenStream; N]> {
+        let params = ty
+            .params
+            .iter()
+            .map(|param| match param.ty {
+                Some(param) => self.type_ref(param),
+                None => Err(format!("type {id} has an unresolved type parameter")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        params
+            .try_into()
+            .map_err(|_| format!("type {id} should have {N} type parameter(s)"))
+    }
+
+    /// Generates a struct or an enum for a composite or variant type.
+    fn define(&mut self, id: TypeId, ty: &'a Type) -> Result<Ident> {
+        if let Some(name) = self.names.get(&id) {
+            return Ok(name.clone());
+        }
+
+        let preferred = match ty.path.last() {
+            Some(last) => last.clone(),
+            None => format!("Type{id}"),
+        };
+        let name = self.allocate_name(preferred, &id.to_string());
+        // Registered before generating the fields, so that recursive types terminate.
+        self.names.insert(id, name.clone());
+
+        let derives = derives();
+        let item = match &ty.def {
+            TypeDef::Composite(composite) => {
+                let fields = self.fields(&composite.fields, true)?;
+                let fields = match composite.fields.first() {
+                    Some(Field { name: Some(_), .. }) => fields,
+                    _ => quote!(#fields;),
+                };
+                quote! {
+                    #derives
+                    pub struct #name #fields
+                }
+            }
+            TypeDef::Variant(variant) => {
+                let mut variants = Vec::new();
+                for variant in &variant.variants {
+                    let variant_name = ident(&variant.name);
+                    let index = Literal::u8_unsuffixed(variant.index);
+                    let docs = docs(&variant.docs);
+                    let fields = self.fields(&variant.fields, false)?;
+                    variants.push(quote! {
+                        #docs
+                        #[codec(index = #index)]
+                        #variant_name #fields
+                    });
+                }
+                quote! {
+                    #derives
+                    pub enum #name {
+                        #(#variants),*
+                    }
+                }
+            }
+            _ => return Err(format!("type {id} is neither a composite nor a variant")),
+        };
+        self.items.push(item);
+
+        Ok(name)
+    }
+
+    /// Generates either named or unnamed fields, depending on the metadata.
+    fn fields(&mut self, fields: &[Field], public: bool) -> Result<TokenStream> {
+        if fields.is_empty() {
+            return Ok(quote!());
+        }
+
+        let visibility = public.then(|| quote!(pub));
+        let named = fields.iter().all(|field| field.name.is_some());
+        if !named && fields.iter().any(|field| field.name.is_some()) {
+            return Err("mixing named and unnamed fields is not supported".to_string());
+        }
+
+        let mut generated = Vec::new();
+        for field in fields {
+            let docs = docs(&field.docs);
+            let (attributes, ty) = match &self.ty(field.ty)?.def {
+                TypeDef::Compact(compact) => {
+                    (quote!(#[codec(compact)]), self.type_ref(compact.ty)?)
+                }
+                _ => (quote!(), self.type_ref(field.ty)?),
+            };
+            let name = field.name.as_deref().map(|name| {
+                let name = ident(name);
+                quote!(#name:)
+            });
+            generated.push(quote!(#docs #attributes #visibility #name #ty));
+        }
+
+        Ok(match named {
+            true => quote!({ #(#generated),* }),
+            false => quote!(( #(#generated),* )),
+        })
+    }
+
+    fn message(
+        &mut self,
+        message: &MessageSpec,
+        method_names: &mut HashSet<String>,
+    ) -> Result<TokenStream> {
+        let label = &message.label;
+        let mut method = label
+            .split("::")
+            .map(ToSnakeCase::to_snake_case)
+            .collect::<Vec<_>>()
+            .join("_");
+        if method_names.contains(&method) {
+            method = format!("{method}_message");
+        }
+        method_names.insert(method.clone());
+
+        let selector = selector(&message.selector)
+            .ok_or_else(|| format!("invalid selector of message {label}"))?
+            .map(Literal::u8_unsuffixed);
+        let (inner, lang_error) = self.message_result(message)?;
+
+        let (args_param, data) = if message.args.is_empty() {
+            (quote!(), quote!(let data = ::std::vec![#(#selector),*];))
+        } else {
+            let args_name =
+                self.allocate_name(format!("{}Args", method.to_upper_camel_case()), "Message");
+            let mut fields = Vec::new();
+            for arg in &message.args {
+                let name = ident(&arg.label);
+                let ty = self.type_ref(arg.ty.ty)?;
+                fields.push(quote!(pub #name: #ty));
+            }
+            let args_docs = format!(" Arguments of the `{label}` message.");
+            let derives = derives();
+            self.items.push(quote! {
+                #[doc = #args_docs]
+                #derives
+                pub struct #args_name {
+                    #(#fields),*
+                }
+            });
+
+            (
+                quote!(args: #args_name,),
+                quote! {
+                    let mut data = ::std::vec![#(#selector),*];
+                    ::contracts_azero_client::codec::Encode::encode_to(&args, &mut data);
+                },
+            )
+        };
+
+        let docs = docs(&message.docs);
+        let method = ident(&method);
+
+        Ok(match message.mutates {
+            true => {
+                let mut prepare = format!("prepare_{method}");
+                if method_names.contains(&prepare) {
+                    prepare = format!("{prepare}_message");
+                }
+                method_names.insert(prepare.clone());
+                let prepare_docs = format!(
+                    " Dry-runs the `{label}` message, so that it can be submitted with `exec_batch`."
+                );
+                let prepare = ident(&prepare);
+
+                quote! {
+                    #docs
+                    pub async fn #method<S: ::contracts_azero_client::Signer>(
+                        &self,
+                        conn: &::contracts_azero_client::ClientWithSigner<S>,
+                        #args_param
+                        params: ::contracts_azero_client::ExecCallParams,
+                    ) -> ::contracts_azero_client::anyhow::Result<
+                        ::contracts_azero_client::ExecOutcome<#inner>,
+                    > {
+                        #data
+                        self.contract
+                            .exec_encoded::<S, #inner, #lang_error>(conn, #label, data, params)
+                            .await
+                    }
+
+                    #[doc = #prepare_docs]
+                    pub async fn #prepare<S: ::contracts_azero_client::Signer>(
+                        &self,
+                        conn: &::contracts_azero_client::ClientWithSigner<S>,
+                        #args_param
+                        params: ::contracts_azero_client::ExecCallParams,
+                    ) -> ::contracts_azero_client::anyhow::Result<
+                        ::contracts_azero_client::PreparedCall<#inner>,
+                    > {
+                        #data
+                        self.contract
+                            .prepare_encoded::<S, #inner, #lang_error>(conn, #label, data, params)
+                            .await
+                    }
+                }
+            }
+            false => quote! {
+                #docs
+                pub async fn #method(
+                    &self,
+                    conn: &::contracts_azero_client::Client,
+                    #args_param
+                    params: ::contracts_azero_client::ReadonlyCallParams,
+                ) -> ::contracts_azero_client::anyhow::Result<#inner> {
+                    #data
+                    self.contract
+                        .read_encoded::<#inner, #lang_error>(conn, #label, data, params)
+                        .await
+                }
+            },
+        })
+    }
+
+    /// Splits the `MessageResult<T>` returned by every ink! 4 message into `T` and `LangError`.
+    fn message_result(&mut self, message: &MessageSpec) -> Result<(TokenStream, TokenStream)> {
+        let label = &message.label;
+        let return_type = message
+            .return_type
+            .as_ref()
+            .ok_or_else(|| format!("message {label} has no return type"))?;
+        let ty = self.ty(return_type.ty)?;
+
+        let [ok, err] = match (ty.path.as_slice(), ty.params.as_slice()) {
+            ([result], [ok, err]) if result == "Result" => [ok.ty, err.ty],
+            _ => return Err(format!("message {label} doesn't return a MessageResult")),
+        };
+        let (Some(ok), Some(err)) = (ok, err) else {
+            return Err(format!("message {label} has an unresolved return type"));
+        };
+        if self.ty(err)?.path.last().map(String::as_str) != Some("LangError") {
+            return Err(format!("message {label} doesn't return a MessageResult"));
+        }
+
+        Ok((self.type_ref(ok)?, self.type_ref(err)?))
+    }
+
+    /// Generates a struct per event and an enum of all of them. The enum decodes the raw data of
+    /// events, which starts with the index of the event in the metadata.
+    fn events(&mut self, event_enum: &Ident, events: &[EventSpec]) -> Result<TokenStream> {
+        if events.is_empty() {
+            return Ok(quote!());
+        }
+        if events.len() > usize::from(u8::MAX) + 1 {
+            return Err("contracts with more than 256 events are not supported".to_string());
+        }
+
+        let derives = derives();
+        let mut variants = Vec::new();
+        for (index, event) in events.iter().enumerate() {
+            let name = self.allocate_name(event.label.clone(), "Event");
+            let mut fields = Vec::new();
+            for arg in &event.args {
+                let docs = docs(&arg.docs);
+                let arg_name = ident(&arg.label);
+                let ty = self.type_ref(arg.ty.ty)?;
+                fields.push(quote!(#docs pub #arg_name: #ty));
+            }
+            let docs = docs(&event.docs);
+            self.items.push(quote! {
+                #docs
+                #derives
+                pub struct #name {
+                    #(#fields),*
+                }
+            });
+
+            let variant = ident(&event.label);
+            let index = Literal::usize_unsuffixed(index);
+            variants.push(quote! {
+                #[codec(index = #index)]
+                #variant(#name)
+            });
+        }
+
+        Ok(quote! {
+            #derives
+            pub enum #event_enum {
+                #(#variants),*
+            }
+
+            impl ::core::convert::TryFrom<&::contracts_azero_client::ContractEvent> for #event_enum {
+                type Error = ::contracts_azero_client::codec::Error;
+
+                fn try_from(
+                    event: &::contracts_azero_client::ContractEvent,
+                ) -> ::core::result::Result<Self, Self::Error> {
+                    <Self as ::contracts_azero_client::codec::DecodeAll>::decode_all(
+                        &mut event.encoded.as_slice(),
+                    )
+                }
+            }
+        })
+    }
+}
+
+/// Derives shared by all generated types.
+fn derives() -> TokenStream {
+    quote! {
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            ::contracts_azero_client::codec::Encode,
+            ::contracts_azero_client::codec::Decode,
+        )]
+        #[codec(crate = ::contracts_azero_client::codec)]
+    }
+}
+
+fn primitive_ref(primitive: Primitive) -> Result<TokenStream> {
+    Ok(match primitive {
+        Primitive::Bool => quote!(bool),
+        Primitive::Str => quote!(::std::string::String),
+        Primitive::U8 => quote!(u8),
+        Primitive::U16 => quote!(u16),
+        Primitive::U32 => quote!(u32),
+        Primitive::U64 => quote!(u64),
+        Primitive::U128 => quote!(u128),
+        Primitive::I8 => quote!(i8),
+        Primitive::I16 => quote!(i16),
+        Primitive::I32 => quote!(i32),
+        Primitive::I64 => quote!(i64),
+        Primitive::I128 => quote!(i128),
+        Primitive::Char | Primitive::U256 | Primitive::I256 => {
+            return Err(format!("primitive type {primitive:?} is not supported"))
+        }
+    })
+}
+
+fn selector(selector: &str) -> Option<[u8; 4]> {
+    let hex = selector.strip_prefix("0x")?;
+    if hex.len() != 8 {
+        return None;
+    }
+
+    u32::from_str_radix(hex, 16).ok().map(u32::to_be_bytes)
+}
+
+fn docs(lines: &[String]) -> TokenStream {
+    quote!(#(#[doc = #lines])*)
+}
+
+/// Falls back to a raw identifier for names that are Rust keywords.
+fn ident(name: &str) -> Ident {
+    syn::parse_str::<Ident>(name).unwrap_or_else(|_| Ident::new_raw(name, Span::call_site()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+        "version": "4",
+        "types": [
+            { "id": 0, "type": { "def": { "primitive": "u128" } } },
+            { "id": 1, "type": { "def": { "array": { "len": 32, "type": 2 } } } },
+            { "id": 2, "type": { "def": { "primitive": "u8" } } },
+            { "id": 3, "type": { "def": { "primitive": "bool" } } },
+            { "id": 4, "type": { "def": { "tuple": [] } } },
+            {
+                "id": 5,
+                "type": {
+                    "path": ["ink_primitives", "LangError"],
+                    "def": { "variant": { "variants": [{ "name": "CouldNotReadInput", "index": 1 }] } }
+                }
+            },
+            {
+                "id": 6,
+                "type": {
+                    "path": ["Result"],
+                    "params": [{ "name": "T", "type": 4 }, { "name": "E", "type": 5 }],
+                    "def": { "variant": { "variants": [
+                        { "name": "Ok", "fields": [{ "type": 4 }], "index": 0 },
+                        { "name": "Err", "fields": [{ "type": 5 }], "index": 1 }
+                    ] } }
+                }
+            },
+            {
+                "id": 7,
+                "type": {
+                    "path": ["most", "MostError"],
+                    "def": { "variant": { "variants": [
+                        { "name": "NotOwner", "fields": [{ "type": 11, "typeName": "AccountId" }], "index": 0 },
+                        { "name": "Custom", "fields": [{ "type": 8, "typeName": "String" }], "index": 1 }
+                    ] } }
+                }
+            },
+            { "id": 8, "type": { "def": { "primitive": "str" } } },
+            {
+                "id": 9,
+                "type": {
+                    "path": ["Result"],
+                    "params": [{ "name": "T", "type": 3 }, { "name": "E", "type": 7 }],
+                    "def": { "variant": { "variants": [
+                        { "name": "Ok", "fields": [{ "type": 3 }], "index": 0 },
+                        { "name": "Err", "fields": [{ "type": 7 }], "index": 1 }
+                    ] } }
+                }
+            },
+            {
+                "id": 10,
+                "type": {
+                    "path": ["Result"],
+                    "params": [{ "name": "T", "type": 9 }, { "name": "E", "type": 5 }],
+                    "def": { "variant": { "variants": [
+                        { "name": "Ok", "fields": [{ "type": 9 }], "index": 0 },
+                        { "name": "Err", "fields": [{ "type": 5 }], "index": 1 }
+                    ] } }
+                }
+            },
+            {
+                "id": 11,
+                "type": {
+                    "path": ["ink_primitives", "types", "AccountId"],
+                    "def": { "composite": { "fields": [{ "type": 1 }] } }
+                }
+            }
+        ],
+        "storage": {},
+        "spec": {
+            "constructors": [],
+            "messages": [
+                {
+                    "label": "needs_signature",
+                    "selector": "0x0a0b0c0d",
+                    "mutates": false,
+                    "payable": false,
+                    "args": [
+                        { "label": "request_hash", "type": { "type": 1, "displayName": ["HashedRequest"] } },
+                        { "label": "account", "type": { "type": 11, "displayName": ["AccountId"] } }
+                    ],
+                    "returnType": { "type": 10, "displayName": ["ink", "MessageResult"] },
+                    "docs": [" Checks whether the account still needs to sign the request"],
+                    "default": false
+                },
+                {
+                    "label": "Ownable2Step::accept_ownership",
+                    "selector": "0xdeadbeef",
+                    "mutates": true,
+                    "payable": false,
+                    "args": [],
+                    "returnType": { "type": 6, "displayName": ["ink", "MessageResult"] },
+                    "docs": [],
+                    "default": false
+                }
+            ],
+            "events": [
+                {
+                    "label": "CrosschainTransferRequest",
+                    "args": [
+                        { "label": "committee_id", "indexed": true, "type": { "type": 0, "displayName": ["u128"] }, "docs": [] },
+                        { "label": "dest_receiver_address", "indexed": true, "type": { "type": 1, "displayName": [] }, "docs": [] }
+                    ],
+                    "docs": []
+                }
+            ]
+        }
+    }"#;
+
+    fn generate_test_bindings(metadata: &str) -> Result<String> {
+        let metadata: InkProject = serde_json::from_str(metadata).expect("valid metadata");
+        let name = Ident::new("TestContract", Span::call_site());
+
+        generate(&name, &metadata, None).map(|tokens| tokens.to_string())
+    }
+
+    #[test]
+    fn generates_valid_bindings() {
+        let generated = generate_test_bindings(METADATA).expect("bindings");
+        syn::parse_file(&generated).expect("generated code should parse");
+
+        for expected in [
+            "pub mod test_contract",
+            "pub async fn needs_signature",
+            "pub struct NeedsSignatureArgs",
+            "pub async fn ownable2_step_accept_ownership < S",
+            "pub enum MostError",
+            "pub enum TestContractEvent",
+            "pub struct CrosschainTransferRequest",
+            "request_hash : [u8 ; 32]",
+            "account : :: contracts_azero_client :: AccountId",
+            "222 , 173 , 190 , 239",
+        ] {
+            assert!(
+                generated.contains(expected),
+                "{expected:?} not found in {generated}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_messages_without_message_result() {
+        let metadata = METADATA.replace(
+            r#""returnType": { "type": 6"#,
+            r#""returnType": { "type": 4"#,
+        );
+
+        assert!(generate_test_bindings(&metadata).is_err());
+    }
+}
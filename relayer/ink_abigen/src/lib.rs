@@ -0,0 +1,87 @@
+//! Generates typed bindings for ink! contracts from their metadata, in the spirit of `ethers`'
+//! `abigen!`.
+//!
+//! ```ignore
+//! ink_abigen!(AzeroMost, "../azero/artifacts/most.json");
+//! ```
+//!
+//! expands to a module `azero_most` with:
+//! - the `AzeroMost` contract struct (also re-exported next to the module), with a method per
+//!   message taking the message arguments as a struct and returning the decoded result (along
+//!   with the transaction details and emitted events for messages that mutate the state),
+//! - every type used by the messages and events, e.g. `MostError`,
+//! - a struct per event and an `AzeroMostEvent` enum decoding them from a `ContractEvent`.
+//!
+//! The generated code refers to `contracts_azero_client`, which re-exports this macro.
+
+mod generator;
+mod metadata;
+
+use std::{env, fs, path::PathBuf};
+
+use proc_macro::TokenStream;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Ident, LitStr, Token,
+};
+
+use crate::metadata::InkProject;
+
+struct Input {
+    name: Ident,
+    path: LitStr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        Ok(Self { name, path })
+    }
+}
+
+#[proc_macro]
+pub fn ink_abigen(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Input);
+
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &Input) -> syn::Result<proc_macro2::TokenStream> {
+    let error = |message: String| syn::Error::new(input.path.span(), message);
+
+    let path = resolve(&input.path.value()).ok_or_else(|| {
+        error(format!(
+            "Metadata file {:?} not found, are the contracts compiled?",
+            input.path.value()
+        ))
+    })?;
+    let contents = fs::read_to_string(&path)
+        .map_err(|why| error(format!("Failed to read {path:?}: {why}")))?;
+    let metadata: InkProject = serde_json::from_str(&contents)
+        .map_err(|why| error(format!("Failed to parse ink! metadata in {path:?}: {why}")))?;
+
+    generator::generate(&input.name, &metadata, Some(&path.to_string_lossy()))
+        .map_err(|why| error(format!("Failed to generate bindings: {why}")))
+}
+
+/// Relative paths are resolved against the directory of the crate being compiled and then its
+/// ancestors, so that artifacts outside of the workspace can be referred to the same way from
+/// every crate in it.
+fn resolve(path: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        return path.exists().then_some(path);
+    }
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").ok()?);
+    manifest_dir
+        .ancestors()
+        .map(|dir| dir.join(&path))
+        .find(|candidate| candidate.exists())
+}
@@ -0,0 +1,168 @@
+//! The subset of the ink! 4 metadata format needed to generate bindings.
+
+use serde::{de::IgnoredAny, Deserialize};
+
+pub type TypeId = u32;
+
+#[derive(Deserialize, Debug)]
+pub struct InkProject {
+    pub types: Vec<PortableType>,
+    pub spec: ContractSpec,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PortableType {
+    pub id: TypeId,
+    #[serde(rename = "type")]
+    pub ty: Type,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Type {
+    #[serde(default)]
+    pub path: Vec<String>,
+    #[serde(default)]
+    pub params: Vec<TypeParam>,
+    pub def: TypeDef,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TypeParam {
+    #[serde(rename = "type")]
+    pub ty: Option<TypeId>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeDef {
+    Composite(Composite),
+    Variant(VariantDef),
+    Sequence(Sequence),
+    Array(Array),
+    Tuple(Vec<TypeId>),
+    Primitive(Primitive),
+    Compact(Compact),
+    BitSequence(IgnoredAny),
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Composite {
+    #[serde(default)]
+    pub fields: Vec<Field>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct VariantDef {
+    #[serde(default)]
+    pub variants: Vec<Variant>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Variant {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<Field>,
+    pub index: u8,
+    #[serde(default)]
+    pub docs: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Field {
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub ty: TypeId,
+    #[serde(default)]
+    pub docs: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Sequence {
+    #[serde(rename = "type")]
+    pub ty: TypeId,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Array {
+    pub len: u32,
+    #[serde(rename = "type")]
+    pub ty: TypeId,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Compact {
+    #[serde(rename = "type")]
+    pub ty: TypeId,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Primitive {
+    Bool,
+    Char,
+    Str,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    I256,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ContractSpec {
+    #[serde(default)]
+    pub messages: Vec<MessageSpec>,
+    #[serde(default)]
+    pub events: Vec<EventSpec>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageSpec {
+    pub label: String,
+    pub selector: String,
+    pub mutates: bool,
+    #[serde(default)]
+    pub args: Vec<MessageParamSpec>,
+    pub return_type: Option<TypeSpec>,
+    #[serde(default)]
+    pub docs: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MessageParamSpec {
+    pub label: String,
+    #[serde(rename = "type")]
+    pub ty: TypeSpec,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EventSpec {
+    pub label: String,
+    #[serde(default)]
+    pub args: Vec<EventParamSpec>,
+    #[serde(default)]
+    pub docs: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EventParamSpec {
+    pub label: String,
+    #[serde(rename = "type")]
+    pub ty: TypeSpec,
+    #[serde(default)]
+    pub docs: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TypeSpec {
+    #[serde(rename = "type")]
+    pub ty: TypeId,
+}
@@ -22,6 +22,17 @@ impl Deref for SyncFromBlock {
     }
 }
 
+/// Parses a multiplier of estimated gas. A multiplier below 1.0 would make calls run out of gas.
+fn parse_gas_multiplier(s: &str) -> Result<f64, String> {
+    let multiplier: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    if !multiplier.is_finite() || multiplier < 1.0 {
+        return Err(format!(
+            "expected a finite number of at least 1.0, got {multiplier}"
+        ));
+    }
+    Ok(multiplier)
+}
+
 #[derive(Debug, clap::Parser)]
 pub struct Config {
     #[arg(long)]
@@ -55,6 +66,14 @@ pub struct Config {
     #[arg(long, default_value = "1234")]
     pub signer_port: u32,
 
+    /// How long to wait for a response from the signer, including time spent reconnecting
+    #[arg(long, default_value = "30")]
+    pub signer_request_timeout_sec: u64,
+
+    /// Id of the signer key to use, the signer's default key if not set
+    #[arg(long)]
+    pub signer_key_id: Option<String>,
+
     #[arg(long)]
     pub azero_contract_address: String,
 
@@ -67,12 +86,22 @@ pub struct Config {
     #[arg(long, default_value = "1000")]
     pub azero_max_event_handler_tasks: usize,
 
+    /// Cap on the ref time of submitted Azero calls
     #[arg(long, default_value = "100000000000")]
     pub azero_ref_time_limit: u64,
 
+    /// Cap on the proof size of submitted Azero calls
     #[arg(long, default_value = "10000000")]
     pub azero_proof_size_limit: u64,
 
+    /// Safety margin applied to the gas estimated by dry-running Azero calls, at least 1.0
+    #[arg(long, default_value = "1.5", value_parser = parse_gas_multiplier)]
+    pub azero_gas_multiplier: f64,
+
+    /// Maximum number of guardian votes submitted together in a single Azero extrinsic
+    #[arg(long, default_value = "10")]
+    pub azero_max_batched_votes: usize,
+
     #[arg(long, default_value = "1")]
     pub default_sync_from_block_azero: SyncFromBlock,
 
@@ -112,3 +141,19 @@ pub struct Config {
     #[arg(long, default_value = "info")]
     pub rust_log: log::Level,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gas_multiplier() {
+        assert_eq!(parse_gas_multiplier("1.5"), Ok(1.5));
+        assert_eq!(parse_gas_multiplier("1"), Ok(1.0));
+        assert!(parse_gas_multiplier("0.99").is_err());
+        assert!(parse_gas_multiplier("-2").is_err());
+        assert!(parse_gas_multiplier("NaN").is_err());
+        assert!(parse_gas_multiplier("inf").is_err());
+        assert!(parse_gas_multiplier("x").is_err());
+    }
+}
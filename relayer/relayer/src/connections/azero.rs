@@ -3,7 +3,6 @@ use std::time::Duration;
 use contracts_azero_client::{AccountId, ClientConfig, KeyPair, MultiSignature, Signer};
 use signer_client::Client;
 use subxt::ext::sp_core::Pair;
-use tokio::sync::Mutex;
 
 pub type AzeroWsConnection = contracts_azero_client::Client;
 
@@ -19,7 +18,7 @@ pub async fn init(url: &str) -> AzeroWsConnection {
 }
 
 pub struct AzeroSignerClient {
-    client: Mutex<Client>,
+    client: Client,
     account_id: AccountId,
 }
 
@@ -36,10 +35,13 @@ pub enum Error {
 }
 
 impl AzeroSignerClient {
-    pub async fn new(cid: u32, port: u32) -> Result<Self, Error> {
-        let mut client = Client::new(cid, port).await?;
+    pub async fn new(
+        cid: u32,
+        port: u32,
+        config: signer_client::ClientConfig,
+    ) -> Result<Self, Error> {
+        let client = Client::with_config(cid, port, config).await?;
         let account_id = client.azero_account_id().await?;
-        let client = Mutex::new(client);
 
         Ok(Self {
             client,
@@ -65,9 +67,7 @@ impl AzeroSigner {
         match self {
             AzeroSigner::Dev(keypair) => Ok(keypair.signer().sign(payload).into()),
             AzeroSigner::Signer(signer) => {
-                let mut client = signer.client.lock().await;
-                let payload = payload.to_vec();
-                let signature = client.sign_azero(&payload).await?;
+                let signature = signer.client.sign_azero(payload).await?;
 
                 Ok(signature.into())
             }
@@ -20,7 +20,7 @@ use ethers::{
 };
 use log::{debug, warn};
 use thiserror::Error;
-use tokio::{sync::Mutex, time::sleep};
+use tokio::time::sleep;
 
 use crate::{config::Config, listeners::ETH_BLOCK_PROD_TIME_SEC};
 
@@ -137,7 +137,7 @@ impl Signer for EthereumSigner {
 }
 
 pub struct EthVsockSigner {
-    client: Mutex<signer_client::Client>,
+    client: signer_client::Client,
     chain_id: u64,
     address: Address,
 }
@@ -153,8 +153,7 @@ impl Debug for EthVsockSigner {
 
 impl EthVsockSigner {
     async fn sign_hash(&self, hash: H256) -> Result<Signature, EthVsockSignerError> {
-        let mut client = self.client.lock().await;
-        let signature = client.sign_eth_hash(hash).await?;
+        let signature = self.client.sign_eth_hash(hash).await?;
 
         Ok(signature)
     }
@@ -175,9 +174,7 @@ impl Signer for EthVsockSigner {
     }
 
     async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
-        let mut client = self.client.lock().await;
-        let tx = tx.clone();
-        let signature = client.sign_eth_tx(&tx).await?;
+        let signature = self.client.sign_eth_tx(tx).await?;
 
         Ok(signature)
     }
@@ -225,10 +222,10 @@ pub async fn with_signer(
     connection: GasEscalatingEthConnection,
     cid: u32,
     port: u32,
+    config: signer_client::ClientConfig,
 ) -> Result<SignedEthConnection, EthConnectionError> {
-    let mut client = signer_client::Client::new(cid, port).await?;
+    let client = signer_client::Client::with_config(cid, port, config).await?;
     let address = client.eth_address().await?;
-    let client = Mutex::new(client);
     let nonce_manager = with_nonce_manager(connection, address).await?;
 
     let signer = EthVsockSigner {
@@ -1,18 +1,19 @@
-use std::{
-    collections::HashMap,
-    str,
-    str::{FromStr, Utf8Error},
-};
+use std::str::FromStr;
 
 use contracts_azero_client::{
-    contract_transcode::{Value, Value::Seq},
-    AccountId, Client, ClientWithSigner, ContractInstance, ExecCallParams, ReadonlyCallParams,
-    Weight,
+    codec, ink_abigen, AccountId, BatchMode, Client, ClientWithSigner, ContractEvent,
+    ExecCallParams, ReadonlyCallParams, Reverted, StorageKey, TxInfo, Weight,
 };
-use log::{debug, error};
+use log::debug;
 use thiserror::Error;
 
-use crate::connections::azero::AzeroSigner;
+use crate::{connections::azero::AzeroSigner, helpers::RequestHashDomain};
+
+ink_abigen!(AzeroMost, "../azero/artifacts/most.json");
+ink_abigen!(Advisory, "../azero/artifacts/advisory.json");
+
+/// Root key of the `refunded_requests` mapping of `Most`.
+const REFUNDED_REQUESTS_KEY: u32 = 0x52454655;
 
 #[derive(Debug, Error)]
 #[error(transparent)]
@@ -27,15 +28,51 @@ pub enum AzeroContractError {
     #[error("not account id")]
     NotAccountId(String),
 
-    #[error("Invalid UTF-8 sequence")]
-    InvalidUTF8(#[from] Utf8Error),
+    #[error("Most contract returned an error: {0:?}")]
+    Most(azero_most::MostError),
+
+    #[error("Failed to decode contract event")]
+    EventDecode(#[from] codec::Error),
+}
+
+impl azero_most::AzeroMostEvent {
+    /// Whether the event records a change of the bridge configuration, made by the owner of `Most`.
+    pub fn is_config_change(&self) -> bool {
+        use azero_most::AzeroMostEvent::*;
 
-    #[error("Missing or invalid field")]
-    MissingOrInvalidField(String),
+        matches!(
+            self,
+            HaltedStateChanged(_)
+                | TransferOwnershipInitiated(_)
+                | TransferOwnershipAccepted(_)
+                | CommitteeUpdated(_)
+                | MaxPocketMoneyChanged(_)
+                | CodeHashChanged(_)
+                | BaseFeeConstraintsChanged(_)
+                | SupportedPairChanged(_)
+                | TransferLimitsChanged(_)
+                | LargeTransferThresholdChanged(_)
+                | LargeTransferDelayChanged(_)
+                | AdvisoryChanged(_)
+                | WethChanged(_)
+                | WazeroChanged(_)
+                | GasPriceOracleChanged(_)
+                | EthTransferGasUsageChanged(_)
+                | FundsRecovered(_)
+                | OperationScheduled(_)
+                | OperationCancelled(_)
+                | OperationExecuted(_)
+                | OperationDelayChanged(_)
+                | RoleGranted(_)
+                | RoleRevoked(_)
+                | PairPauseChanged(_)
+                | RequestDomainSet(_)
+        )
+    }
 }
 
 pub struct AdvisoryInstance {
-    pub contract: ContractInstance,
+    pub contract: Advisory,
     pub address: AccountId,
 }
 
@@ -45,7 +82,7 @@ impl AdvisoryInstance {
             .map_err(|why| AzeroContractError::NotAccountId(why.to_string()))?;
         Ok(Self {
             address: address.clone(),
-            contract: ContractInstance::new(address, metadata_path)?,
+            contract: Advisory::new(address, metadata_path)?,
         })
     }
 
@@ -55,7 +92,7 @@ impl AdvisoryInstance {
     ) -> Result<(bool, AccountId), AzeroContractError> {
         match self
             .contract
-            .read0::<bool>(connection, "is_emergency", Default::default())
+            .is_emergency(connection, Default::default())
             .await
         {
             Ok(is_emergency) => Ok((is_emergency, self.address.clone())),
@@ -64,10 +101,46 @@ impl AdvisoryInstance {
     }
 }
 
+/// What a `receive_request` vote amounted to, according to the events it emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteStatus {
+    /// The vote was counted.
+    Signed,
+    /// The guardian had already voted for the request.
+    AlreadySigned,
+    /// The request had already been processed before the vote.
+    AlreadyProcessed,
+}
+
+impl VoteStatus {
+    fn from_events(events: &[ContractEvent]) -> Self {
+        for event in events {
+            match azero_most::AzeroMostEvent::try_from(event) {
+                Ok(azero_most::AzeroMostEvent::RequestAlreadySigned(_)) => {
+                    return VoteStatus::AlreadySigned
+                }
+                Ok(azero_most::AzeroMostEvent::SignedProcessedRequest(_)) => {
+                    return VoteStatus::AlreadyProcessed
+                }
+                _ => (),
+            }
+        }
+
+        VoteStatus::Signed
+    }
+}
+
+#[derive(Debug)]
+pub struct Vote {
+    pub tx_info: TxInfo,
+    pub status: VoteStatus,
+}
+
 pub struct MostInstance {
-    pub contract: ContractInstance,
+    pub contract: AzeroMost,
     pub ref_time_limit: u64,
     pub proof_size_limit: u64,
+    pub gas_multiplier: f64,
 }
 
 impl MostInstance {
@@ -76,49 +149,89 @@ impl MostInstance {
         metadata_path: &str,
         ref_time_limit: u64,
         proof_size_limit: u64,
+        gas_multiplier: f64,
     ) -> Result<Self, AzeroContractError> {
         let address = AccountId::from_str(address)
             .map_err(|why| AzeroContractError::NotAccountId(why.to_string()))?;
         Ok(Self {
-            contract: ContractInstance::new(address, metadata_path)?,
+            contract: AzeroMost::new(address, metadata_path)?,
             ref_time_limit,
             proof_size_limit,
+            gas_multiplier,
         })
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub async fn receive_request(
         &self,
         signed_connection: &ClientWithSigner<AzeroSigner>,
-        request_hash: [u8; 32],
-        committee_id: u128,
-        dest_token_address: [u8; 32],
-        amount: u128,
-        dest_receiver_address: [u8; 32],
-        request_nonce: u128,
-    ) -> Result<(), AzeroContractError> {
-        let gas_limit = Weight {
-            ref_time: self.ref_time_limit,
-            proof_size: self.proof_size_limit,
-        };
-        let args = [
-            bytes32_to_str(&request_hash),
-            committee_id.to_string(),
-            bytes32_to_str(&dest_token_address),
-            amount.to_string(),
-            bytes32_to_str(&dest_receiver_address),
-            request_nonce.to_string(),
-        ];
-        let params = ExecCallParams::new().gas_limit(gas_limit);
-
+        args: azero_most::ReceiveRequestArgs,
+    ) -> Result<Vote, AzeroContractError> {
         // Exec does dry run first, so there's no need to repeat it here
         let call_result = self
             .contract
-            .exec(signed_connection, "receive_request", &args, params)
-            .await
-            .map_err(AzeroContractError::ContractCall);
+            .receive_request(signed_connection, args, self.exec_params())
+            .await;
         debug!("receive_request: {:?}", call_result);
-        call_result
+        let outcome = call_result.map_err(exec_error)?;
+
+        Ok(Vote {
+            tx_info: outcome.tx_info,
+            status: VoteStatus::from_events(&outcome.events),
+        })
+    }
+
+    /// Votes for the requests in a single `Utility::force_batch` extrinsic, so a vote that fails
+    /// doesn't affect the others. Returns the outcome of each vote along with its request hash.
+    pub async fn receive_requests(
+        &self,
+        signed_connection: &ClientWithSigner<AzeroSigner>,
+        requests: Vec<azero_most::ReceiveRequestArgs>,
+    ) -> Result<Vec<([u8; 32], Result<Vote, AzeroContractError>)>, AzeroContractError> {
+        let mut votes = Vec::new();
+        let mut calls = Vec::new();
+        let mut hashes = Vec::new();
+        for args in requests {
+            let request_hash = args.request_hash;
+            // Requests whose dry-run reverts are left out of the batch
+            match self
+                .contract
+                .prepare_receive_request(signed_connection, args, self.exec_params())
+                .await
+            {
+                Ok(call) => {
+                    calls.push(call);
+                    hashes.push(request_hash);
+                }
+                Err(why) => votes.push((request_hash, Err(exec_error(why)))),
+            }
+        }
+        if calls.is_empty() {
+            return Ok(votes);
+        }
+
+        let outcomes = self
+            .contract
+            .instance()
+            .exec_batch(signed_connection, calls, BatchMode::Force)
+            .await?;
+        debug!("receive_requests: {:?}", outcomes);
+
+        votes.extend(
+            hashes
+                .into_iter()
+                .zip(outcomes)
+                .map(|(request_hash, outcome)| {
+                    let vote = outcome
+                        .map(|outcome| Vote {
+                            tx_info: outcome.tx_info,
+                            status: VoteStatus::from_events(&outcome.events),
+                        })
+                        .map_err(AzeroContractError::ContractCall);
+                    (request_hash, vote)
+                }),
+        );
+
+        Ok(votes)
     }
 
     pub async fn set_payout_account(
@@ -127,28 +240,27 @@ impl MostInstance {
         committee_id: u128,
         payout_account: AccountId,
     ) -> Result<(), AzeroContractError> {
-        let gas_limit = Weight {
-            ref_time: self.ref_time_limit,
-            proof_size: self.proof_size_limit,
+        let args = azero_most::SetPayoutAccountArgs {
+            committee_id,
+            account: payout_account,
         };
-        let args = [committee_id.to_string(), payout_account.to_string()];
-        let params = ExecCallParams::new().gas_limit(gas_limit);
 
         // Exec does dry run first, so there's no need to repeat it here
         let call_result = self
             .contract
-            .exec(signed_connection, "set_payout_account", &args, params)
-            .await
-            .map_err(AzeroContractError::ContractCall);
+            .set_payout_account(signed_connection, args, self.exec_params())
+            .await;
         debug!("set_payout_account: {:?}", call_result);
-        call_result
+        call_result.map_err(exec_error)?;
+
+        Ok(())
     }
 
     pub async fn is_halted(&self, connection: &Client) -> Result<bool, AzeroContractError> {
-        Ok(self
-            .contract
-            .read0::<Result<bool, _>>(connection, "is_halted", Default::default())
-            .await??)
+        self.contract
+            .is_halted(connection, Default::default())
+            .await?
+            .map_err(AzeroContractError::Most)
     }
 
     pub async fn needs_signature(
@@ -166,18 +278,15 @@ impl MostInstance {
                 ReadonlyCallParams::new().at(finalized_hash)
             }
         };
+        let args = azero_most::NeedsSignatureArgs {
+            request_hash,
+            account,
+            committee_id,
+        };
+
         Ok(self
             .contract
-            .read(
-                connection,
-                "needs_signature",
-                &[
-                    bytes32_to_str(&request_hash),
-                    account.to_string(),
-                    committee_id.to_string(),
-                ],
-                params,
-            )
+            .needs_signature(connection, args, params)
             .await?)
     }
 
@@ -185,10 +294,46 @@ impl MostInstance {
         &self,
         connection: &Client,
     ) -> Result<u128, AzeroContractError> {
+        self.contract
+            .get_current_committee_id(connection, Default::default())
+            .await?
+            .map_err(AzeroContractError::Most)
+    }
+
+    /// The domain `Most` binds the hashes of the incoming requests to, `None` until the owner sets it.
+    pub async fn request_hash_domain(
+        &self,
+        connection: &Client,
+    ) -> Result<Option<RequestHashDomain>, AzeroContractError> {
+        let domain = self
+            .contract
+            .get_request_domain(connection, Default::default())
+            .await?;
+
+        Ok(domain.map(|domain| {
+            RequestHashDomain::new(
+                domain.src_chain_id,
+                domain.src_contract,
+                self.contract.address().0,
+                domain.from_nonce,
+            )
+        }))
+    }
+
+    /// Whether the outgoing request with `request_nonce` was refunded, in which case it must never
+    /// be processed on Ethereum. Read directly from the contract storage.
+    pub async fn is_refunded(
+        &self,
+        connection: &Client,
+        request_nonce: u128,
+    ) -> Result<bool, AzeroContractError> {
+        let key = StorageKey::mapping(REFUNDED_REQUESTS_KEY, &request_nonce);
         Ok(self
             .contract
-            .read0::<Result<u128, _>>(connection, "get_current_committee_id", Default::default())
-            .await??)
+            .instance()
+            .storage::<()>(connection, &key, None)
+            .await?
+            .is_some())
     }
 
     pub async fn is_in_committee(
@@ -197,93 +342,32 @@ impl MostInstance {
         committee_id: u128,
         account: AccountId,
     ) -> Result<bool, AzeroContractError> {
+        let args = azero_most::IsInCommitteeArgs {
+            committee_id,
+            account,
+        };
+
         Ok(self
             .contract
-            .read(
-                connection,
-                "is_in_committee",
-                &[committee_id.to_string(), account.to_string()],
-                Default::default(),
-            )
+            .is_in_committee(connection, args, Default::default())
             .await?)
     }
-}
-
-#[derive(Debug)]
-pub struct CrosschainTransferRequestData {
-    pub committee_id: u128,
-    pub dest_token_address: [u8; 32],
-    pub amount: u128,
-    pub dest_receiver_address: [u8; 32],
-    pub request_nonce: u128,
-}
 
-pub fn get_request_event_data(
-    data: &HashMap<String, Value>,
-) -> Result<CrosschainTransferRequestData, AzeroContractError> {
-    let committee_id: u128 = decode_uint_field(data, "committee_id")?;
-    let dest_token_address: [u8; 32] = decode_seq_field(data, "dest_token_address")?;
-    let amount: u128 = decode_uint_field(data, "amount")?;
-    let dest_receiver_address: [u8; 32] = decode_seq_field(data, "dest_receiver_address")?;
-    let request_nonce: u128 = decode_uint_field(data, "request_nonce")?;
-
-    Ok(CrosschainTransferRequestData {
-        committee_id,
-        dest_token_address,
-        amount,
-        dest_receiver_address,
-        request_nonce,
-    })
-}
-
-fn decode_seq_field(
-    data: &HashMap<String, Value>,
-    field: &str,
-) -> Result<[u8; 32], AzeroContractError> {
-    if let Some(Seq(seq_data)) = data.get(field) {
-        match seq_data
-            .elems()
-            .iter()
-            .try_fold(Vec::new(), |mut v, x| match x {
-                Value::UInt(x) => {
-                    v.push(*x as u8);
-                    Ok(v)
-                }
-                _ => Err(AzeroContractError::MissingOrInvalidField(format!(
-                    "Seq under data field {:?} contains elements of incorrect type",
-                    field
-                ))),
-            })?
-            .try_into()
-        {
-            Ok(x) => Ok(x),
-            Err(_) => Err(AzeroContractError::MissingOrInvalidField(format!(
-                "Seq under data field {:?} has incorrect length",
-                field
-            ))),
-        }
-    } else {
-        Err(AzeroContractError::MissingOrInvalidField(format!(
-            "Data field {:?} couldn't be found or has incorrect format",
-            field
-        )))
+    fn exec_params(&self) -> ExecCallParams {
+        ExecCallParams::new().estimated_gas_limit(
+            self.gas_multiplier,
+            Weight::new(self.ref_time_limit, self.proof_size_limit),
+        )
     }
 }
 
-fn decode_uint_field(
-    data: &HashMap<String, Value>,
-    field: &str,
-) -> Result<u128, AzeroContractError> {
-    if let Some(Value::UInt(x)) = data.get(field) {
-        Ok(*x)
-    } else {
-        Err(AzeroContractError::MissingOrInvalidField(format!(
-            "Data field {:?} couldn't be found or has incorrect format",
-            field
-        )))
+/// Surfaces the `MostError` of a call that reverted.
+fn exec_error(why: anyhow::Error) -> AzeroContractError {
+    match why.downcast::<Reverted<Result<(), azero_most::MostError>>>() {
+        Ok(Reverted {
+            result: Err(error), ..
+        }) => AzeroContractError::Most(error),
+        Ok(reverted) => AzeroContractError::ContractCall(reverted.into()),
+        Err(why) => AzeroContractError::ContractCall(why),
     }
 }
-
-fn bytes32_to_str(data: &[u8; 32]) -> String {
-    "0x".to_owned() + &hex::encode(data)
-}
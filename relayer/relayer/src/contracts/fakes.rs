@@ -0,0 +1,281 @@
+//! In-memory `Most` for testing the event handlers.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use contracts_azero_client::TxInfo;
+use ethers::types::{H256, U256};
+
+use crate::contracts::{
+    azero_most::MostError, AzeroContractError, AzeroMostApi, AzeroRefundsApi, EthContractError,
+    EthMostApi, SignatureState, TransferRequest, Vote, VoteStatus,
+};
+
+/// `Most` with a single guardian, whose votes only become finalized after the finalized state
+/// has been queried a number of times.
+#[derive(Default)]
+pub struct FakeMost {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    current_committee_id: u128,
+    committees: HashSet<u128>,
+    finality_delay: usize,
+    reverts: bool,
+    drops_votes: bool,
+    rejected: HashSet<[u8; 32]>,
+    signed: HashSet<[u8; 32]>,
+    finalized: HashSet<[u8; 32]>,
+    /// Signed requests waiting for finalization, with the number of queries left.
+    pending: HashMap<[u8; 32], usize>,
+    votes: Vec<TransferRequest>,
+    batches: Vec<Vec<[u8; 32]>>,
+    refunded: HashSet<u128>,
+    /// Nonces refunded once a vote for their request has been sent.
+    refunded_after_vote: HashSet<u128>,
+}
+
+impl State {
+    fn needs_signature(
+        &mut self,
+        request_hash: [u8; 32],
+        committee_id: u128,
+        finalized: bool,
+    ) -> bool {
+        if !self.committees.contains(&committee_id) {
+            return false;
+        }
+        if !finalized {
+            return !self.signed.contains(&request_hash);
+        }
+
+        if let Some(left) = self.pending.get_mut(&request_hash) {
+            *left = left.saturating_sub(1);
+            if *left == 0 {
+                self.pending.remove(&request_hash);
+                self.finalized.insert(request_hash);
+            }
+        }
+        !self.finalized.contains(&request_hash)
+    }
+
+    fn sign(&mut self, request_hash: [u8; 32]) {
+        self.signed.insert(request_hash);
+        if self.finality_delay == 0 {
+            self.finalized.insert(request_hash);
+        } else {
+            self.pending.insert(request_hash, self.finality_delay);
+        }
+    }
+}
+
+impl FakeMost {
+    pub fn new(current_committee_id: u128) -> Self {
+        let fake = Self::default();
+        fake.state.lock().unwrap().current_committee_id = current_committee_id;
+        fake
+    }
+
+    /// Makes the guardian a member of `committee_id`.
+    pub fn with_member_of(self, committee_id: u128) -> Self {
+        self.state.lock().unwrap().committees.insert(committee_id);
+        self
+    }
+
+    /// Votes are finalized after the finalized state is queried `queries` times.
+    pub fn finalizing_after(self, queries: usize) -> Self {
+        self.state.lock().unwrap().finality_delay = queries;
+        self
+    }
+
+    /// Every vote reverts.
+    pub fn reverting(self) -> Self {
+        self.state.lock().unwrap().reverts = true;
+        self
+    }
+
+    /// Votes succeed but are never recorded, as if they were dropped in a reorg.
+    pub fn dropping_votes(self) -> Self {
+        self.state.lock().unwrap().drops_votes = true;
+        self
+    }
+
+    /// Votes for `request_hash` revert.
+    pub fn rejecting(self, request_hash: [u8; 32]) -> Self {
+        self.state.lock().unwrap().rejected.insert(request_hash);
+        self
+    }
+
+    /// Records a vote of the guardian cast before the handler started.
+    pub fn with_signed(self, request_hash: [u8; 32]) -> Self {
+        self.state.lock().unwrap().sign(request_hash);
+        self
+    }
+
+    /// Marks the outgoing request with `request_nonce` as refunded.
+    pub fn with_refunded(self, request_nonce: u128) -> Self {
+        self.state.lock().unwrap().refunded.insert(request_nonce);
+        self
+    }
+
+    /// Refunds the outgoing request with `request_nonce` right after the first vote for it.
+    pub fn refunding_after_vote(self, request_nonce: u128) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .refunded_after_vote
+            .insert(request_nonce);
+        self
+    }
+
+    /// Votes the handler sent, including the reverted ones.
+    pub fn votes(&self) -> Vec<TransferRequest> {
+        self.state.lock().unwrap().votes.clone()
+    }
+
+    /// Request hashes of the votes sent together by each `receive_requests` call.
+    pub fn batches(&self) -> Vec<Vec<[u8; 32]>> {
+        self.state.lock().unwrap().batches.clone()
+    }
+
+    /// `None` if the vote reverted.
+    fn vote(&self, request: &TransferRequest) -> Option<VoteStatus> {
+        let mut state = self.state.lock().unwrap();
+        state.votes.push(*request);
+        if state.refunded_after_vote.remove(&request.request_nonce) {
+            state.refunded.insert(request.request_nonce);
+        }
+        if state.reverts || state.rejected.contains(&request.request_hash) {
+            return None;
+        }
+        if state.signed.contains(&request.request_hash) {
+            return Some(VoteStatus::AlreadySigned);
+        }
+        if state.drops_votes {
+            return Some(VoteStatus::Signed);
+        }
+
+        state.sign(request.request_hash);
+        Some(VoteStatus::Signed)
+    }
+}
+
+#[async_trait]
+impl AzeroMostApi for FakeMost {
+    async fn is_in_committee(&self, committee_id: u128) -> Result<bool, AzeroContractError> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .committees
+            .contains(&committee_id))
+    }
+
+    async fn current_committee_id(&self) -> Result<u128, AzeroContractError> {
+        Ok(self.state.lock().unwrap().current_committee_id)
+    }
+
+    async fn needs_signature(
+        &self,
+        request_hash: [u8; 32],
+        committee_id: u128,
+        block_finalized: bool,
+    ) -> Result<bool, AzeroContractError> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .needs_signature(request_hash, committee_id, block_finalized))
+    }
+
+    async fn receive_request(&self, request: &TransferRequest) -> Result<Vote, AzeroContractError> {
+        let status = self
+            .vote(request)
+            .ok_or(AzeroContractError::Most(MostError::IsHalted))?;
+
+        Ok(Vote {
+            tx_info: TxInfo {
+                block_hash: Default::default(),
+                tx_hash: Default::default(),
+                fee: None,
+            },
+            status,
+        })
+    }
+
+    async fn receive_requests(
+        &self,
+        requests: &[TransferRequest],
+    ) -> Result<Vec<([u8; 32], Result<Vote, AzeroContractError>)>, AzeroContractError> {
+        self.state.lock().unwrap().batches.push(
+            requests
+                .iter()
+                .map(|request| request.request_hash)
+                .collect(),
+        );
+
+        let mut votes = Vec::new();
+        for request in requests {
+            votes.push((
+                request.request_hash,
+                AzeroMostApi::receive_request(self, request).await,
+            ));
+        }
+
+        Ok(votes)
+    }
+}
+
+#[async_trait]
+impl EthMostApi for FakeMost {
+    async fn is_in_committee(&self, committee_id: U256) -> Result<bool, EthContractError> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .committees
+            .contains(&committee_id.as_u128()))
+    }
+
+    async fn committee_id(&self) -> Result<U256, EthContractError> {
+        Ok(self.state.lock().unwrap().current_committee_id.into())
+    }
+
+    async fn signature_state(
+        &self,
+        request_hash: [u8; 32],
+        committee_id: u128,
+    ) -> Result<SignatureState, EthContractError> {
+        let mut state = self.state.lock().unwrap();
+
+        Ok(
+            match (
+                state.needs_signature(request_hash, committee_id, true),
+                state.needs_signature(request_hash, committee_id, false),
+            ) {
+                (false, _) => SignatureState::Signed { finalized: true },
+                (true, false) => SignatureState::Signed { finalized: false },
+                (true, true) => SignatureState::NeedSignature,
+            },
+        )
+    }
+
+    async fn receive_request(&self, request: &TransferRequest) -> Result<H256, EthContractError> {
+        match self.vote(request) {
+            Some(_) => Ok(H256::zero()),
+            None => Err(EthContractError::Reverted(H256::zero())),
+        }
+    }
+}
+
+#[async_trait]
+impl AzeroRefundsApi for FakeMost {
+    async fn is_refunded(&self, request_nonce: u128) -> Result<bool, AzeroContractError> {
+        Ok(self.state.lock().unwrap().refunded.contains(&request_nonce))
+    }
+}
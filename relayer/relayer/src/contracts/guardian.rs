@@ -0,0 +1,285 @@
+//! The `Most` operations a guardian performs on each chain, behind traits so that the event
+//! handlers can be exercised against the in-memory fakes.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use contracts_azero_client::{Client, ClientWithSigner};
+use ethers::{
+    contract::{ContractCall, ContractError},
+    providers::{Middleware, ProviderError},
+    types::{Address, H256, U256, U64},
+};
+use log::debug;
+use thiserror::Error;
+
+use crate::{
+    config::Config,
+    connections::{azero::AzeroSigner, eth::SignedEthConnection},
+    contracts::{
+        azero_most, contract_signature_state, AzeroContractError, Most, MostInstance,
+        SignatureState, Vote,
+    },
+    helpers::RequestHashDomain,
+};
+
+/// A cross-chain transfer request, as voted on by the guardians of the destination chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferRequest {
+    pub request_hash: [u8; 32],
+    pub committee_id: u128,
+    pub dest_token_address: [u8; 32],
+    pub amount: u128,
+    pub dest_receiver_address: [u8; 32],
+    pub request_nonce: u128,
+}
+
+/// `Most` on Aleph Zero, as seen by a guardian.
+#[async_trait]
+pub trait AzeroMostApi {
+    async fn is_in_committee(&self, committee_id: u128) -> Result<bool, AzeroContractError>;
+
+    async fn current_committee_id(&self) -> Result<u128, AzeroContractError>;
+
+    /// Whether the guardian still has to vote for the request, either in the latest or in the
+    /// latest finalized block.
+    async fn needs_signature(
+        &self,
+        request_hash: [u8; 32],
+        committee_id: u128,
+        block_finalized: bool,
+    ) -> Result<bool, AzeroContractError>;
+
+    async fn receive_request(&self, request: &TransferRequest) -> Result<Vote, AzeroContractError>;
+
+    /// Votes for all the requests at once. A vote that fails doesn't affect the others, its error
+    /// is returned along with its request hash.
+    async fn receive_requests(
+        &self,
+        requests: &[TransferRequest],
+    ) -> Result<Vec<([u8; 32], Result<Vote, AzeroContractError>)>, AzeroContractError>;
+}
+
+/// `MostInstance` used with the guardian's signed connection.
+pub struct AzeroGuardian<'a> {
+    pub most: &'a MostInstance,
+    pub connection: &'a ClientWithSigner<AzeroSigner>,
+}
+
+#[async_trait]
+impl AzeroMostApi for AzeroGuardian<'_> {
+    async fn is_in_committee(&self, committee_id: u128) -> Result<bool, AzeroContractError> {
+        self.most
+            .is_in_committee(
+                self.connection.client(),
+                committee_id,
+                self.connection.account_id().clone(),
+            )
+            .await
+    }
+
+    async fn current_committee_id(&self) -> Result<u128, AzeroContractError> {
+        self.most
+            .current_committee_id(self.connection.client())
+            .await
+    }
+
+    async fn needs_signature(
+        &self,
+        request_hash: [u8; 32],
+        committee_id: u128,
+        block_finalized: bool,
+    ) -> Result<bool, AzeroContractError> {
+        self.most
+            .needs_signature(
+                self.connection.client(),
+                request_hash,
+                self.connection.account_id().clone(),
+                committee_id,
+                block_finalized,
+            )
+            .await
+    }
+
+    async fn receive_request(&self, request: &TransferRequest) -> Result<Vote, AzeroContractError> {
+        self.most
+            .receive_request(self.connection, request.into())
+            .await
+    }
+
+    async fn receive_requests(
+        &self,
+        requests: &[TransferRequest],
+    ) -> Result<Vec<([u8; 32], Result<Vote, AzeroContractError>)>, AzeroContractError> {
+        self.most
+            .receive_requests(self.connection, requests.iter().map(Into::into).collect())
+            .await
+    }
+}
+
+/// Refunds of the outgoing requests of `Most` on Aleph Zero.
+#[async_trait]
+pub trait AzeroRefundsApi {
+    /// Whether the outgoing request with `request_nonce` was refunded, in which case it must
+    /// never be processed on the destination chain.
+    async fn is_refunded(&self, request_nonce: u128) -> Result<bool, AzeroContractError>;
+}
+
+/// `MostInstance` used with a read-only connection.
+pub struct AzeroRefunds<'a> {
+    pub most: &'a MostInstance,
+    pub connection: &'a Client,
+}
+
+#[async_trait]
+impl AzeroRefundsApi for AzeroRefunds<'_> {
+    async fn is_refunded(&self, request_nonce: u128) -> Result<bool, AzeroContractError> {
+        self.most.is_refunded(self.connection, request_nonce).await
+    }
+}
+
+impl From<&TransferRequest> for azero_most::ReceiveRequestArgs {
+    fn from(request: &TransferRequest) -> Self {
+        Self {
+            request_hash: request.request_hash,
+            committee_id: request.committee_id,
+            dest_token_address: request.dest_token_address,
+            amount: request.amount,
+            dest_receiver_address: request.dest_receiver_address,
+            request_nonce: request.request_nonce,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error(transparent)]
+#[non_exhaustive]
+pub enum EthContractError {
+    #[error("Eth contract error")]
+    Contract(#[from] ContractError<SignedEthConnection>),
+
+    #[error("Ethers provider error")]
+    Provider(#[from] ProviderError),
+
+    #[error("Tx was not present in any block or mempool after the maximum number of retries")]
+    TxNotPresentInBlockOrMempool,
+
+    #[error("Tx {0:?} reverted")]
+    Reverted(H256),
+}
+
+/// `Most` on Ethereum, as seen by a guardian.
+#[async_trait]
+pub trait EthMostApi {
+    async fn is_in_committee(&self, committee_id: U256) -> Result<bool, EthContractError>;
+
+    async fn committee_id(&self) -> Result<U256, EthContractError>;
+
+    async fn signature_state(
+        &self,
+        request_hash: [u8; 32],
+        committee_id: u128,
+    ) -> Result<SignatureState, EthContractError>;
+
+    /// Sends the vote and waits for the configured number of confirmations, returning the hash
+    /// of the transaction.
+    async fn receive_request(&self, request: &TransferRequest) -> Result<H256, EthContractError>;
+}
+
+/// `Most` used with the guardian's signed connection.
+pub struct EthGuardian {
+    most: Most<SignedEthConnection>,
+    connection: Arc<SignedEthConnection>,
+    gas_limit: u32,
+    min_confirmations: usize,
+    submission_retries: usize,
+}
+
+impl EthGuardian {
+    pub fn new(address: Address, connection: Arc<SignedEthConnection>, config: &Config) -> Self {
+        Self {
+            most: Most::new(address, connection.clone()),
+            connection,
+            gas_limit: config.eth_gas_limit,
+            min_confirmations: config.eth_tx_min_confirmations,
+            submission_retries: config.eth_tx_submission_retries,
+        }
+    }
+
+    /// The domain `Most` binds the hashes of the incoming requests to, `None` until the owner sets it.
+    pub async fn request_hash_domain(&self) -> Result<Option<RequestHashDomain>, EthContractError> {
+        let domain = self.most.request_domain().await?;
+        if domain.src_contract == [0; 32] {
+            return Ok(None);
+        }
+
+        Ok(Some(RequestHashDomain::new(
+            domain.src_chain_id.as_u128(),
+            domain.src_contract,
+            H256::from(self.most.address()).0,
+            domain.from_nonce.as_u128(),
+        )))
+    }
+}
+
+#[async_trait]
+impl EthMostApi for EthGuardian {
+    async fn is_in_committee(&self, committee_id: U256) -> Result<bool, EthContractError> {
+        Ok(self
+            .most
+            .is_in_committee(committee_id, self.connection.address())
+            .await?)
+    }
+
+    async fn committee_id(&self) -> Result<U256, EthContractError> {
+        Ok(self.most.committee_id().await?)
+    }
+
+    async fn signature_state(
+        &self,
+        request_hash: [u8; 32],
+        committee_id: u128,
+    ) -> Result<SignatureState, EthContractError> {
+        Ok(contract_signature_state(
+            &self.most,
+            request_hash,
+            self.connection.address(),
+            committee_id,
+        )
+        .await?)
+    }
+
+    async fn receive_request(&self, request: &TransferRequest) -> Result<H256, EthContractError> {
+        let request_hash_hex = hex::encode(request.request_hash);
+        let call: ContractCall<SignedEthConnection, ()> = self.most.receive_request(
+            request.request_hash,
+            request.committee_id.into(),
+            request.dest_token_address,
+            request.amount.into(),
+            request.dest_receiver_address,
+            request.request_nonce.into(),
+        );
+
+        debug!("Dry-running tx for request 0x{request_hash_hex}");
+
+        // Dry-run the tx to check for potential reverts.
+        call.clone().gas(self.gas_limit).call().await?;
+
+        let receipt = call
+            .gas(self.gas_limit)
+            .nonce(self.connection.inner().next())
+            .send()
+            .await?
+            .confirmations(self.min_confirmations)
+            .retries(self.submission_retries)
+            .await?
+            .ok_or(EthContractError::TxNotPresentInBlockOrMempool)?;
+
+        // Check if the tx reverted.
+        if receipt.status == Some(U64::from(0)) {
+            return Err(EthContractError::Reverted(receipt.transaction_hash));
+        }
+
+        Ok(receipt.transaction_hash)
+    }
+}
@@ -1,15 +1,20 @@
 mod azero;
 #[cfg(not(feature = "l2"))]
 mod eth;
+#[cfg(test)]
+pub mod fakes;
+mod guardian;
 #[cfg(feature = "l2")]
 mod l2_eth;
 
 pub use azero::*;
 #[cfg(not(feature = "l2"))]
 pub use eth::*;
+pub use guardian::*;
 #[cfg(feature = "l2")]
 pub use l2_eth::*;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SignatureState {
     Signed { finalized: bool },
     NeedSignature,
@@ -4,9 +4,7 @@ use contracts_azero_client::ContractEvent;
 use ethers::{
     abi::{self, Token},
     core::types::{Address, H256},
-    prelude::{ContractCall, ContractError},
-    providers::{Middleware, ProviderError},
-    types::{U256, U64},
+    types::U256,
     utils::keccak256,
 };
 use log::{debug, error, info, trace, warn};
@@ -20,10 +18,11 @@ use tokio::{
 
 use crate::{
     config::Config,
-    connections::eth::SignedEthConnection,
+    connections::{azero::AzeroWsConnection, eth::SignedEthConnection},
     contracts::{
-        contract_signature_state, get_request_event_data, AzeroContractError,
-        CrosschainTransferRequestData, Most, SignatureState,
+        azero_most::{AzeroMostEvent, CrosschainTransferRequest},
+        AzeroContractError, AzeroRefunds, AzeroRefundsApi, EthContractError, EthGuardian,
+        EthMostApi, MostInstance, SignatureState, TransferRequest,
     },
     listeners::AzeroMostEvents,
     CircuitBreakerEvent,
@@ -39,17 +38,11 @@ pub enum AlephZeroEventHandlerError {
     #[error("Error when parsing ethereum address")]
     FromHex(#[from] rustc_hex::FromHexError),
 
-    #[error("Ethers provider error")]
-    Provider(#[from] ProviderError),
-
     #[error("Azero contract error")]
     AzeroContract(#[from] AzeroContractError),
 
     #[error("Eth contract error")]
-    EthContractTx(#[from] ContractError<SignedEthConnection>),
-
-    #[error("Tx was not present in any block or mempool after the maximum number of retries")]
-    TxNotPresentInBlockOrMempool,
+    EthContract(#[from] EthContractError),
 
     #[error("Contract reverted")]
     EthContractReverted,
@@ -65,31 +58,33 @@ impl AlephZeroEventHandler {
         event: ContractEvent,
         config: Arc<Config>,
         eth_signed_connection: Arc<SignedEthConnection>,
+        azero_connection: Arc<AzeroWsConnection>,
+        most_azero: Arc<MostInstance>,
     ) -> Result<(), AlephZeroEventHandlerError> {
         let Config {
             eth_contract_address,
             eth_tx_min_confirmations,
-            eth_tx_submission_retries,
             blacklisted_requests,
             ..
         } = &*config;
 
-        if !event
-            .name
-            .is_some_and(|name| name.eq("CrosschainTransferRequest"))
-        {
-            debug!("Skipping non azero contract event");
-            return Ok(());
-        }
-
-        let data = event.data;
-
         // decode event data
-        let crosschain_transfer_event = get_request_event_data(&data)?;
+        let crosschain_transfer_event =
+            match AzeroMostEvent::try_from(&event).map_err(AzeroContractError::EventDecode)? {
+                AzeroMostEvent::CrosschainTransferRequest(event) => event,
+                event if event.is_config_change() => {
+                    warn!("Most configuration changed: {event:?}");
+                    return Ok(());
+                }
+                _ => {
+                    debug!("Skipping non azero contract event");
+                    return Ok(());
+                }
+            };
 
         debug!("Handling azero contract event: {crosschain_transfer_event:?}");
 
-        let CrosschainTransferRequestData {
+        let CrosschainTransferRequest {
             committee_id,
             dest_token_address,
             amount,
@@ -97,20 +92,37 @@ impl AlephZeroEventHandler {
             request_nonce,
         } = crosschain_transfer_event;
 
-        // NOTE: for some reason, ethers-rs's `encode_packed` does not properly encode the data
-        // (it does not pad uint to 32 bytes, but uses the actual number of bytes required to store the value)
-        // so we use `abi::encode` instead (it only differs for signed and dynamic size types, which we don't use here)
-        let bytes = abi::encode(&[
-            Token::Uint(committee_id.into()),
-            Token::FixedBytes(dest_token_address.to_vec()),
-            Token::Uint(amount.into()),
-            Token::FixedBytes(dest_receiver_address.to_vec()),
-            Token::Uint(request_nonce.into()),
-        ]);
-
-        trace!("ABI compliant concatenated event bytes {bytes:?}");
+        let address = eth_contract_address.parse::<Address>()?;
+        let guardian = EthGuardian::new(address, eth_signed_connection, &config);
 
-        let request_hash = keccak256(bytes);
+        let v1_hash = guardian.request_hash_domain().await?.and_then(|domain| {
+            domain.hash_request(
+                committee_id,
+                &dest_token_address,
+                amount,
+                &dest_receiver_address,
+                request_nonce,
+            )
+        });
+        let request_hash = match v1_hash {
+            Some(request_hash) => request_hash,
+            None => {
+                // NOTE: for some reason, ethers-rs's `encode_packed` does not properly encode the data
+                // (it does not pad uint to 32 bytes, but uses the actual number of bytes required to store the value)
+                // so we use `abi::encode` instead (it only differs for signed and dynamic size types, which we don't use here)
+                let bytes = abi::encode(&[
+                    Token::Uint(committee_id.into()),
+                    Token::FixedBytes(dest_token_address.to_vec()),
+                    Token::Uint(amount.into()),
+                    Token::FixedBytes(dest_receiver_address.to_vec()),
+                    Token::Uint(request_nonce.into()),
+                ]);
+
+                trace!("ABI compliant concatenated event bytes {bytes:?}");
+
+                keccak256(bytes)
+            }
+        };
         debug!("Hashed event data: {request_hash:?}");
 
         let request_hash_hex = hex::encode(request_hash);
@@ -128,87 +140,81 @@ impl AlephZeroEventHandler {
             }
         }
 
-        let address = eth_contract_address.parse::<Address>()?;
-        let contract = Most::new(address, eth_signed_connection.clone());
-
-        if not_in_committee(
-            &contract,
-            committee_id.into(),
-            eth_signed_connection.address(),
-        )
-        .await?
-        {
-            info!("Guardian signature for 0x{request_hash_hex} not needed - request from a past committee");
-            return Ok(());
-        }
+        let refunds = AzeroRefunds {
+            most: &most_azero,
+            connection: &azero_connection,
+        };
+        let request = TransferRequest {
+            request_hash,
+            committee_id,
+            dest_token_address,
+            amount,
+            dest_receiver_address,
+            request_nonce,
+        };
 
-        loop {
-            match contract_signature_state(
-                &contract,
-                request_hash,
-                eth_signed_connection.address(),
-                committee_id,
-            )
+        handle_request(&guardian, &refunds, &request, *eth_tx_min_confirmations).await
+    }
+}
+
+/// Votes for the request until the vote is finalized, unless the guardian is not in its committee
+/// or the request gets refunded on Aleph Zero.
+async fn handle_request<M: EthMostApi + Sync, R: AzeroRefundsApi + Sync>(
+    most: &M,
+    refunds: &R,
+    request: &TransferRequest,
+    eth_tx_min_confirmations: usize,
+) -> Result<(), AlephZeroEventHandlerError> {
+    let request_hash_hex = hex::encode(request.request_hash);
+
+    if not_in_committee(most, request.committee_id.into()).await? {
+        info!("Guardian signature for 0x{request_hash_hex} not needed - request from a past committee");
+        return Ok(());
+    }
+
+    loop {
+        match most
+            .signature_state(request.request_hash, request.committee_id)
             .await?
-            {
-                SignatureState::Signed { finalized: true } => {
-                    info!("Guardian signature for 0x{request_hash_hex} no longer needed");
+        {
+            SignatureState::Signed { finalized: true } => {
+                info!("Guardian signature for 0x{request_hash_hex} no longer needed");
+                return Ok(());
+            }
+            SignatureState::Signed { finalized: false } => {
+                info!("Request 0x{request_hash_hex} not yet finalized.");
+                sleep(Duration::from_secs(ETH_WAIT_FOR_FINALITY_CHECK_SEC)).await;
+            }
+            SignatureState::NeedSignature => {
+                // a refund can land at any time before the request is processed
+                if refunds.is_refunded(request.request_nonce).await? {
+                    warn!("Skipping request 0x{request_hash_hex} refunded on Aleph Zero");
                     return Ok(());
                 }
-                SignatureState::Signed { finalized: false } => {
-                    info!("Request 0x{request_hash_hex} not yet finalized.");
-                    sleep(Duration::from_secs(ETH_WAIT_FOR_FINALITY_CHECK_SEC)).await;
-                }
-                SignatureState::NeedSignature => {
-                    // forward transfer & vote
-                    let call: ContractCall<SignedEthConnection, ()> = contract.receive_request(
-                        request_hash,
-                        committee_id.into(),
-                        dest_token_address,
-                        amount.into(),
-                        dest_receiver_address,
-                        request_nonce.into(),
-                    );
-
-                    debug!("Dry-running tx for request 0x{request_hash_hex}");
-
-                    // Dry-run the tx to check for potential reverts.
-                    call.clone().gas(config.eth_gas_limit).call().await?;
-
-                    info!("Sending tx for request 0x{request_hash_hex} to the Ethereum network and waiting for {eth_tx_min_confirmations} confirmations.");
-
-                    let receipt = call
-                        .gas(config.eth_gas_limit)
-                        .nonce(eth_signed_connection.inner().next())
-                        .send()
-                        .await?
-                        .confirmations(*eth_tx_min_confirmations)
-                        .retries(*eth_tx_submission_retries)
-                        .await?
-                        .ok_or(AlephZeroEventHandlerError::TxNotPresentInBlockOrMempool)?;
-
-                    let tx_hash = receipt.transaction_hash;
-                    let tx_status = receipt.status;
-
-                    // Check if the tx reverted.
-                    if tx_status == Some(U64::from(0)) {
+
+                // forward transfer & vote
+                info!("Sending tx for request 0x{request_hash_hex} to the Ethereum network and waiting for {eth_tx_min_confirmations} confirmations.");
+
+                match most.receive_request(request).await {
+                    Ok(tx_hash) => {
+                        info!("Tx for request 0x{request_hash_hex} has been sent to the Ethereum network: {tx_hash:?} and received {eth_tx_min_confirmations} confirmations.");
+                    }
+                    Err(EthContractError::Reverted(tx_hash)) => {
                         warn!("Tx for request 0x{request_hash_hex} has been sent to the Ethereum network: {tx_hash:?} but it reverted.");
                         return Err(AlephZeroEventHandlerError::EthContractReverted);
                     }
-
-                    info!("Tx for request 0x{request_hash_hex} has been sent to the Ethereum network: {tx_hash:?} and received {eth_tx_min_confirmations} confirmations.");
+                    Err(why) => return Err(why.into()),
                 }
             }
         }
     }
 }
 
-async fn not_in_committee(
-    most: &Most<SignedEthConnection>,
+async fn not_in_committee<M: EthMostApi + Sync>(
+    most: &M,
     committee_id: U256,
-    address: Address,
 ) -> Result<bool, AlephZeroEventHandlerError> {
-    if most.is_in_committee(committee_id, address).await? {
+    if most.is_in_committee(committee_id).await? {
         return Ok(false);
     }
 
@@ -235,6 +241,9 @@ pub enum AlephZeroEventsHandlerError {
     #[error("task join error")]
     Join(#[from] JoinError),
 
+    #[error("Azero contract error")]
+    AzeroContract(#[from] AzeroContractError),
+
     #[error("ack receiver dropped before response could be sent")]
     AckSend,
 }
@@ -245,11 +254,19 @@ impl AlephZeroEventsHandler {
     pub async fn run(
         config: Arc<Config>,
         eth_signed_connection: Arc<SignedEthConnection>,
+        azero_connection: Arc<AzeroWsConnection>,
         mut azero_events_receiver: mpsc::Receiver<AzeroMostEvents>,
         circuit_breaker_sender: broadcast::Sender<CircuitBreakerEvent>,
         mut circuit_breaker_receiver: broadcast::Receiver<CircuitBreakerEvent>,
     ) -> Result<CircuitBreakerEvent, AlephZeroEventsHandlerError> {
         let mut event_handler_tasks = JoinSet::new();
+        let most_azero = Arc::new(MostInstance::new(
+            &config.azero_contract_address,
+            &config.azero_contract_metadata,
+            config.azero_ref_time_limit,
+            config.azero_proof_size_limit,
+            config.azero_gas_multiplier,
+        )?);
 
         loop {
             debug!("Ping");
@@ -272,6 +289,8 @@ impl AlephZeroEventsHandler {
 
                     let config = Arc::clone(&config);
                     let eth_signed_connection = Arc::clone(&eth_signed_connection);
+                    let azero_connection = Arc::clone(&azero_connection);
+                    let most_azero = Arc::clone(&most_azero);
                     let circuit_breaker_sender = circuit_breaker_sender.clone ();
 
                     // spawn non-blocking task to handle all events w-out blocking the events publisher
@@ -283,6 +302,8 @@ impl AlephZeroEventsHandler {
                                 event,
                                 Arc::clone(&config),
                                 Arc::clone(&eth_signed_connection),
+                                Arc::clone(&azero_connection),
+                                Arc::clone(&most_azero),
                             ));
                         }
 
@@ -312,3 +333,112 @@ impl AlephZeroEventsHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::contracts::fakes::FakeMost;
+
+    const COMMITTEE_ID: u128 = 1;
+    const MIN_CONFIRMATIONS: usize = 1;
+
+    fn request(committee_id: u128) -> TransferRequest {
+        TransferRequest {
+            request_hash: [1; 32],
+            committee_id,
+            dest_token_address: [2; 32],
+            amount: 100,
+            dest_receiver_address: [3; 32],
+            request_nonce: 0,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_votes_and_waits_for_finality() {
+        let most = FakeMost::new(COMMITTEE_ID)
+            .with_member_of(COMMITTEE_ID)
+            .finalizing_after(3);
+
+        let result = handle_request(&most, &most, &request(COMMITTEE_ID), MIN_CONFIRMATIONS).await;
+
+        assert!(result.is_ok());
+        assert_eq!(most.votes(), vec![request(COMMITTEE_ID)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_skips_requests_from_past_committees() {
+        let most = FakeMost::new(COMMITTEE_ID + 1).with_member_of(COMMITTEE_ID + 1);
+
+        let result = handle_request(&most, &most, &request(COMMITTEE_ID), MIN_CONFIRMATIONS).await;
+
+        assert!(result.is_ok());
+        assert!(most.votes().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fails_on_committee_mismatch() {
+        let most = FakeMost::new(COMMITTEE_ID).with_member_of(COMMITTEE_ID);
+
+        let result =
+            handle_request(&most, &most, &request(COMMITTEE_ID + 1), MIN_CONFIRMATIONS).await;
+
+        assert!(matches!(
+            result,
+            Err(AlephZeroEventHandlerError::CommitteeIdMismatch)
+        ));
+        assert!(most.votes().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_does_not_vote_again_when_already_signed() {
+        let most = FakeMost::new(COMMITTEE_ID)
+            .with_member_of(COMMITTEE_ID)
+            .finalizing_after(2)
+            .with_signed(request(COMMITTEE_ID).request_hash);
+
+        let result = handle_request(&most, &most, &request(COMMITTEE_ID), MIN_CONFIRMATIONS).await;
+
+        assert!(result.is_ok());
+        assert!(most.votes().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_skips_refunded_requests() {
+        let most = FakeMost::new(COMMITTEE_ID)
+            .with_member_of(COMMITTEE_ID)
+            .with_refunded(request(COMMITTEE_ID).request_nonce);
+
+        let result = handle_request(&most, &most, &request(COMMITTEE_ID), MIN_CONFIRMATIONS).await;
+
+        assert!(result.is_ok());
+        assert!(most.votes().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_checks_for_refund_before_every_vote() {
+        let most = FakeMost::new(COMMITTEE_ID)
+            .with_member_of(COMMITTEE_ID)
+            .dropping_votes()
+            .refunding_after_vote(request(COMMITTEE_ID).request_nonce);
+
+        let result = handle_request(&most, &most, &request(COMMITTEE_ID), MIN_CONFIRMATIONS).await;
+
+        assert!(result.is_ok());
+        assert_eq!(most.votes(), vec![request(COMMITTEE_ID)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fails_when_vote_reverts() {
+        let most = FakeMost::new(COMMITTEE_ID)
+            .with_member_of(COMMITTEE_ID)
+            .reverting();
+
+        let result = handle_request(&most, &most, &request(COMMITTEE_ID), MIN_CONFIRMATIONS).await;
+
+        assert!(matches!(
+            result,
+            Err(AlephZeroEventHandlerError::EthContractReverted)
+        ));
+        assert_eq!(most.votes(), vec![request(COMMITTEE_ID)]);
+    }
+}
@@ -14,8 +14,11 @@ use tokio::{
 use crate::{
     config::Config,
     connections::azero::AzeroSigner,
-    contracts::{AzeroContractError, CrosschainTransferRequestFilter, MostEvents, MostInstance},
-    helpers::concat_u8_arrays,
+    contracts::{
+        azero_most::MostError, AzeroContractError, AzeroGuardian, AzeroMostApi,
+        CrosschainTransferRequestFilter, MostEvents, MostInstance, TransferRequest, VoteStatus,
+    },
+    helpers::{concat_u8_arrays, RequestHashDomain},
     listeners::EthMostEvents,
     CircuitBreakerEvent,
 };
@@ -53,19 +56,50 @@ pub enum EthereumEventHandlerError {
 pub struct EthereumEventHandler;
 
 impl EthereumEventHandler {
-    pub async fn handle_event(
-        event: MostEvents,
+    /// Votes for the transfer requests among `events`, see `handle_requests`.
+    pub async fn handle_events(
+        events: Vec<MostEvents>,
         config: &Config,
         azero_connection: &ClientWithSigner<AzeroSigner>,
     ) -> Result<(), EthereumEventHandlerError> {
-        let Config {
-            azero_contract_address,
-            azero_contract_metadata,
-            blacklisted_requests,
-            ..
-        } = config;
-
-        if let MostEvents::CrosschainTransferRequestFilter(
+        let contract = MostInstance::new(
+            &config.azero_contract_address,
+            &config.azero_contract_metadata,
+            config.azero_ref_time_limit,
+            config.azero_proof_size_limit,
+            config.azero_gas_multiplier,
+        )?;
+        let domain = contract
+            .request_hash_domain(azero_connection.client())
+            .await?;
+
+        let mut requests = Vec::new();
+        for event in events {
+            if let Some(request) = Self::transfer_request(event, config, domain.as_ref())? {
+                requests.push(request);
+            }
+        }
+        if requests.is_empty() {
+            return Ok(());
+        }
+
+        let guardian = AzeroGuardian {
+            most: &contract,
+            connection: azero_connection,
+        };
+
+        handle_requests(&guardian, &requests, config.azero_max_batched_votes).await
+    }
+
+    /// The request of a `CrosschainTransferRequest` event, `None` for other events and
+    /// blacklisted requests. The request is hashed with the version 1 hash once `domain` applies to
+    /// it, and with the legacy hash otherwise.
+    fn transfer_request(
+        event: MostEvents,
+        config: &Config,
+        domain: Option<&RequestHashDomain>,
+    ) -> Result<Option<TransferRequest>, EthereumEventHandlerError> {
+        let MostEvents::CrosschainTransferRequestFilter(
             crosschain_transfer_event @ CrosschainTransferRequestFilter {
                 committee_id,
                 dest_token_address,
@@ -75,125 +109,178 @@ impl EthereumEventHandler {
                 ..
             },
         ) = event
-        {
-            debug!("Handling eth contract event: {crosschain_transfer_event:?}");
+        else {
+            return Ok(None);
+        };
 
-            // concat bytes
-            let bytes = concat_u8_arrays(vec![
-                &committee_id.as_u128().to_le_bytes(),
+        debug!("Handling eth contract event: {crosschain_transfer_event:?}");
+
+        let v1_hash = domain.and_then(|domain| {
+            domain.hash_request(
+                committee_id.as_u128(),
                 &dest_token_address,
-                &amount.as_u128().to_le_bytes(),
+                amount.as_u128(),
                 &dest_receiver_address,
-                &request_nonce.as_u128().to_le_bytes(),
-            ]);
-
-            trace!("Concatenated event bytes: {bytes:?}");
-
-            let request_hash = keccak256(bytes);
-            debug!("Hashed event data: {request_hash:?}");
+                request_nonce.as_u128(),
+            )
+        });
+        let request_hash = match v1_hash {
+            Some(request_hash) => request_hash,
+            None => {
+                // concat bytes
+                let bytes = concat_u8_arrays(vec![
+                    &committee_id.as_u128().to_le_bytes(),
+                    &dest_token_address,
+                    &amount.as_u128().to_le_bytes(),
+                    &dest_receiver_address,
+                    &request_nonce.as_u128().to_le_bytes(),
+                ]);
+
+                trace!("Concatenated event bytes: {bytes:?}");
+
+                keccak256(bytes)
+            }
+        };
+        debug!("Hashed event data: {request_hash:?}");
 
-            let request_hash_hex = hex::encode(request_hash);
+        let request_hash_hex = hex::encode(request_hash);
 
-            info!(
-                "Decoded event data: [request_hash: 0x{request_hash_hex}, dest_token_address: {}, amount: {amount}, dest_receiver_address: {}, request_nonce: {request_nonce}, committee_id: {committee_id}]",
-                AccountId::from(dest_token_address),
-                AccountId::from(dest_receiver_address)
-            );
+        info!(
+            "Decoded event data: [request_hash: 0x{request_hash_hex}, dest_token_address: {}, amount: {amount}, dest_receiver_address: {}, request_nonce: {request_nonce}, committee_id: {committee_id}]",
+            AccountId::from(dest_token_address),
+            AccountId::from(dest_receiver_address)
+        );
 
-            if let Some(blacklist) = blacklisted_requests {
-                if blacklist.contains(&H256::from_str(&request_hash_hex)?) {
-                    warn!("Skipping blacklisted request: 0x{request_hash_hex}");
-                    return Ok(());
-                }
+        if let Some(blacklist) = &config.blacklisted_requests {
+            if blacklist.contains(&H256::from_str(&request_hash_hex)?) {
+                warn!("Skipping blacklisted request: 0x{request_hash_hex}");
+                return Ok(None);
             }
+        }
 
-            let contract = MostInstance::new(
-                azero_contract_address,
-                azero_contract_metadata,
-                config.azero_ref_time_limit,
-                config.azero_proof_size_limit,
-            )?;
+        Ok(Some(TransferRequest {
+            request_hash,
+            committee_id: committee_id.as_u128(),
+            dest_token_address,
+            amount: amount.as_u128(),
+            dest_receiver_address,
+            request_nonce: request_nonce.as_u128(),
+        }))
+    }
+}
 
-            let committee_id = committee_id.as_u128();
-            let amount = amount.as_u128();
-            let request_nonce = request_nonce.as_u128();
+/// Votes for the requests until the votes are finalized, skipping the requests from committees
+/// the guardian is not in. Votes that are ready at the same time are sent together, at most
+/// `max_batch` per extrinsic.
+async fn handle_requests<M: AzeroMostApi + Sync>(
+    most: &M,
+    requests: &[TransferRequest],
+    max_batch: usize,
+) -> Result<(), EthereumEventHandlerError> {
+    let mut pending = Vec::new();
+    for request in requests {
+        if not_in_committee(most, request.committee_id).await? {
+            info!(
+                "Guardian signature for 0x{} not needed - request from a different committee",
+                hex::encode(request.request_hash)
+            );
+        } else {
+            pending.push(*request);
+        }
+    }
 
-            if not_in_committee(&contract, azero_connection, committee_id).await? {
-                info!("Guardian signature for 0x{request_hash_hex} not needed - request from a different committee");
-                return Ok(());
+    while !pending.is_empty() {
+        let mut unfinalized = Vec::new();
+        for request in pending {
+            let request_hash_hex = hex::encode(request.request_hash);
+            if most
+                .needs_signature(request.request_hash, request.committee_id, true)
+                .await?
+            {
+                debug!("Azero: request 0x{request_hash_hex} not yet finalized.");
+                unfinalized.push(request);
+            } else {
+                info!("Guardian signature for 0x{request_hash_hex} no longer needed");
             }
+        }
+        pending = unfinalized;
 
-            while contract
-                .needs_signature(
-                    azero_connection.client(),
-                    request_hash,
-                    azero_connection.account_id().clone(),
-                    committee_id,
-                    true,
-                )
+        let mut ready = Vec::new();
+        for request in &pending {
+            if most
+                .needs_signature(request.request_hash, request.committee_id, false)
                 .await?
             {
-                debug!("Azero: request 0x{request_hash_hex} not yet finalized.");
+                ready.push(*request);
+            }
+        }
+        if ready.is_empty() {
+            if !pending.is_empty() {
+                sleep(AZERO_WAIT_FOR_FINALITY_CHECK).await;
+            }
+            continue;
+        }
+
+        for batch in ready.chunks(max_batch.max(1)) {
+            vote(most, batch).await?;
+        }
+    }
 
-                if !contract
-                    .needs_signature(
-                        azero_connection.client(),
-                        request_hash,
-                        azero_connection.account_id().clone(),
-                        committee_id,
-                        false,
-                    )
-                    .await?
-                {
-                    sleep(AZERO_WAIT_FOR_FINALITY_CHECK).await;
-                    continue;
+    Ok(())
+}
+
+/// Sends the votes for the requests together. Every failed vote is logged, the first one is
+/// returned as the error.
+async fn vote<M: AzeroMostApi + Sync>(
+    most: &M,
+    requests: &[TransferRequest],
+) -> Result<(), EthereumEventHandlerError> {
+    let mut failed = None;
+    for (request_hash, vote) in most.receive_requests(requests).await? {
+        let request_hash_hex = hex::encode(request_hash);
+        match vote {
+            Ok(vote) => {
+                let tx_hash = vote.tx_info.tx_hash;
+                let fee = vote.tx_info.fee;
+                match vote.status {
+                    VoteStatus::Signed => info!("Voted for request 0x{request_hash_hex} in tx {tx_hash:?}, fee paid: {fee:?}"),
+                    VoteStatus::AlreadySigned => info!("Request 0x{request_hash_hex} was already signed by this guardian, tx {tx_hash:?} was a no-op"),
+                    VoteStatus::AlreadyProcessed => info!("Request 0x{request_hash_hex} was already processed, tx {tx_hash:?} was a no-op"),
                 }
-                // send vote
-                contract
-                    .receive_request(
-                        azero_connection,
-                        request_hash,
-                        committee_id,
-                        dest_token_address,
-                        amount,
-                        dest_receiver_address,
-                        request_nonce,
-                    )
-                    .await
-                    // default AlephClient error is MBs large and useless, dumps the entire runtime for some reason
-                    .map_err(|_| EthereumEventHandlerError::ReceiveRequestTxFailure {
-                        request_hash: hex::encode(request_hash),
-                        committee_id,
-                        dest_token_address: hex::encode(dest_token_address),
-                        amount,
-                        dest_receiver_address: hex::encode(dest_receiver_address),
-                        request_nonce,
-                    })?;
             }
-            info!("Guardian signature for 0x{request_hash_hex} no longer needed");
+            Err(AzeroContractError::Most(MostError::RequestAlreadySigned)) => {
+                info!("Request 0x{request_hash_hex} was already signed by this guardian");
+            }
+            Err(why) => {
+                // Only the short description, the default AlephClient error is MBs large and useless, dumps the entire runtime for some reason
+                warn!("Vote for request 0x{request_hash_hex} failed: {why}");
+                failed.get_or_insert(request_hash);
+            }
         }
+    }
 
-        Ok(())
+    match failed.and_then(|hash| requests.iter().find(|request| request.request_hash == hash)) {
+        Some(request) => Err(EthereumEventHandlerError::ReceiveRequestTxFailure {
+            request_hash: hex::encode(request.request_hash),
+            committee_id: request.committee_id,
+            dest_token_address: hex::encode(request.dest_token_address),
+            amount: request.amount,
+            dest_receiver_address: hex::encode(request.dest_receiver_address),
+            request_nonce: request.request_nonce,
+        }),
+        None => Ok(()),
     }
 }
 
-async fn not_in_committee(
-    most: &MostInstance,
-    connection: &ClientWithSigner<AzeroSigner>,
+async fn not_in_committee<M: AzeroMostApi + Sync>(
+    most: &M,
     committee_id: u128,
 ) -> Result<bool, EthereumEventHandlerError> {
-    if most
-        .is_in_committee(
-            connection.client(),
-            committee_id,
-            connection.account_id().clone(),
-        )
-        .await?
-    {
+    if most.is_in_committee(committee_id).await? {
         return Ok(false);
     }
 
-    if committee_id > most.current_committee_id(connection.client()).await? {
+    if committee_id > most.current_committee_id().await? {
         error!("Request from a future committee {committee_id} - this likely indicates MOST contracts misconfiguration");
         return Err(EthereumEventHandlerError::CommitteeIdMismatch);
     }
@@ -245,22 +332,20 @@ impl EthereumEventsHandler {
 
                     info!("Received a batch of {} events from blocks {from_block} to {to_block}", events.len());
 
-                    for event in events {
-                        select! {
-                            cb_event = circuit_breaker_receiver.recv () => {
-                                warn!("Exiting due to a circuit breaker event {cb_event:?}");
-                                return Ok(cb_event?);
-                            },
-
-                            result = EthereumEventHandler::handle_event(event, &config, &azero_signed_connection) => {
-                                if let Err(why) = result {
-                                    circuit_breaker_sender.send(CircuitBreakerEvent::EthEventHandlerFailure)?;
-                                    warn!("Event handler failed {why:?}, exiting");
-                                    return Ok (CircuitBreakerEvent::EthEventHandlerFailure);
-                                }
-                            },
-
-                        }
+                    select! {
+                        cb_event = circuit_breaker_receiver.recv () => {
+                            warn!("Exiting due to a circuit breaker event {cb_event:?}");
+                            return Ok(cb_event?);
+                        },
+
+                        result = EthereumEventHandler::handle_events(events, &config, &azero_signed_connection) => {
+                            if let Err(why) = result {
+                                circuit_breaker_sender.send(CircuitBreakerEvent::EthEventHandlerFailure)?;
+                                warn!("Event handler failed {why:?}, exiting");
+                                return Ok (CircuitBreakerEvent::EthEventHandlerFailure);
+                            }
+                        },
+
                     }
 
                     info!("Acknowledging events batch");
@@ -274,3 +359,138 @@ impl EthereumEventsHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::contracts::fakes::FakeMost;
+
+    const COMMITTEE_ID: u128 = 1;
+    const MAX_BATCH: usize = 2;
+
+    fn request(committee_id: u128) -> TransferRequest {
+        TransferRequest {
+            request_hash: [committee_id as u8; 32],
+            committee_id,
+            dest_token_address: [2; 32],
+            amount: 100,
+            dest_receiver_address: [3; 32],
+            request_nonce: 0,
+        }
+    }
+
+    /// Another request of `COMMITTEE_ID`, with a distinct hash.
+    fn nth_request(nonce: u128) -> TransferRequest {
+        TransferRequest {
+            request_hash: [0x10 + nonce as u8; 32],
+            request_nonce: nonce,
+            ..request(COMMITTEE_ID)
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_votes_and_waits_for_finality() {
+        let most = FakeMost::new(COMMITTEE_ID)
+            .with_member_of(COMMITTEE_ID)
+            .finalizing_after(3);
+
+        let result = handle_requests(&most, &[request(COMMITTEE_ID)], MAX_BATCH).await;
+
+        assert!(result.is_ok());
+        assert_eq!(most.votes(), vec![request(COMMITTEE_ID)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_skips_requests_from_past_committees() {
+        let most = FakeMost::new(COMMITTEE_ID + 1).with_member_of(COMMITTEE_ID + 1);
+
+        let result = handle_requests(&most, &[request(COMMITTEE_ID)], MAX_BATCH).await;
+
+        assert!(result.is_ok());
+        assert!(most.votes().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fails_on_committee_mismatch() {
+        let most = FakeMost::new(COMMITTEE_ID).with_member_of(COMMITTEE_ID);
+
+        let result = handle_requests(&most, &[request(COMMITTEE_ID + 1)], MAX_BATCH).await;
+
+        assert!(matches!(
+            result,
+            Err(EthereumEventHandlerError::CommitteeIdMismatch)
+        ));
+        assert!(most.votes().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_does_not_vote_again_when_already_signed() {
+        let most = FakeMost::new(COMMITTEE_ID)
+            .with_member_of(COMMITTEE_ID)
+            .finalizing_after(2)
+            .with_signed(request(COMMITTEE_ID).request_hash);
+
+        let result = handle_requests(&most, &[request(COMMITTEE_ID)], MAX_BATCH).await;
+
+        assert!(result.is_ok());
+        assert!(most.votes().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fails_when_vote_reverts() {
+        let most = FakeMost::new(COMMITTEE_ID)
+            .with_member_of(COMMITTEE_ID)
+            .reverting();
+
+        let result = handle_requests(&most, &[request(COMMITTEE_ID)], MAX_BATCH).await;
+
+        assert!(matches!(
+            result,
+            Err(EthereumEventHandlerError::ReceiveRequestTxFailure { .. })
+        ));
+        assert_eq!(most.votes(), vec![request(COMMITTEE_ID)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batches_ready_votes() {
+        let most = FakeMost::new(COMMITTEE_ID)
+            .with_member_of(COMMITTEE_ID)
+            .finalizing_after(2);
+        let requests = [request(COMMITTEE_ID), nth_request(1), nth_request(2)];
+
+        let result = handle_requests(&most, &requests, MAX_BATCH).await;
+
+        assert!(result.is_ok());
+        assert_eq!(most.votes(), requests.to_vec());
+        assert_eq!(
+            most.batches(),
+            vec![
+                vec![requests[0].request_hash, requests[1].request_hash],
+                vec![requests[2].request_hash],
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reports_failed_vote_by_request_hash() {
+        let failing = nth_request(1);
+        let most = FakeMost::new(COMMITTEE_ID)
+            .with_member_of(COMMITTEE_ID)
+            .rejecting(failing.request_hash);
+        let requests = [request(COMMITTEE_ID), failing];
+
+        let result = handle_requests(&most, &requests, MAX_BATCH).await;
+
+        match result {
+            Err(EthereumEventHandlerError::ReceiveRequestTxFailure { request_hash, .. }) => {
+                assert_eq!(request_hash, hex::encode(failing.request_hash))
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+        assert_eq!(most.votes(), requests.to_vec());
+        assert!(!most
+            .needs_signature(requests[0].request_hash, COMMITTEE_ID, true)
+            .await
+            .unwrap());
+    }
+}
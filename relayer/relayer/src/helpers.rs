@@ -1,3 +1,8 @@
+use ethers::utils::keccak256;
+
+/// Tag of the version 1 request hashes, which bind a request to the deployments it goes between.
+const REQUEST_HASH_V1_TAG: &[u8] = b"MOST_REQUEST_V1";
+
 pub fn concat_u8_arrays(arrays: Vec<&[u8]>) -> Vec<u8> {
     let mut result = Vec::new();
     for array in arrays {
@@ -5,3 +10,148 @@ pub fn concat_u8_arrays(arrays: Vec<&[u8]>) -> Vec<u8> {
     }
     result
 }
+
+/// Domain that a destination `Most` binds the hashes of the requests from `from_nonce` on to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestHashDomain {
+    pub separator: [u8; 32],
+    pub from_nonce: u128,
+}
+
+impl RequestHashDomain {
+    pub fn new(
+        src_chain_id: u128,
+        src_contract: [u8; 32],
+        dest_contract: [u8; 32],
+        from_nonce: u128,
+    ) -> Self {
+        Self {
+            separator: request_domain_separator(src_chain_id, &src_contract, &dest_contract),
+            from_nonce,
+        }
+    }
+
+    /// The version 1 hash of the request, `None` for the requests older than the domain.
+    pub fn hash_request(
+        &self,
+        committee_id: u128,
+        dest_token_address: &[u8; 32],
+        amount: u128,
+        dest_receiver_address: &[u8; 32],
+        request_nonce: u128,
+    ) -> Option<[u8; 32]> {
+        (request_nonce >= self.from_nonce).then(|| {
+            hash_request_data_v1(
+                &self.separator,
+                committee_id,
+                dest_token_address,
+                amount,
+                dest_receiver_address,
+                request_nonce,
+            )
+        })
+    }
+}
+
+/// Separates the version 1 request hashes of a source and a destination deployment from those of
+/// any other pair of deployments. Contract addresses shorter than 32 bytes are left-padded.
+pub fn request_domain_separator(
+    src_chain_id: u128,
+    src_contract: &[u8; 32],
+    dest_contract: &[u8; 32],
+) -> [u8; 32] {
+    keccak256(concat_u8_arrays(vec![
+        REQUEST_HASH_V1_TAG,
+        &uint256(src_chain_id),
+        src_contract,
+        dest_contract,
+    ]))
+}
+
+/// Version 1 of the request hash, the same on both chains. Numbers are encoded as in
+/// `abi.encodePacked` of `uint256` values.
+pub fn hash_request_data_v1(
+    domain_separator: &[u8; 32],
+    committee_id: u128,
+    dest_token_address: &[u8; 32],
+    amount: u128,
+    dest_receiver_address: &[u8; 32],
+    request_nonce: u128,
+) -> [u8; 32] {
+    keccak256(concat_u8_arrays(vec![
+        domain_separator,
+        &uint256(committee_id),
+        dest_token_address,
+        &uint256(amount),
+        dest_receiver_address,
+        &uint256(request_nonce),
+    ]))
+}
+
+fn uint256(value: u128) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    const VECTORS: &str = include_str!("../../../test-vectors/request_hash_v1.json");
+
+    fn bytes32(vector: &Value, field: &str) -> [u8; 32] {
+        let hex_string = vector[field].as_str().expect("Field is a string");
+        hex::decode(hex_string.trim_start_matches("0x"))
+            .expect("Field is hex encoded")
+            .try_into()
+            .expect("Field has 32 bytes")
+    }
+
+    fn number(vector: &Value, field: &str) -> u128 {
+        let number = vector[field].as_str().expect("Field is a string");
+        number.parse().expect("Field is a number")
+    }
+
+    #[test]
+    fn test_request_hash_matches_test_vectors() {
+        let vectors: Vec<Value> = serde_json::from_str(VECTORS).expect("Vectors are valid JSON");
+        assert!(!vectors.is_empty());
+
+        for vector in &vectors {
+            let description = vector["description"].as_str().unwrap_or_default();
+            let request_nonce = number(vector, "request_nonce");
+            let domain = RequestHashDomain::new(
+                number(vector, "src_chain_id"),
+                bytes32(vector, "src_contract"),
+                bytes32(vector, "dest_contract"),
+                request_nonce,
+            );
+            assert_eq!(
+                domain.separator,
+                bytes32(vector, "domain_separator"),
+                "{description}"
+            );
+
+            let hash_request = |request_nonce| {
+                domain.hash_request(
+                    number(vector, "committee_id"),
+                    &bytes32(vector, "dest_token_address"),
+                    number(vector, "amount"),
+                    &bytes32(vector, "dest_receiver_address"),
+                    request_nonce,
+                )
+            };
+            assert_eq!(
+                hash_request(request_nonce),
+                Some(bytes32(vector, "request_hash")),
+                "{description}"
+            );
+            if request_nonce > 0 {
+                assert_eq!(hash_request(request_nonce - 1), None, "{description}");
+            }
+        }
+    }
+}
@@ -1,11 +1,8 @@
-use std::{cmp::min, sync::Arc, time::Duration};
+use std::{sync::Arc, time::Duration};
 
-use contracts_azero_client::{Client, ContractEvent, ContractInstance};
-use futures::{
-    future::join_all,
-    stream::{FuturesOrdered, StreamExt},
-};
-use log::{debug, error, info, warn};
+use contracts_azero_client::{Client, ContractEvent};
+use futures::stream::{FuturesOrdered, StreamExt};
+use log::{debug, info, warn};
 use thiserror::Error;
 use tokio::{
     select,
@@ -49,9 +46,6 @@ pub enum AlephZeroListenerError {
     #[error("block seal send error")]
     BlockSealSend(#[from] mpsc::error::SendError<u32>),
 
-    #[error("broadcast send error")]
-    Broadcast(#[from] broadcast::error::SendError<u32>),
-
     #[error("broadcast send error")]
     BroadcastSend(#[from] broadcast::error::SendError<CircuitBreakerEvent>),
 
@@ -66,12 +60,10 @@ pub enum AlephZeroListenerError {
 pub struct AlephZeroListener;
 
 impl AlephZeroListener {
-    #[allow(clippy::too_many_arguments)]
     pub async fn run(
         config: Arc<Config>,
         azero_connection: Arc<Client>,
         azero_events_sender: mpsc::Sender<AzeroMostEvents>,
-        next_block_to_process_sender: broadcast::Sender<u32>,
         mut next_block_to_process_receiver: broadcast::Receiver<u32>,
         block_seal_sender: mpsc::Sender<u32>,
         circuit_breaker_sender: broadcast::Sender<CircuitBreakerEvent>,
@@ -82,6 +74,7 @@ impl AlephZeroListener {
             azero_contract_address,
             azero_ref_time_limit,
             azero_proof_size_limit,
+            azero_gas_multiplier,
             sync_step,
             ..
         } = &*config;
@@ -93,7 +86,27 @@ impl AlephZeroListener {
             azero_contract_metadata,
             *azero_ref_time_limit,
             *azero_proof_size_limit,
+            *azero_gas_multiplier,
         )?;
+        let contracts = [most_azero.contract.instance()];
+
+        let first_unprocessed_block_number = select! {
+            cb_event = circuit_breaker_receiver.recv() => {
+                warn!(target: "AlephZeroListener", "Exiting before starting due to a circuit breaker event {cb_event:?}");
+                return Ok(cb_event?);
+            },
+
+            block_number = next_block_to_process_receiver.recv() => block_number?,
+        };
+
+        info!(target: "AlephZeroListener", "Processing events from block {first_unprocessed_block_number}");
+
+        // Blocks which are ready are batched together, up to `sync_step` of them.
+        let mut finalized_events = Box::pin(
+            azero_connection
+                .finalized_contract_events(first_unprocessed_block_number, &contracts)
+                .ready_chunks(*sync_step as usize),
+        );
 
         loop {
             debug!(target: "AlephZeroListener", "Ping");
@@ -104,39 +117,27 @@ impl AlephZeroListener {
                     return Ok(cb_event?);
                 },
 
-                Ok (unprocessed_block_number) = next_block_to_process_receiver.recv() => {
-                    // Query for the next unknown finalized block number, if not present we wait for it
-                    let next_finalized_block_number = match get_next_finalized_block_number_azero(
-                        azero_connection.clone(),
-                        unprocessed_block_number,
-                    )
-                        .await {
-                            Ok(number) => number,
-                            Err(AlephZeroListenerError::AlephClient(_)) => {
-                                warn!("Aleph client failed when getting next finalized block number. Opening circuit breaker");
-                                let status = CircuitBreakerEvent::AlephClientError;
-                                circuit_breaker_sender.send(status.clone ())?;
-                                return Ok (status);
-                            },
-                            Err (other) => {
-                                return Err (other);
-                            }
-                        };
-
-                    let to_block = min(
-                        next_finalized_block_number,
-                        unprocessed_block_number + sync_step - 1,
-                    );
+                Some(blocks) = finalized_events.next() => {
+                    let blocks = match blocks.into_iter().collect::<Result<Vec<_>, _>>() {
+                        Ok(blocks) => blocks,
+                        Err(why) => {
+                            warn!(target: "AlephZeroListener", "Azero client failed when fetching finalized events: {why:?}. Opening circuit breaker");
+                            let status = CircuitBreakerEvent::AlephClientError;
+                            circuit_breaker_sender.send(status.clone())?;
+                            return Ok(status);
+                        }
+                    };
+                    let (Some((from_block, _)), Some((to_block, _))) = (blocks.first(), blocks.last()) else {
+                        continue;
+                    };
+                    let (from_block, to_block) = (*from_block, *to_block);
+                    let events: Vec<ContractEvent> = blocks.into_iter().flat_map(|(_, events)| events).collect();
 
                     info!(target: "AlephZeroListener",
                           "Processing events from blocks {} - {}",
-                          unprocessed_block_number, to_block
+                          from_block, to_block
                     );
 
-                    let events = fetch_events_in_block_range(&azero_connection, unprocessed_block_number, to_block, &[&most_azero.contract]).await?;
-                    // Fetch the events in parallel.
-
-
                     let (ack_sender, ack_receiver) = oneshot::channel::<u32>();
                     event_batch_ack_receiver.push_back(ack_receiver);
 
@@ -149,17 +150,15 @@ impl AlephZeroListener {
                         Ok(_) = azero_events_sender
                             .send(AzeroMostEvents {
                                 events: events.clone(),
-                                from_block: unprocessed_block_number,
+                                from_block,
                                 to_block,
                                 ack: ack_sender
                             }) => {
                                 info!(target: "AlephZeroListener", "Sending a batch of {} events", &events.len());
                             },
                     }
-
-                    info!(target: "AlephZeroListener", "Sending {} as the next unprocessed block number", to_block + 1);
-                    next_block_to_process_sender.send(to_block + 1)?;
                 },
+
                 Some(processed_block_res) = event_batch_ack_receiver.next() => {
                     let processed_block = processed_block_res?;
                     info!("Marking all events up to block {processed_block} as handled");
@@ -170,55 +169,6 @@ impl AlephZeroListener {
     }
 }
 
-async fn fetch_events_in_block_range(
-    azero_connection: &Arc<Client>,
-    from_block: u32,
-    to_block: u32,
-    contracts: &[&ContractInstance],
-) -> Result<Vec<ContractEvent>, AlephZeroListenerError> {
-    let mut event_fetching_tasks = Vec::new();
-
-    for block_number in from_block..=to_block {
-        let azero_connection = azero_connection.clone();
-
-        event_fetching_tasks.push(async move {
-            azero_connection
-                .fetch_events_from_contracts(block_number, contracts)
-                .await
-        });
-    }
-
-    let events = join_all(event_fetching_tasks)
-        .await
-        .into_iter()
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter()
-        .flatten()
-        .collect();
-
-    Ok(events)
-}
-
-async fn get_next_finalized_block_number_azero(
-    azero_connection: Arc<AzeroWsConnection>,
-    not_older_than: u32,
-) -> Result<u32, AlephZeroListenerError> {
-    loop {
-        let hash = azero_connection.get_finalized_block_hash().await?;
-        let best_finalized_block_number = azero_connection
-            .get_block_number(hash)
-            .await?
-            .expect("Finalized block has a number.");
-
-        if best_finalized_block_number >= not_older_than {
-            return Ok(best_finalized_block_number);
-        }
-
-        // If we are up to date, we can sleep for a longer time.
-        sleep(Duration::from_secs(10 * ALEPH_BLOCK_PROD_TIME_SEC)).await;
-    }
-}
-
 #[derive(Debug, Error)]
 #[error(transparent)]
 #[non_exhaustive]
@@ -233,6 +183,9 @@ pub enum AlephZeroHaltedListenerError {
     BroadcastReceive(#[from] broadcast::error::RecvError),
 }
 
+/// Opens the circuit breaker once `Most` is halted. Pauses of single pairs don't stop the guardians:
+/// `Most` makes no new requests of a paused pair and parks its incoming requests once they reach
+/// the quorum, so the votes keep succeeding.
 #[derive(Copy, Clone)]
 pub struct AlephZeroHaltedListener;
 
@@ -248,6 +201,7 @@ impl AlephZeroHaltedListener {
             azero_contract_address,
             azero_ref_time_limit,
             azero_proof_size_limit,
+            azero_gas_multiplier,
             ..
         } = &*config;
 
@@ -256,6 +210,7 @@ impl AlephZeroHaltedListener {
             azero_contract_metadata,
             *azero_ref_time_limit,
             *azero_proof_size_limit,
+            *azero_gas_multiplier,
         )?;
 
         info!(
@@ -122,13 +122,22 @@ enum CircuitBreakerEvent {
     EthConnectionError,
 }
 
+fn signer_client_config(config: &Config) -> signer_client::ClientConfig {
+    signer_client::ClientConfig {
+        request_timeout: Duration::from_secs(config.signer_request_timeout_sec),
+        key: config.signer_key_id.clone(),
+        ..Default::default()
+    }
+}
+
 async fn create_azero_connections(
     config: &Config,
 ) -> Result<(Arc<AzeroWsConnection>, Arc<ClientWithSigner<AzeroSigner>>), RelayerError> {
     let azero_connection = azero::init(&config.azero_node_wss_url).await;
     let signer = if let Some(cid) = config.signer_cid {
         info!("[AlephZero] Creating signed connection using a Signer client");
-        let client = AzeroSignerClient::new(cid, config.signer_port).await?;
+        let client =
+            AzeroSignerClient::new(cid, config.signer_port, signer_client_config(config)).await?;
         AzeroSigner::Signer(client)
     } else if config.dev {
         let azero_seed = "//".to_owned() + &config.dev_account_index.to_string();
@@ -157,7 +166,13 @@ async fn create_eth_connections(
 ) -> Result<(Arc<EthConnection>, Arc<SignedEthConnection>), EthConnectionError> {
     let eth_signed_connection = if let Some(cid) = config.signer_cid {
         info!("Creating signed connection using a Signer client");
-        eth::with_signer(persistent_eth_connection, cid, config.signer_port).await?
+        eth::with_signer(
+            persistent_eth_connection,
+            cid,
+            config.signer_port,
+            signer_client_config(config),
+        )
+        .await?
     } else if config.dev {
         let wallet =
             // use the default development mnemonic
@@ -199,6 +214,7 @@ async fn set_payout_account(
         &config.azero_contract_metadata,
         config.azero_ref_time_limit,
         config.azero_proof_size_limit,
+        config.azero_gas_multiplier,
     )?;
 
     let current_committee_id = most_azero
@@ -411,7 +427,6 @@ async fn run_relayer(
             Arc::clone(&config),
             Arc::clone(&azero_connection),
             azero_events_sender,
-            azero_block_number_sender.clone(),
             azero_block_number_receiver,
             azero_block_seal_sender.clone(),
             circuit_breaker_sender.clone(),
@@ -424,6 +439,7 @@ async fn run_relayer(
         AlephZeroEventsHandler::run(
             Arc::clone(&config),
             Arc::clone(&eth_signed_connection),
+            Arc::clone(&azero_connection),
             azero_events_receiver,
             circuit_breaker_sender.clone(),
             aleph_events_handler_circuit_breaker_receiver,
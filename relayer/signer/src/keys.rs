@@ -0,0 +1,195 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use ethers::{
+    signers::{LocalWallet, Signer},
+    types::U64,
+};
+use serde::Deserialize;
+use signer_client::{KeyId, KeyInfo, KeyPolicy, DEFAULT_KEY_ID};
+use subxt::ext::sp_core::{sr25519::Pair as KeyPair, Pair};
+
+use crate::Error;
+
+/// A key as described in the keys file. Both parts are optional, so that e.g. a key used only for
+/// updating the gas oracle doesn't need an Aleph Zero counterpart.
+#[derive(Deserialize)]
+struct KeyConfig {
+    azero_key: Option<String>,
+    eth_key: Option<String>,
+    #[serde(default)]
+    policy: KeyPolicy,
+}
+
+struct Key {
+    azero: Option<KeyPair>,
+    eth: Option<LocalWallet>,
+    policy: KeyPolicy,
+}
+
+impl Key {
+    fn new(
+        azero_key: Option<String>,
+        eth_key: Option<String>,
+        policy: KeyPolicy,
+    ) -> Result<Self, Error> {
+        let azero = azero_key
+            .map(|key| KeyPair::from_string(&key, None))
+            .transpose()?;
+        let eth = eth_key
+            .map(|key| -> Result<_, Error> { Ok(LocalWallet::from_bytes(&hex::decode(key)?)?) })
+            .transpose()?;
+
+        Ok(Self { azero, eth, policy })
+    }
+
+    fn info(&self, id: &str) -> KeyInfo {
+        KeyInfo {
+            id: id.to_string(),
+            azero_account_id: self.azero.as_ref().map(|key| key.public().into()),
+            eth_address: self.eth.as_ref().map(|wallet| wallet.address()),
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+/// Reasons for refusing a command. These are reported back to the client rather than closing the
+/// connection.
+#[derive(thiserror::Error, Debug)]
+pub enum Rejection {
+    #[error("Unknown key: {0}")]
+    UnknownKey(KeyId),
+
+    #[error("Key {0} has no Azero part")]
+    NoAzeroKey(KeyId),
+
+    #[error("Key {0} has no ETH part")]
+    NoEthKey(KeyId),
+
+    #[error("Key {key} is not allowed to {operation}")]
+    NotAllowed { key: KeyId, operation: &'static str },
+
+    #[error("Key {key} is not allowed to sign transactions for chain {chain_id}")]
+    ChainIdNotAllowed { key: KeyId, chain_id: U64 },
+
+    #[error("Rate limit exceeded for {0}")]
+    RateLimited(&'static str),
+
+    #[error("Too many distinct nonces signed recently by key {key} for chain {chain_id}")]
+    TooManyEthNonces { key: KeyId, chain_id: U64 },
+
+    #[error("Signer is locked down")]
+    LockedDown,
+}
+
+#[derive(Default)]
+pub struct Keys(BTreeMap<KeyId, Key>);
+
+impl Keys {
+    /// Loads the keys file (if any) and adds the keys passed on the command line as the default
+    /// key, allowed to sign everything.
+    pub fn load(
+        keys_file: Option<&Path>,
+        azero_key: Option<String>,
+        eth_key: Option<String>,
+    ) -> Result<Self, Error> {
+        let mut keys = Keys::default();
+
+        if let Some(path) = keys_file {
+            let configs: BTreeMap<KeyId, KeyConfig> = serde_json::from_slice(&fs::read(path)?)?;
+            for (id, config) in configs {
+                keys.insert(
+                    id,
+                    Key::new(config.azero_key, config.eth_key, config.policy)?,
+                )?;
+            }
+        }
+
+        if azero_key.is_some() || eth_key.is_some() {
+            keys.insert(
+                DEFAULT_KEY_ID.to_string(),
+                Key::new(azero_key, eth_key, KeyPolicy::default())?,
+            )?;
+        }
+
+        if keys.0.is_empty() {
+            return Err(Error::NoKeys);
+        }
+
+        Ok(keys)
+    }
+
+    fn insert(&mut self, id: KeyId, key: Key) -> Result<(), Error> {
+        if self.0.contains_key(&id) {
+            return Err(Error::DuplicateKey(id));
+        }
+        self.0.insert(id, key);
+
+        Ok(())
+    }
+
+    pub fn infos(&self) -> Vec<KeyInfo> {
+        self.0.iter().map(|(id, key)| key.info(id)).collect()
+    }
+
+    /// Looks up the key named in a command, falling back to the default key.
+    fn get(&self, id: Option<KeyId>) -> Result<(KeyId, &Key), Rejection> {
+        let id = id.unwrap_or_else(|| DEFAULT_KEY_ID.to_string());
+
+        match self.0.get(&id) {
+            Some(key) => Ok((id, key)),
+            None => Err(Rejection::UnknownKey(id)),
+        }
+    }
+
+    pub fn azero(&self, id: Option<KeyId>) -> Result<&KeyPair, Rejection> {
+        let (id, key) = self.get(id)?;
+        key.azero.as_ref().ok_or(Rejection::NoAzeroKey(id))
+    }
+
+    pub fn eth(&self, id: Option<KeyId>) -> Result<&LocalWallet, Rejection> {
+        let (id, key) = self.get(id)?;
+        key.eth.as_ref().ok_or(Rejection::NoEthKey(id))
+    }
+
+    pub fn azero_signer(&self, id: Option<KeyId>) -> Result<&KeyPair, Rejection> {
+        let (id, key) = self.get(id)?;
+        ensure_allowed(key.policy.sign_azero, &id, "sign Azero payloads")?;
+
+        key.azero.as_ref().ok_or(Rejection::NoAzeroKey(id))
+    }
+
+    pub fn eth_hash_signer(&self, id: Option<KeyId>) -> Result<&LocalWallet, Rejection> {
+        let (id, key) = self.get(id)?;
+        ensure_allowed(key.policy.sign_eth_hash, &id, "sign ETH hashes")?;
+
+        key.eth.as_ref().ok_or(Rejection::NoEthKey(id))
+    }
+
+    pub fn eth_tx_signer(
+        &self,
+        id: Option<KeyId>,
+        chain_id: U64,
+    ) -> Result<&LocalWallet, Rejection> {
+        let (id, key) = self.get(id)?;
+        ensure_allowed(key.policy.sign_eth_tx, &id, "sign ETH transactions")?;
+
+        if let Some(chain_ids) = &key.policy.eth_chain_ids {
+            if !chain_ids.contains(&chain_id) {
+                return Err(Rejection::ChainIdNotAllowed { key: id, chain_id });
+            }
+        }
+
+        key.eth.as_ref().ok_or(Rejection::NoEthKey(id))
+    }
+}
+
+fn ensure_allowed(allowed: bool, key: &str, operation: &'static str) -> Result<(), Rejection> {
+    if !allowed {
+        return Err(Rejection::NotAllowed {
+            key: key.to_string(),
+            operation,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime},
+};
+
+use ethers::types::{U256, U64};
+use log::{error, info, warn};
+use signer_client::KeyId;
+
+use crate::{keys::Rejection, Error};
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct LimitArguments {
+    /// Maximum number of Azero payloads signed per minute
+    #[clap(long)]
+    pub max_azero_signatures_per_minute: Option<usize>,
+
+    /// Maximum number of ETH hashes signed per minute
+    #[clap(long)]
+    pub max_eth_hash_signatures_per_minute: Option<usize>,
+
+    /// Maximum number of ETH transactions signed per minute
+    #[clap(long)]
+    pub max_eth_tx_signatures_per_minute: Option<usize>,
+
+    /// Maximum number of distinct nonces signed for, per key and chain, within the nonce window.
+    /// Re-signing a transaction with an already seen nonce (e.g. to bump the gas price) is free.
+    #[clap(long)]
+    pub max_eth_nonces_per_window: Option<usize>,
+
+    #[clap(long, default_value = "600")]
+    pub eth_nonce_window_sec: u64,
+
+    /// Locks the signer down once this many commands are rejected within the violation window.
+    /// `signer_tester conformance` provokes rejections on purpose, so it must not be run against a
+    /// signer with a lockdown threshold.
+    #[clap(long)]
+    pub lockdown_after_violations: Option<usize>,
+
+    #[clap(long, default_value = "3600")]
+    pub violation_window_sec: u64,
+
+    /// Records the lockdown, so that it survives restarts
+    #[clap(long, default_value = "signer.lockdown")]
+    pub lockdown_file: PathBuf,
+
+    /// Clears a lockdown recorded by a previous run. Only to be used by an operator after
+    /// investigating why the signer was locked down.
+    #[clap(long)]
+    pub clear_lockdown: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SignatureKind {
+    Azero,
+    EthHash,
+    EthTx,
+}
+
+impl SignatureKind {
+    fn name(self) -> &'static str {
+        match self {
+            SignatureKind::Azero => "Azero signatures",
+            SignatureKind::EthHash => "ETH hash signatures",
+            SignatureKind::EthTx => "ETH transaction signatures",
+        }
+    }
+}
+
+/// Timestamps of events that happened within the last `window`.
+#[derive(Default)]
+struct SlidingWindow(VecDeque<Instant>);
+
+impl SlidingWindow {
+    fn prune(&mut self, now: Instant, window: Duration) {
+        while let Some(&oldest) = self.0.front() {
+            if now.duration_since(oldest) < window {
+                break;
+            }
+            self.0.pop_front();
+        }
+    }
+
+    /// Whether there were fewer than `max` events within the window.
+    fn has_room(&mut self, now: Instant, window: Duration, max: usize) -> bool {
+        self.prune(now, window);
+        self.0.len() < max
+    }
+
+    /// Records an event, unless there already were `max` of them within the window.
+    fn try_record(&mut self, now: Instant, window: Duration, max: usize) -> bool {
+        if !self.has_room(now, window, max) {
+            return false;
+        }
+        self.0.push_back(now);
+
+        true
+    }
+}
+
+#[derive(Default)]
+struct State {
+    locked_down: bool,
+    azero_signatures: SlidingWindow,
+    eth_hash_signatures: SlidingWindow,
+    eth_tx_signatures: SlidingWindow,
+    /// Nonces signed for recently, with the time they were first seen.
+    eth_nonces: HashMap<(KeyId, U64), VecDeque<(Instant, U256)>>,
+    violations: SlidingWindow,
+}
+
+/// Rate limits and the lockdown state, shared by all connections.
+pub struct Limits {
+    args: LimitArguments,
+    state: Mutex<State>,
+}
+
+impl Limits {
+    pub fn new(args: LimitArguments) -> Result<Self, Error> {
+        if args.clear_lockdown && args.lockdown_file.exists() {
+            fs::remove_file(&args.lockdown_file)?;
+            info!("Lockdown cleared by the operator");
+        }
+
+        let locked_down = args.lockdown_file.exists();
+        if locked_down {
+            warn!(
+                "Signer is locked down (see {:?}), all commands will be refused until it is \
+                 restarted with --clear-lockdown",
+                args.lockdown_file
+            );
+        }
+
+        Ok(Self {
+            args,
+            state: Mutex::new(State {
+                locked_down,
+                ..Default::default()
+            }),
+        })
+    }
+
+    pub fn ensure_not_locked_down(&self) -> Result<(), Rejection> {
+        if self.state().locked_down {
+            return Err(Rejection::LockedDown);
+        }
+
+        Ok(())
+    }
+
+    /// Counts a signature of the given kind against its per-minute limit.
+    pub fn record_signature(&self, kind: SignatureKind) -> Result<(), Rejection> {
+        let max = match kind {
+            SignatureKind::Azero => self.args.max_azero_signatures_per_minute,
+            SignatureKind::EthHash => self.args.max_eth_hash_signatures_per_minute,
+            SignatureKind::EthTx => self.args.max_eth_tx_signatures_per_minute,
+        };
+        let Some(max) = max else {
+            return Ok(());
+        };
+
+        let mut state = self.state();
+        let window = match kind {
+            SignatureKind::Azero => &mut state.azero_signatures,
+            SignatureKind::EthHash => &mut state.eth_hash_signatures,
+            SignatureKind::EthTx => &mut state.eth_tx_signatures,
+        };
+
+        if !window.try_record(Instant::now(), Duration::from_secs(60), max) {
+            return Err(Rejection::RateLimited(kind.name()));
+        }
+
+        Ok(())
+    }
+
+    /// Counts an ETH transaction against the signatures limit and its nonce against the distinct
+    /// nonces limit of the key and chain. Nothing is counted unless both limits allow it.
+    pub fn record_eth_tx(&self, key: &str, chain_id: U64, nonce: U256) -> Result<(), Rejection> {
+        let now = Instant::now();
+        let mut state = self.state();
+
+        let max_signatures = self.args.max_eth_tx_signatures_per_minute;
+        if let Some(max) = max_signatures {
+            if !state
+                .eth_tx_signatures
+                .has_room(now, Duration::from_secs(60), max)
+            {
+                return Err(Rejection::RateLimited(SignatureKind::EthTx.name()));
+            }
+        }
+
+        self.record_eth_nonce(&mut state, now, key, chain_id, nonce)?;
+        if max_signatures.is_some() {
+            state.eth_tx_signatures.0.push_back(now);
+        }
+
+        Ok(())
+    }
+
+    /// Counts the nonce of a transaction against the distinct nonces limit of the key and chain.
+    fn record_eth_nonce(
+        &self,
+        state: &mut State,
+        now: Instant,
+        key: &str,
+        chain_id: U64,
+        nonce: U256,
+    ) -> Result<(), Rejection> {
+        let Some(max) = self.args.max_eth_nonces_per_window else {
+            return Ok(());
+        };
+
+        let window = Duration::from_secs(self.args.eth_nonce_window_sec);
+        let nonces = state
+            .eth_nonces
+            .entry((key.to_string(), chain_id))
+            .or_default();
+
+        while let Some(&(first_seen, _)) = nonces.front() {
+            if now.duration_since(first_seen) < window {
+                break;
+            }
+            nonces.pop_front();
+        }
+
+        if nonces.iter().any(|&(_, seen)| seen == nonce) {
+            return Ok(());
+        }
+        if nonces.len() >= max {
+            return Err(Rejection::TooManyEthNonces {
+                key: key.to_string(),
+                chain_id,
+            });
+        }
+        nonces.push_back((now, nonce));
+
+        Ok(())
+    }
+
+    /// Counts a rejected command, locking the signer down if there were too many of them.
+    pub fn record_violation(&self, rejection: &Rejection) {
+        let Some(max) = self.args.lockdown_after_violations else {
+            return;
+        };
+
+        let mut state = self.state();
+        if state.locked_down {
+            return;
+        }
+
+        let window = Duration::from_secs(self.args.violation_window_sec);
+        if state
+            .violations
+            .try_record(Instant::now(), window, max.saturating_sub(1))
+        {
+            return;
+        }
+
+        state.locked_down = true;
+        error!(
+            "Signer locked down after {} violations, the last one being: {}",
+            max, rejection
+        );
+
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let record = format!("locked down at {timestamp} after: {rejection}\n");
+        if let Err(why) = fs::write(&self.args.lockdown_file, record) {
+            error!(
+                "Failed to record the lockdown in {:?}, it will not survive a restart: {:?}",
+                self.args.lockdown_file, why
+            );
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        // The state stays consistent even if a thread panicked while holding the lock.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
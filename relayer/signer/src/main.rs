@@ -1,27 +1,47 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
 use clap::Parser;
-use ethers::{
-    signers::{LocalWallet, Signer},
-    types::Address,
-};
-use log::info;
-use signer_client::{Client, Command, Response};
-use subxt::ext::{
-    sp_core::{crypto::SecretStringError, sr25519::Pair as KeyPair, Pair},
-    sp_runtime::AccountId32,
+use ethers::signers::Signer;
+use keys::{Keys, Rejection};
+use limits::{LimitArguments, Limits, SignatureKind};
+use log::{info, warn};
+use replies::Replies;
+use signer_client::{
+    Command, Connection, KeyId, Reply, Request, RequestId, Response, DEFAULT_KEY_ID,
 };
+use subxt::ext::sp_core::{crypto::SecretStringError, Pair};
 use tokio::spawn;
 use tokio_vsock::{VsockAddr, VsockListener, VMADDR_CID_ANY};
 
+mod keys;
+mod limits;
+mod replies;
+
 #[derive(Parser)]
 struct ServerArguments {
     #[clap(short, long, default_value = "1234")]
     port: u32,
 
+    /// Azero part of the default key
     #[clap(short, long)]
-    azero_key: String,
+    azero_key: Option<String>,
 
+    /// ETH part of the default key
     #[clap(short, long)]
-    eth_key: String,
+    eth_key: Option<String>,
+
+    /// JSON file mapping key ids to their `azero_key`, `eth_key` and `policy`
+    #[clap(short, long)]
+    keys_file: Option<PathBuf>,
+
+    #[clap(flatten)]
+    limits: LimitArguments,
+
+    /// How long the response to a request is remembered, so that a client resending it after a
+    /// reconnection gets the same response instead of a second signature. Has to exceed the
+    /// request timeout of the clients.
+    #[clap(long, default_value = "600")]
+    reply_retention_sec: u64,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -43,6 +63,15 @@ enum Error {
 
     #[error("Hex decoding error: {0}")]
     Hex(#[from] hex::FromHexError),
+
+    #[error("No keys configured")]
+    NoKeys,
+
+    #[error("Key {0} configured more than once")]
+    DuplicateKey(KeyId),
+
+    #[error("Rejected: {0}")]
+    Rejected(#[from] Rejection),
 }
 
 #[tokio::main]
@@ -50,11 +79,18 @@ async fn main() -> Result<(), Error> {
     env_logger::init();
 
     let args = ServerArguments::parse();
-    let mut server = Server::new(args.azero_key, args.eth_key, args.port)?;
+    let keys = Keys::load(args.keys_file.as_deref(), args.azero_key, args.eth_key)?;
+    let limits = Limits::new(args.limits)?;
+    let replies = Replies::new(Duration::from_secs(args.reply_retention_sec));
+    let mut server = Server::new(keys, limits, replies, args.port)?;
 
     info!("Server listening on: {:?}", server.local_addr()?);
-    info!("Azero account ID: {:?}", server.azero_account_id());
-    info!("ETH address: {:?}", server.eth_address());
+    for key in server.keys.infos() {
+        info!(
+            "Key {}: Azero account ID: {:?}, ETH address: {:?}, policy: {:?}",
+            key.id, key.azero_account_id, key.eth_address, key.policy
+        );
+    }
 
     server.accept_loop().await?;
 
@@ -63,45 +99,37 @@ async fn main() -> Result<(), Error> {
 
 struct Server {
     listener: VsockListener,
-    azero_key: KeyPair,
-    eth_wallet: LocalWallet,
+    keys: Arc<Keys>,
+    limits: Arc<Limits>,
+    replies: Arc<Replies>,
 }
 
 impl Server {
-    fn new(azero_key: String, eth_key: String, port: u32) -> Result<Self, Error> {
-        let azero_key = KeyPair::from_string(&azero_key, None)?;
+    fn new(keys: Keys, limits: Limits, replies: Replies, port: u32) -> Result<Self, Error> {
         let address = VsockAddr::new(VMADDR_CID_ANY, port);
         let listener = VsockListener::bind(address)?;
-        let eth_key = hex::decode(eth_key)?;
-        let eth_wallet = LocalWallet::from_bytes(&eth_key)?;
 
         Ok(Self {
             listener,
-            azero_key,
-            eth_wallet,
+            keys: Arc::new(keys),
+            limits: Arc::new(limits),
+            replies: Arc::new(replies),
         })
     }
 
-    fn azero_account_id(&self) -> AccountId32 {
-        self.azero_key.public().into()
-    }
-
-    fn eth_address(&self) -> Address {
-        self.eth_wallet.address()
-    }
-
     fn local_addr(&self) -> Result<VsockAddr, Error> {
         Ok(self.listener.local_addr()?)
     }
 
     async fn accept_one(&mut self) -> Result<(), Error> {
-        let (client, _) = self.listener.accept().await?;
-        let client = Client::from(client);
+        let (connection, _) = self.listener.accept().await?;
+        let connection = Connection::from(connection);
 
         spawn(handle_client(
-            client,
-            self.azero_key.clone(),
-            self.eth_wallet.clone(),
+            connection,
+            self.keys.clone(),
+            self.limits.clone(),
+            self.replies.clone(),
         ));
 
         Ok(())
@@ -114,75 +142,123 @@ impl Server {
     }
 }
 
-async fn handle_client(client: Client, azero_key: KeyPair, eth_wallet: LocalWallet) {
-    let result = do_handle_client(client, &azero_key, &eth_wallet).await;
+async fn handle_client(
+    connection: Connection,
+    keys: Arc<Keys>,
+    limits: Arc<Limits>,
+    replies: Arc<Replies>,
+) {
+    let result = do_handle_client(connection, &keys, &limits, &replies).await;
     info!("Client disconnected: {:?}", result);
 }
 
 async fn do_handle_client(
-    mut client: Client,
-    azero_key: &KeyPair,
-    eth_wallet: &LocalWallet,
+    mut connection: Connection,
+    keys: &Keys,
+    limits: &Limits,
+    replies: &Replies,
 ) -> Result<(), Error> {
     loop {
-        let command = client.recv().await?;
-        info!("Received command: {:?}", command);
+        let Request {
+            id,
+            session,
+            command,
+        } = connection.recv().await?;
+        info!("Received command {}: {:?}", id, command);
+
+        // A resent request gets its earlier response, so its rejection isn't recorded again either.
+        let response =
+            replies.get_or_respond(session, id, || respond(keys, limits, id, command))?;
+
+        connection.send(&Reply { id, response }).await?;
+    }
+}
 
-        match command {
-            Command::Ping => {
-                client.send(&Response::Pong).await?;
+fn respond(
+    keys: &Keys,
+    limits: &Limits,
+    id: RequestId,
+    command: Command,
+) -> Result<Response, Error> {
+    match handle_command(keys, limits, command) {
+        Ok(response) => Ok(response),
+        Err(Error::Rejected(why)) => {
+            warn!("Rejected command {}: {}", id, why);
+            if !matches!(why, Rejection::LockedDown) {
+                limits.record_violation(&why);
             }
+            Ok(Response::Rejected {
+                reason: why.to_string(),
+            })
+        }
+        Err(why) => Err(why),
+    }
+}
 
-            Command::AccountIdAzero => {
-                let account_id = azero_key.public().into();
-                client
-                    .send(&Response::AccountIdAzero { account_id })
-                    .await?;
-            }
+fn handle_command(keys: &Keys, limits: &Limits, command: Command) -> Result<Response, Error> {
+    limits.ensure_not_locked_down()?;
 
-            Command::SignAzero { payload } => {
-                let signature = azero_key.sign(&payload);
-                let signature = subxt::ext::sp_runtime::MultiSignature::Sr25519(signature);
+    let response = match command {
+        Command::Ping => Response::Pong,
 
-                client
-                    .send(&Response::SignedAzero { payload, signature })
-                    .await?;
-            }
+        Command::ListKeys => Response::Keys { keys: keys.infos() },
 
-            Command::EthAddress => {
-                let address = eth_wallet.address();
-                client.send(&Response::EthAddress { address }).await?;
-            }
+        Command::AccountIdAzero { key } => {
+            let account_id = keys.azero(key)?.public().into();
+            Response::AccountIdAzero { account_id }
+        }
 
-            Command::SignEthHash { hash } => {
-                let signature = eth_wallet.sign_hash(hash)?;
-                client
-                    .send(&Response::SignedEthHash { hash, signature })
-                    .await?;
-            }
+        Command::SignAzero { payload, key } => {
+            let azero_key = keys.azero_signer(key)?;
+            limits.record_signature(SignatureKind::Azero)?;
+            let signature = azero_key.sign(&payload);
+            let signature = subxt::ext::sp_runtime::MultiSignature::Sr25519(signature);
+
+            Response::SignedAzero { payload, signature }
+        }
+
+        Command::EthAddress { key } => {
+            let address = keys.eth(key)?.address();
+            Response::EthAddress { address }
+        }
 
-            Command::SignEthTx { mut tx, chain_id } => {
-                tx.set_chain_id(chain_id);
-                let signature = eth_wallet.sign_transaction_sync(&tx)?;
-                client
-                    .send(&Response::SignedEthTx {
-                        tx,
-                        chain_id,
-                        signature,
-                    })
-                    .await?;
+        Command::SignEthHash { hash, key } => {
+            let wallet = keys.eth_hash_signer(key)?;
+            limits.record_signature(SignatureKind::EthHash)?;
+            let signature = wallet.sign_hash(hash)?;
+            Response::SignedEthHash { hash, signature }
+        }
+
+        Command::SignEthTx {
+            mut tx,
+            chain_id,
+            key,
+        } => {
+            let key = key.unwrap_or_else(|| DEFAULT_KEY_ID.to_string());
+            let wallet = keys.eth_tx_signer(Some(key.clone()), chain_id)?;
+            limits.record_eth_tx(&key, chain_id, tx.nonce().copied().unwrap_or_default())?;
+            tx.set_chain_id(chain_id);
+            let signature = wallet.sign_transaction_sync(&tx)?;
+
+            Response::SignedEthTx {
+                tx,
+                chain_id,
+                signature,
             }
         }
-    }
+    };
+
+    Ok(response)
 }
 
 #[cfg(test)]
 mod test {
-    use std::{env, str::FromStr};
+    use std::{env, fs, str::FromStr};
 
     use assert2::{assert, let_assert};
     use ethers::{addressbook::Address, types::transaction::eip2718::TypedTransaction};
     use serial_test::serial;
+    use signer_client::{Client, KeyPolicy, DEFAULT_KEY_ID};
     use subxt::ext::sp_runtime::traits::Verify;
     use vsock::VMADDR_CID_HOST;
 
@@ -192,14 +268,17 @@ mod test {
     const ETH_PRIVATE_KEY: &str =
         "58039a48427a62f77e5562d7f565d10595d92abdd4813233607ec2ac5ac4b9de";
     const ETH_MAINNET_CHAIN_ID: u64 = 1;
+    const ORACLE_KEY_ID: &str = "oracle";
+    const ORACLE_ETH_PRIVATE_KEY: &str =
+        "f0ecd1edf6c8bd1249e3e89935c433f891b30571b22cacf22bae0cd974f6c349";
+    const ORACLE_CHAIN_ID: u64 = 1337;
 
     #[tokio::test]
     #[serial]
     async fn test_ping() {
-        let mut client = connect().await;
+        let client = connect().await;
 
-        client.send(&Command::Ping).await.unwrap();
-        let response: Response = client.recv().await.unwrap();
+        let response = client.call(Command::Ping).await.unwrap();
 
         assert!(matches!(response, Response::Pong));
     }
@@ -207,10 +286,12 @@ mod test {
     #[tokio::test]
     #[serial]
     async fn test_account_id_azero() {
-        let mut client = connect().await;
+        let client = connect().await;
 
-        client.send(&Command::AccountIdAzero).await.unwrap();
-        let response: Response = client.recv().await.unwrap();
+        let response = client
+            .call(Command::AccountIdAzero { key: None })
+            .await
+            .unwrap();
 
         let_assert!(Response::AccountIdAzero { account_id } = response);
         assert!(account_id.to_string() == "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY");
@@ -219,16 +300,16 @@ mod test {
     #[tokio::test]
     #[serial]
     async fn test_sign_azero() {
-        let mut client = connect().await;
+        let client = connect().await;
         let payload = b"Hello, world!".to_vec();
 
-        client
-            .send(&Command::SignAzero {
+        let response = client
+            .call(Command::SignAzero {
                 payload: payload.clone(),
+                key: None,
             })
             .await
             .unwrap();
-        let response: Response = client.recv().await.unwrap();
 
         let_assert!(
             Response::SignedAzero {
@@ -244,7 +325,7 @@ mod test {
     #[tokio::test]
     #[serial]
     async fn test_eth_address() {
-        let mut client = connect().await;
+        let client = connect().await;
 
         let address = client.eth_address().await.unwrap();
 
@@ -254,7 +335,7 @@ mod test {
     #[tokio::test]
     #[serial]
     async fn test_sign_eth_hash() {
-        let mut client = connect().await;
+        let client = connect().await;
         let payload = b"Hello, world!".to_vec();
         let hash = ethers::utils::keccak256(payload).into();
 
@@ -267,7 +348,7 @@ mod test {
     #[tokio::test]
     #[serial]
     async fn test_sign_eth_tx_without_chain_id() {
-        let mut client = connect().await;
+        let client = connect().await;
         let mut tx = TypedTransaction::Eip1559(Default::default());
 
         let signature = client.sign_eth_tx(&tx).await.unwrap();
@@ -282,7 +363,7 @@ mod test {
     #[tokio::test]
     #[serial]
     async fn test_sign_eth_tx_with_chain_id() {
-        let mut client = connect().await;
+        let client = connect().await;
         let mut tx = TypedTransaction::Eip1559(Default::default());
         tx.set_chain_id(1337);
 
@@ -293,9 +374,285 @@ mod test {
         assert!(signature.verify(hash, address).is_ok())
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_pipelined_requests() {
+        let client = connect().await;
+        let payloads: Vec<_> = (0..16u8).map(|i| vec![i; 32]).collect();
+
+        let signatures = futures::future::try_join_all(
+            payloads.iter().map(|payload| client.sign_azero(payload)),
+        )
+        .await
+        .unwrap();
+
+        let account_id = client.azero_account_id().await.unwrap();
+        for (payload, signature) in payloads.iter().zip(signatures) {
+            assert!(signature.verify(&payload[..], &account_id));
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_list_keys() {
+        let client = connect().await;
+
+        let keys = client.list_keys().await.unwrap();
+
+        let ids: Vec<_> = keys.iter().map(|key| key.id.as_str()).collect();
+        assert!(ids == [DEFAULT_KEY_ID, ORACLE_KEY_ID]);
+        assert!(keys[0].azero_account_id == Some(client.azero_account_id().await.unwrap()));
+        assert!(keys[0].eth_address == Some(Address::from_str(ETH_PUBLIC_ADDRESS).unwrap()));
+        assert!(keys[1].azero_account_id.is_none());
+        assert!(keys[1].policy == oracle_policy());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sign_eth_tx_with_named_key() {
+        let client = connect().await;
+        let mut tx = TypedTransaction::Eip1559(Default::default());
+        tx.set_chain_id(ORACLE_CHAIN_ID);
+
+        let response = client
+            .call(Command::SignEthTx {
+                tx: tx.clone(),
+                chain_id: ORACLE_CHAIN_ID.into(),
+                key: Some(ORACLE_KEY_ID.to_string()),
+            })
+            .await
+            .unwrap();
+        let address = client
+            .call(Command::EthAddress {
+                key: Some(ORACLE_KEY_ID.to_string()),
+            })
+            .await
+            .unwrap();
+
+        let_assert!(Response::SignedEthTx { signature, .. } = response);
+        let_assert!(Response::EthAddress { address } = address);
+        assert!(address != Address::from_str(ETH_PUBLIC_ADDRESS).unwrap());
+        assert!(signature.verify(tx.sighash(), address).is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_policy_rejections() {
+        let client = connect().await;
+        let oracle = Some(ORACLE_KEY_ID.to_string());
+
+        let rejected = [
+            Command::SignAzero {
+                payload: vec![],
+                key: oracle.clone(),
+            },
+            Command::SignEthHash {
+                hash: Default::default(),
+                key: oracle.clone(),
+            },
+            Command::SignEthTx {
+                tx: TypedTransaction::Eip1559(Default::default()),
+                chain_id: ETH_MAINNET_CHAIN_ID.into(),
+                key: oracle.clone(),
+            },
+            Command::AccountIdAzero { key: oracle },
+            Command::EthAddress {
+                key: Some("unknown".to_string()),
+            },
+        ];
+
+        for command in rejected {
+            let_assert!(Err(signer_client::Error::Rejected(_)) = client.call(command).await);
+        }
+
+        // The connection stays usable after a rejection
+        assert!(client.call(Command::Ping).await.unwrap() == Response::Pong);
+    }
+
+    fn oracle_policy() -> KeyPolicy {
+        KeyPolicy {
+            sign_azero: false,
+            sign_eth_hash: false,
+            sign_eth_tx: true,
+            eth_chain_ids: Some(vec![ORACLE_CHAIN_ID.into()]),
+        }
+    }
+
+    fn keys() -> Keys {
+        let keys_file = env::temp_dir().join("signer_test_keys.json");
+        let config = serde_json::json!({
+            ORACLE_KEY_ID: {
+                "eth_key": ORACLE_ETH_PRIVATE_KEY,
+                "policy": oracle_policy(),
+            }
+        });
+        fs::write(&keys_file, config.to_string()).unwrap();
+
+        Keys::load(
+            Some(keys_file.as_path()),
+            Some("//Alice".to_string()),
+            Some(ETH_PRIVATE_KEY.to_string()),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_signature_rate_limit() {
+        let client = connect_with_limits(LimitArguments {
+            max_azero_signatures_per_minute: Some(2),
+            ..limit_arguments()
+        })
+        .await;
+
+        assert!(client.sign_azero(b"1").await.is_ok());
+        assert!(client.sign_azero(b"2").await.is_ok());
+        let_assert!(Err(signer_client::Error::Rejected(_)) = client.sign_azero(b"3").await);
+
+        // Other command types have their own limits
+        assert!(client.sign_eth_hash(Default::default()).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_eth_nonce_limit() {
+        let client = connect_with_limits(LimitArguments {
+            max_eth_nonces_per_window: Some(2),
+            ..limit_arguments()
+        })
+        .await;
+        let tx = |nonce: u64, chain_id: u64| {
+            let mut tx = TypedTransaction::Eip1559(Default::default());
+            tx.set_nonce(nonce).set_chain_id(chain_id);
+            tx
+        };
+
+        assert!(client.sign_eth_tx(&tx(0, 1)).await.is_ok());
+        assert!(client.sign_eth_tx(&tx(1, 1)).await.is_ok());
+        // Re-signing an already seen nonce doesn't count against the limit
+        assert!(client.sign_eth_tx(&tx(0, 1)).await.is_ok());
+        let_assert!(Err(signer_client::Error::Rejected(_)) = client.sign_eth_tx(&tx(2, 1)).await);
+
+        // Nonces are tracked per chain
+        assert!(client.sign_eth_tx(&tx(2, 1337)).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_rejected_eth_nonce_is_not_counted_as_signature() {
+        let client = connect_with_limits(LimitArguments {
+            max_eth_tx_signatures_per_minute: Some(2),
+            max_eth_nonces_per_window: Some(1),
+            ..limit_arguments()
+        })
+        .await;
+        let tx = |nonce: u64| {
+            let mut tx = TypedTransaction::Eip1559(Default::default());
+            tx.set_nonce(nonce).set_chain_id(1);
+            tx
+        };
+
+        assert!(client.sign_eth_tx(&tx(0)).await.is_ok());
+        let_assert!(Err(signer_client::Error::Rejected(_)) = client.sign_eth_tx(&tx(1)).await);
+        assert!(client.sign_eth_tx(&tx(0)).await.is_ok());
+        let_assert!(Err(signer_client::Error::Rejected(_)) = client.sign_eth_tx(&tx(0)).await);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_lockdown() {
+        let args = LimitArguments {
+            lockdown_after_violations: Some(2),
+            ..limit_arguments()
+        };
+        let client = connect_with_limits(args.clone()).await;
+        let unknown_key = || Command::EthAddress {
+            key: Some("unknown".to_string()),
+        };
+
+        let_assert!(Err(signer_client::Error::Rejected(_)) = client.call(unknown_key()).await);
+        assert!(client.call(Command::Ping).await.is_ok());
+        let_assert!(Err(signer_client::Error::Rejected(_)) = client.call(unknown_key()).await);
+
+        // Everything is refused once locked down
+        let_assert!(Err(signer_client::Error::Rejected(_)) = client.call(Command::Ping).await);
+        let_assert!(Err(signer_client::Error::Rejected(_)) = client.eth_address().await);
+
+        // The lockdown survives a restart, unless cleared by the operator
+        let restarted = Limits::new(LimitArguments {
+            clear_lockdown: false,
+            ..args.clone()
+        })
+        .unwrap();
+        assert!(restarted.ensure_not_locked_down().is_err());
+
+        let cleared = Limits::new(args).unwrap();
+        assert!(cleared.ensure_not_locked_down().is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_resent_request_is_not_signed_again() {
+        let limits = Limits::new(LimitArguments {
+            max_azero_signatures_per_minute: Some(1),
+            ..limit_arguments()
+        })
+        .unwrap();
+        let mut server = Server::new(keys(), limits, replies(), port()).unwrap();
+        let request = |id| Request {
+            id,
+            session: Some(7),
+            command: Command::SignAzero {
+                payload: b"Hello, world!".to_vec(),
+                key: None,
+            },
+        };
+
+        let mut connection = Connection::new(VMADDR_CID_HOST, port()).await.unwrap();
+        server.accept_one().await.unwrap();
+        connection.send(&request(0)).await.unwrap();
+        let reply: Reply = connection.recv().await.unwrap();
+        let_assert!(Response::SignedAzero { .. } = &reply.response);
+
+        // The client resends the request after reconnecting
+        drop(connection);
+        let mut connection = Connection::new(VMADDR_CID_HOST, port()).await.unwrap();
+        server.accept_one().await.unwrap();
+        connection.send(&request(0)).await.unwrap();
+        let resent_reply: Reply = connection.recv().await.unwrap();
+        assert!(resent_reply == reply);
+
+        // The replay didn't use up the limit, the first signature did
+        connection.send(&request(1)).await.unwrap();
+        let rejected: Reply = connection.recv().await.unwrap();
+        let_assert!(Response::Rejected { .. } = rejected.response);
+    }
+
+    fn replies() -> Replies {
+        Replies::new(Duration::from_secs(600))
+    }
+
+    fn limit_arguments() -> LimitArguments {
+        LimitArguments {
+            max_azero_signatures_per_minute: None,
+            max_eth_hash_signatures_per_minute: None,
+            max_eth_tx_signatures_per_minute: None,
+            max_eth_nonces_per_window: None,
+            eth_nonce_window_sec: 600,
+            lockdown_after_violations: None,
+            violation_window_sec: 3600,
+            lockdown_file: env::temp_dir().join("signer_test.lockdown"),
+            clear_lockdown: true,
+        }
+    }
+
     async fn connect() -> Client {
-        let mut server =
-            Server::new("//Alice".to_string(), ETH_PRIVATE_KEY.to_string(), port()).unwrap();
+        connect_with_limits(limit_arguments()).await
+    }
+
+    async fn connect_with_limits(limit_arguments: LimitArguments) -> Client {
+        let limits = Limits::new(limit_arguments).unwrap();
+        let mut server = Server::new(keys(), limits, replies(), port()).unwrap();
         let client = Client::new(VMADDR_CID_HOST, port()).await.unwrap();
         server.accept_one().await.unwrap();
 
@@ -0,0 +1,63 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use log::info;
+use signer_client::{RequestId, Response, SessionId};
+
+/// Responses given recently to requests that carry a session, shared by all connections.
+///
+/// A client resends the requests that didn't get a response after it reconnects. Answering them
+/// from here means a command the signer already executed doesn't produce a second signature, nor
+/// count against the limits again.
+pub struct Replies {
+    retention: Duration,
+    responses: Mutex<HashMap<(SessionId, RequestId), (Instant, Response)>>,
+}
+
+impl Replies {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            retention,
+            responses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the response remembered for the request, or executes it with `respond`. Requests
+    /// without a session are always executed.
+    ///
+    /// The lock is held while executing, so that a request resent on a new connection waits for
+    /// the original one instead of racing it.
+    pub fn get_or_respond<E>(
+        &self,
+        session: Option<SessionId>,
+        id: RequestId,
+        respond: impl FnOnce() -> Result<Response, E>,
+    ) -> Result<Response, E> {
+        let Some(session) = session else {
+            return respond();
+        };
+
+        let mut responses = self
+            .responses
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        responses.retain(|_, (at, _)| now.duration_since(*at) < self.retention);
+
+        if let Some((_, response)) = responses.get(&(session, id)) {
+            info!(
+                "Replaying the response to request {} of session {}",
+                id, session
+            );
+            return Ok(response.clone());
+        }
+
+        let response = respond()?;
+        responses.insert((session, id), (now, response.clone()));
+
+        Ok(response)
+    }
+}
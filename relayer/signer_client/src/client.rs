@@ -0,0 +1,539 @@
+use std::{
+    cmp::min,
+    collections::{hash_map::RandomState, BTreeMap},
+    hash::{BuildHasher, Hasher},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use futures::{future::BoxFuture, FutureExt as _};
+use log::{info, warn};
+use subxt::ext::{sp_core::crypto::AccountId32, sp_runtime::MultiSignature};
+use tokio::{
+    select,
+    sync::{mpsc, oneshot},
+    time::{sleep, timeout},
+};
+
+use crate::{
+    Command, Connection, Error, EthAddress, EthH256, EthSignature, EthTypedTransaction, KeyId,
+    KeyInfo, Reply, Request, RequestId, Response, SessionId, ETH_MAINNET_CHAIN_ID,
+};
+
+const LOG_TARGET: &str = "SignerClient";
+
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// How long a single call waits for its response, including the time spent reconnecting.
+    pub request_timeout: Duration,
+    /// Delay before the first reconnection attempt, doubled after every failed attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between reconnection attempts.
+    pub max_backoff: Duration,
+    /// Key used by the typed helpers. The signer's default key is used if not set.
+    pub key: Option<KeyId>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(30),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            key: None,
+        }
+    }
+}
+
+/// Opens a new connection to the signer.
+type Connect = Box<dyn FnMut() -> BoxFuture<'static, Result<Connection, Error>> + Send>;
+
+struct PendingRequest {
+    request: Request,
+    reply: oneshot::Sender<Response>,
+}
+
+enum Message {
+    Call(PendingRequest),
+    /// The caller stopped waiting for the response, so the request must not be sent again.
+    Cancel(RequestId),
+}
+
+/// A signer client that can be shared between tasks.
+///
+/// Requests are tagged with ids, so several of them can be in flight on the same connection. The
+/// connection is owned by a background task, which transparently reconnects (with exponential
+/// backoff) when the signer goes away and resends the requests that didn't get a response.
+///
+/// Every request also carries the session of the client, which the signer uses to answer a resent
+/// request it has already executed with its earlier response. A resent signing command therefore
+/// doesn't produce a second signature, nor count against the limits of the signer twice.
+pub struct Client {
+    messages: mpsc::UnboundedSender<Message>,
+    session: SessionId,
+    next_id: AtomicU64,
+    request_timeout: Duration,
+    key: Option<KeyId>,
+}
+
+impl Client {
+    pub async fn new(cid: u32, port: u32) -> Result<Self, Error> {
+        Self::with_config(cid, port, ClientConfig::default()).await
+    }
+
+    /// Connects to the signer. Only the first connection attempt is reported as an error, later
+    /// disconnections are handled in the background.
+    pub async fn with_config(cid: u32, port: u32, config: ClientConfig) -> Result<Self, Error> {
+        let connection = Connection::new(cid, port).await?;
+        let connect: Connect = Box::new(move || Connection::new(cid, port).boxed());
+
+        Ok(Self::with_connection(config, connection, connect))
+    }
+
+    fn with_connection(config: ClientConfig, connection: Connection, connect: Connect) -> Self {
+        let (messages, messages_receiver) = mpsc::unbounded_channel();
+        let request_timeout = config.request_timeout;
+        let key = config.key.clone();
+
+        tokio::spawn(run_connection(
+            config,
+            connection,
+            connect,
+            messages_receiver,
+        ));
+
+        Self {
+            messages,
+            session: new_session_id(),
+            next_id: AtomicU64::new(0),
+            request_timeout,
+            key,
+        }
+    }
+
+    /// Sends a command to the signer and waits for the matching response. Rejections are reported
+    /// as [`Error::Rejected`].
+    pub async fn call(&self, command: Command) -> Result<Response, Error> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (reply, response) = oneshot::channel();
+
+        let request = Request {
+            id,
+            session: Some(self.session),
+            command,
+        };
+
+        self.messages
+            .send(Message::Call(PendingRequest { request, reply }))
+            .map_err(|_| Error::Closed)?;
+
+        match timeout(self.request_timeout, response).await {
+            Ok(Ok(Response::Rejected { reason })) => Err(Error::Rejected(reason)),
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(Error::Closed),
+            Err(_) => {
+                let _ = self.messages.send(Message::Cancel(id));
+                Err(Error::Timeout(id))
+            }
+        }
+    }
+
+    pub async fn list_keys(&self) -> Result<Vec<KeyInfo>, Error> {
+        match self.call(Command::ListKeys).await? {
+            Response::Keys { keys } => Ok(keys),
+            other => Err(Error::InvalidResponse {
+                expected: "Keys".to_string(),
+                got: other,
+            }),
+        }
+    }
+
+    pub async fn azero_account_id(&self) -> Result<AccountId32, Error> {
+        let command = Command::AccountIdAzero {
+            key: self.key.clone(),
+        };
+
+        match self.call(command).await? {
+            Response::AccountIdAzero { account_id } => Ok(account_id),
+            other => Err(Error::InvalidResponse {
+                expected: "AccountIdAzero".to_string(),
+                got: other,
+            }),
+        }
+    }
+
+    pub async fn sign_azero(&self, payload: &[u8]) -> Result<MultiSignature, Error> {
+        let command = Command::SignAzero {
+            payload: payload.to_vec(),
+            key: self.key.clone(),
+        };
+
+        match self.call(command).await? {
+            Response::SignedAzero {
+                payload: return_payload,
+                signature,
+            } if return_payload == payload => Ok(signature),
+            other => Err(Error::InvalidResponse {
+                expected: format!("SignedAzero(payload: {:?})", payload),
+                got: other,
+            }),
+        }
+    }
+
+    pub async fn eth_address(&self) -> Result<EthAddress, Error> {
+        let command = Command::EthAddress {
+            key: self.key.clone(),
+        };
+
+        match self.call(command).await? {
+            Response::EthAddress { address } => Ok(address),
+            other => Err(Error::InvalidResponse {
+                expected: "EthAddress".to_string(),
+                got: other,
+            }),
+        }
+    }
+
+    pub async fn sign_eth_hash(&self, hash: EthH256) -> Result<EthSignature, Error> {
+        let command = Command::SignEthHash {
+            hash,
+            key: self.key.clone(),
+        };
+
+        match self.call(command).await? {
+            Response::SignedEthHash {
+                hash: return_hash,
+                signature,
+            } if return_hash == hash => Ok(signature),
+            other => Err(Error::InvalidResponse {
+                expected: format!("SignedEthHash(hash: {:?})", hash),
+                got: other,
+            }),
+        }
+    }
+
+    pub async fn sign_eth_tx(&self, tx: &EthTypedTransaction) -> Result<EthSignature, Error> {
+        let chain_id = tx.chain_id().unwrap_or(ETH_MAINNET_CHAIN_ID);
+        let res = self
+            .call(Command::SignEthTx {
+                tx: tx.clone(),
+                chain_id,
+                key: self.key.clone(),
+            })
+            .await?;
+
+        if let Response::SignedEthTx {
+            tx: mut return_tx,
+            signature,
+            chain_id: return_chain_id,
+        } = res.clone()
+        {
+            // The Serialize and Deserialize implementations for TypedTransacion do not
+            // serialize and deserialize the chain_id field, so we need to supply it
+            // manually to the comparison here.
+            if tx.chain_id().is_some() {
+                return_tx.set_chain_id(return_chain_id);
+            }
+
+            if return_tx == *tx {
+                return Ok(signature);
+            }
+        }
+
+        Err(Error::InvalidResponse {
+            expected: format!("SignedEthTx(tx: {:?})", tx),
+            got: res,
+        })
+    }
+}
+
+/// Random, so that a restarted client doesn't get the responses given to its previous run.
+fn new_session_id() -> SessionId {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(since_epoch.as_nanos());
+    hasher.write_u32(process::id());
+
+    hasher.finish()
+}
+
+/// Owns the connection to the signer. Exits once every `Client` handle is dropped.
+async fn run_connection(
+    config: ClientConfig,
+    mut connection: Connection,
+    mut connect: Connect,
+    mut messages: mpsc::UnboundedReceiver<Message>,
+) {
+    // Requests that were accepted from the clients, but haven't been answered yet.
+    let mut in_flight = BTreeMap::new();
+
+    loop {
+        match serve(&mut connection, &mut messages, &mut in_flight).await {
+            Ok(()) => return,
+            Err(why) => warn!(target: LOG_TARGET, "Connection to the signer lost: {why:?}"),
+        }
+
+        connection = match reconnect(&config, &mut connect, &mut messages, &mut in_flight).await {
+            Some(connection) => connection,
+            None => return,
+        };
+    }
+}
+
+/// Records a call as in flight or forgets a cancelled one. Returns the id of a request to send.
+fn accept(
+    message: Message,
+    in_flight: &mut BTreeMap<RequestId, PendingRequest>,
+) -> Option<RequestId> {
+    match message {
+        Message::Call(pending) => {
+            let id = pending.request.id;
+            in_flight.insert(id, pending);
+            Some(id)
+        }
+        Message::Cancel(id) => {
+            in_flight.remove(&id);
+            None
+        }
+    }
+}
+
+async fn serve(
+    connection: &mut Connection,
+    messages: &mut mpsc::UnboundedReceiver<Message>,
+    in_flight: &mut BTreeMap<RequestId, PendingRequest>,
+) -> Result<(), Error> {
+    // Requests sent over a previous connection may never have reached the signer, so they are sent
+    // again. The signer recognizes the ones it has already executed by their session and id. The
+    // ones whose callers have already given up are dropped.
+    in_flight.retain(|_, pending| !pending.reply.is_closed());
+    for pending in in_flight.values() {
+        connection.send(&pending.request).await?;
+    }
+
+    loop {
+        select! {
+            message = messages.recv() => {
+                let Some(message) = message else {
+                    return Ok(());
+                };
+                if let Some(id) = accept(message, in_flight) {
+                    connection.send(&in_flight[&id].request).await?;
+                }
+            },
+
+            reply = connection.recv::<Reply>() => {
+                let Reply { id, response } = reply?;
+                match in_flight.remove(&id) {
+                    // The caller may have timed out in the meantime, which is fine.
+                    Some(pending) => {
+                        let _ = pending.reply.send(response);
+                    }
+                    None => warn!(target: LOG_TARGET, "Received a response to an unknown request {id}"),
+                }
+            },
+        }
+    }
+}
+
+async fn reconnect(
+    config: &ClientConfig,
+    connect: &mut Connect,
+    messages: &mut mpsc::UnboundedReceiver<Message>,
+    in_flight: &mut BTreeMap<RequestId, PendingRequest>,
+) -> Option<Connection> {
+    let mut delay = config.initial_backoff;
+
+    loop {
+        let backoff = sleep(delay);
+        tokio::pin!(backoff);
+
+        // Keep accepting requests while the signer is unavailable, they will be sent as soon as
+        // the connection is back up.
+        loop {
+            select! {
+                _ = &mut backoff => break,
+
+                message = messages.recv() => {
+                    accept(message?, in_flight);
+                },
+            }
+        }
+
+        match connect().await {
+            Ok(connection) => {
+                info!(target: LOG_TARGET, "Reconnected to the signer");
+                return Some(connection);
+            }
+            Err(why) => {
+                warn!(target: LOG_TARGET, "Failed to reconnect to the signer: {why:?}");
+                delay = min(delay * 2, config.max_backoff);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+        time::Instant,
+    };
+
+    use subxt::ext::{sp_core::sr25519, sp_runtime::MultiSignature};
+    use tokio::io::{duplex, split};
+
+    use super::*;
+
+    const INITIAL_BACKOFF: Duration = Duration::from_millis(50);
+
+    fn config() -> ClientConfig {
+        ClientConfig {
+            request_timeout: Duration::from_secs(5),
+            initial_backoff: INITIAL_BACKOFF,
+            max_backoff: INITIAL_BACKOFF * 4,
+            key: None,
+        }
+    }
+
+    /// Returns the client and the signer end of an in-memory connection.
+    fn pipe() -> (Connection, Connection) {
+        let (client, signer) = duplex(4096);
+        let (client_read, client_write) = split(client);
+        let (signer_read, signer_write) = split(signer);
+
+        (
+            Connection::from_split(client_read, client_write),
+            Connection::from_split(signer_read, signer_write),
+        )
+    }
+
+    /// Hands out the given connection attempts in order and records when they were made.
+    fn connector(attempts: Vec<Result<Connection, Error>>) -> (Connect, Arc<Mutex<Vec<Instant>>>) {
+        let attempts = Arc::new(Mutex::new(VecDeque::from(attempts)));
+        let made = Arc::new(Mutex::new(Vec::new()));
+        let made_clone = made.clone();
+
+        let connect: Connect = Box::new(move || {
+            made_clone.lock().unwrap().push(Instant::now());
+            let attempt = attempts
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or(Err(Error::Closed));
+            async move { attempt }.boxed()
+        });
+
+        (connect, made)
+    }
+
+    fn signed_azero(request: &Request) -> Reply {
+        let Command::SignAzero { payload, .. } = &request.command else {
+            panic!("Unexpected command {:?}", request.command);
+        };
+
+        Reply {
+            id: request.id,
+            response: Response::SignedAzero {
+                payload: payload.clone(),
+                signature: MultiSignature::Sr25519(sr25519::Signature::from_raw([0; 64])),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn responses_are_matched_by_request_id() {
+        let (connection, mut signer) = pipe();
+        let (connect, _) = connector(vec![]);
+        let client = Client::with_connection(config(), connection, connect);
+
+        let signer = tokio::spawn(async move {
+            let first: Request = signer.recv().await.unwrap();
+            let second: Request = signer.recv().await.unwrap();
+            // Answer out of order
+            signer.send(&signed_azero(&second)).await.unwrap();
+            signer.send(&signed_azero(&first)).await.unwrap();
+            (first, second)
+        });
+
+        let (first, second) =
+            tokio::join!(client.sign_azero(b"first"), client.sign_azero(b"second"));
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+
+        let (first, second) = signer.await.unwrap();
+        assert_ne!(first.id, second.id);
+        assert_eq!(first.session, second.session);
+    }
+
+    #[tokio::test]
+    async fn unanswered_requests_are_resent_after_reconnecting_with_backoff() {
+        let (connection, mut first_signer) = pipe();
+        let (second_connection, mut second_signer) = pipe();
+        let (connect, attempts) = connector(vec![Err(Error::Closed), Ok(second_connection)]);
+        let client = Client::with_connection(config(), connection, connect);
+
+        let signer = tokio::spawn(async move {
+            let unanswered: Request = first_signer.recv().await.unwrap();
+            let disconnected_at = Instant::now();
+            drop(first_signer);
+
+            let resent: Request = second_signer.recv().await.unwrap();
+            second_signer.send(&signed_azero(&resent)).await.unwrap();
+            (unanswered, resent, disconnected_at)
+        });
+
+        assert!(client.sign_azero(b"payload").await.is_ok());
+
+        // The resent request has the same session and id, so the signer can tell it was resent
+        let (unanswered, resent, disconnected_at) = signer.await.unwrap();
+        assert!(resent.session.is_some());
+        assert_eq!(unanswered, resent);
+
+        let attempts = attempts.lock().unwrap();
+        assert_eq!(attempts.len(), 2);
+        assert!(attempts[0] - disconnected_at >= INITIAL_BACKOFF);
+        assert!(attempts[1] - attempts[0] >= INITIAL_BACKOFF * 2);
+    }
+
+    #[tokio::test]
+    async fn timed_out_requests_are_not_resent() {
+        let (connection, mut first_signer) = pipe();
+        let (second_connection, mut second_signer) = pipe();
+        let (connect, _) = connector(vec![Ok(second_connection)]);
+        let config = ClientConfig {
+            request_timeout: Duration::from_millis(100),
+            ..config()
+        };
+        let client = Client::with_connection(config, connection, connect);
+
+        assert!(matches!(
+            client.call(Command::Ping).await,
+            Err(Error::Timeout(0))
+        ));
+        let timed_out: Request = first_signer.recv().await.unwrap();
+        drop(first_signer);
+
+        let signer = tokio::spawn(async move {
+            let request: Request = second_signer.recv().await.unwrap();
+            let response = Response::Pong;
+            second_signer
+                .send(&Reply {
+                    id: request.id,
+                    response,
+                })
+                .await
+                .unwrap();
+            request
+        });
+
+        assert_eq!(client.call(Command::Ping).await.unwrap(), Response::Pong);
+        let request = signer.await.unwrap();
+        assert_eq!(timed_out.id, 0);
+        assert_eq!(request.id, 1);
+    }
+}
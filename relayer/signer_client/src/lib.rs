@@ -2,10 +2,15 @@ use futures::{SinkExt as _, StreamExt as _};
 use serde::{Deserialize, Serialize};
 use serde_json::Deserializer;
 use subxt::ext::{sp_core::crypto::AccountId32, sp_runtime::MultiSignature};
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
-use tokio_vsock::{OwnedReadHalf, OwnedWriteHalf, VsockStream};
+use tokio_vsock::VsockStream;
 use vsock::VsockAddr;
 
+mod client;
+
+pub use client::{Client, ClientConfig};
+
 type EthAddress = ethers::types::Address;
 type EthSignature = ethers::types::Signature;
 type EthH256 = ethers::types::H256;
@@ -24,28 +29,78 @@ pub enum Error {
     InvalidResponse { expected: String, got: Response },
     #[error("Connection closed")]
     Closed,
+    #[error("Request {0} timed out")]
+    Timeout(RequestId),
+    #[error("Request rejected by the signer: {0}")]
+    Rejected(String),
+}
+
+/// Identifies a request on a connection, so that responses can be matched with the requests even
+/// if several of them are in flight at the same time.
+pub type RequestId = u64;
+
+/// Identifies a client across its connections. Together with the [`RequestId`], it lets the signer
+/// recognize a request that is sent again after a reconnection.
+pub type SessionId = u64;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub id: RequestId,
+    /// If set, the signer remembers its response for a while and answers a request with the same
+    /// session and id with it again, instead of executing the command a second time.
+    #[serde(default)]
+    pub session: Option<SessionId>,
+    pub command: Command,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub id: RequestId,
+    pub response: Response,
+}
+
+/// Identifies one of the keys held by the signer.
+pub type KeyId = String;
+
+/// The key used by commands that don't name one explicitly.
+pub const DEFAULT_KEY_ID: &str = "default";
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum Command {
     Ping,
-    AccountIdAzero,
+    ListKeys,
+    AccountIdAzero {
+        #[serde(default)]
+        key: Option<KeyId>,
+    },
     SignAzero {
         payload: Vec<u8>,
+        #[serde(default)]
+        key: Option<KeyId>,
+    },
+    EthAddress {
+        #[serde(default)]
+        key: Option<KeyId>,
     },
-    EthAddress,
     SignEthHash {
         hash: EthH256,
+        #[serde(default)]
+        key: Option<KeyId>,
     },
     SignEthTx {
         tx: ethers::types::transaction::eip2718::TypedTransaction,
         chain_id: EthChainId,
+        #[serde(default)]
+        key: Option<KeyId>,
     },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum Response {
     Pong,
+    Keys {
+        keys: Vec<KeyInfo>,
+    },
     AccountIdAzero {
         account_id: AccountId32,
     },
@@ -65,29 +120,77 @@ pub enum Response {
         signature: EthSignature,
         chain_id: EthChainId,
     },
+    /// The command was refused, e.g. because the key doesn't exist or its policy forbids it.
+    Rejected {
+        reason: String,
+    },
+}
+
+/// Describes what a key is allowed to sign.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct KeyPolicy {
+    pub sign_azero: bool,
+    pub sign_eth_hash: bool,
+    pub sign_eth_tx: bool,
+    /// If set, only Ethereum transactions for these chains are signed.
+    pub eth_chain_ids: Option<Vec<EthChainId>>,
+}
+
+impl Default for KeyPolicy {
+    fn default() -> Self {
+        Self {
+            sign_azero: true,
+            sign_eth_hash: true,
+            sign_eth_tx: true,
+            eth_chain_ids: None,
+        }
+    }
 }
 
-pub struct Client {
-    read: FramedRead<OwnedReadHalf, LengthDelimitedCodec>,
-    write: FramedWrite<OwnedWriteHalf, LengthDelimitedCodec>,
+/// Public identities and the policy of a key held by the signer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct KeyInfo {
+    pub id: KeyId,
+    pub azero_account_id: Option<AccountId32>,
+    pub eth_address: Option<EthAddress>,
+    pub policy: KeyPolicy,
+}
+
+/// A single framed connection to the signer. The server uses it to talk to its clients, while
+/// [`Client`] builds reconnection and request multiplexing on top of it.
+pub struct Connection {
+    read: FramedRead<Box<dyn AsyncRead + Send + Unpin>, LengthDelimitedCodec>,
+    write: FramedWrite<Box<dyn AsyncWrite + Send + Unpin>, LengthDelimitedCodec>,
 }
 
-impl From<VsockStream> for Client {
+impl From<VsockStream> for Connection {
     fn from(connection: VsockStream) -> Self {
         let (read, write) = connection.into_split();
-        let write = FramedWrite::new(write, LengthDelimitedCodec::new());
-        let read = FramedRead::new(read, LengthDelimitedCodec::new());
-
-        Self { write, read }
+        Self::from_split(read, write)
     }
 }
 
-impl Client {
+impl Connection {
     pub async fn new(cid: u32, port: u32) -> Result<Self, Error> {
         let connection = VsockStream::connect(VsockAddr::new(cid, port)).await?;
         Ok(connection.into())
     }
 
+    /// Frames any pair of byte streams, e.g. the halves of an in-memory pipe in tests.
+    pub fn from_split(
+        read: impl AsyncRead + Send + Unpin + 'static,
+        write: impl AsyncWrite + Send + Unpin + 'static,
+    ) -> Self {
+        let read: Box<dyn AsyncRead + Send + Unpin> = Box::new(read);
+        let write: Box<dyn AsyncWrite + Send + Unpin> = Box::new(write);
+
+        Self {
+            read: FramedRead::new(read, LengthDelimitedCodec::new()),
+            write: FramedWrite::new(write, LengthDelimitedCodec::new()),
+        }
+    }
+
     pub async fn send<T: Serialize>(&mut self, msg: &T) -> Result<(), Error> {
         let msg = serde_json::to_vec(msg)?;
         self.write.send(msg.into()).await?;
@@ -101,94 +204,4 @@ impl Client {
 
         Ok(res)
     }
-
-    pub async fn azero_account_id(&mut self) -> Result<AccountId32, Error> {
-        self.send(&Command::AccountIdAzero).await?;
-
-        match self.recv().await? {
-            Response::AccountIdAzero { account_id } => Ok(account_id),
-            other => Err(Error::InvalidResponse {
-                expected: "AccountIdAzero".to_string(),
-                got: other,
-            }),
-        }
-    }
-
-    pub async fn sign_azero(&mut self, payload: &[u8]) -> Result<MultiSignature, Error> {
-        self.send(&Command::SignAzero {
-            payload: payload.to_vec(),
-        })
-        .await?;
-
-        match self.recv().await? {
-            Response::SignedAzero {
-                payload: return_payload,
-                signature,
-            } if return_payload == payload => Ok(signature),
-            other => Err(Error::InvalidResponse {
-                expected: format!("SignedAzero(payload: {:?})", payload),
-                got: other,
-            }),
-        }
-    }
-
-    pub async fn eth_address(&mut self) -> Result<EthAddress, Error> {
-        self.send(&Command::EthAddress).await?;
-
-        match self.recv().await? {
-            Response::EthAddress { address } => Ok(address),
-            other => Err(Error::InvalidResponse {
-                expected: "EthAddress".to_string(),
-                got: other,
-            }),
-        }
-    }
-
-    pub async fn sign_eth_hash(&mut self, hash: EthH256) -> Result<EthSignature, Error> {
-        self.send(&Command::SignEthHash { hash }).await?;
-
-        match self.recv().await? {
-            Response::SignedEthHash {
-                hash: return_hash,
-                signature,
-            } if return_hash == hash => Ok(signature),
-            other => Err(Error::InvalidResponse {
-                expected: format!("SignedEthHash(hash: {:?})", hash),
-                got: other,
-            }),
-        }
-    }
-
-    pub async fn sign_eth_tx(&mut self, tx: &EthTypedTransaction) -> Result<EthSignature, Error> {
-        let chain_id = tx.chain_id().unwrap_or(ETH_MAINNET_CHAIN_ID);
-        self.send(&Command::SignEthTx {
-            tx: tx.clone(),
-            chain_id,
-        })
-        .await?;
-        let res = self.recv::<Response>().await?;
-
-        if let Response::SignedEthTx {
-            tx: mut return_tx,
-            signature,
-            chain_id: return_chain_id,
-        } = res.clone()
-        {
-            // The Serialize and Deserialize implementations for TypedTransacion do not
-            // serialize and deserialize the chain_id field, so we need to supply it
-            // manually to the comparison here.
-            if tx.chain_id().is_some() {
-                return_tx.set_chain_id(return_chain_id);
-            }
-
-            if return_tx == *tx {
-                return Ok(signature);
-            }
-        }
-
-        Err(Error::InvalidResponse {
-            expected: format!("SignedEthTx(tx: {:?})", tx),
-            got: res,
-        })
-    }
 }
@@ -4,10 +4,27 @@ set -eou pipefail
 
 # --- Prepare arguments
 
-ARGS=(
-  --azero-key=${AZERO_KEY}
-  --eth-key=${ETH_KEY}
-)
+ARGS=()
+
+if [[ -n "${AZERO_KEY:-}" ]]; then
+  ARGS+=(--azero-key=${AZERO_KEY})
+fi
+
+if [[ -n "${ETH_KEY:-}" ]]; then
+  ARGS+=(--eth-key=${ETH_KEY})
+fi
+
+if [[ -n "${KEYS_FILE:-}" ]]; then
+  ARGS+=(--keys-file=${KEYS_FILE})
+fi
+
+if [[ -n "${LOCKDOWN_FILE:-}" ]]; then
+  ARGS+=(--lockdown-file=${LOCKDOWN_FILE})
+fi
+
+if [[ "${CLEAR_LOCKDOWN:-}" == "true" ]]; then
+  ARGS+=(--clear-lockdown)
+fi
 
 if [[ -n "${PORT}" ]]; then
   ARGS+=(--port=${PORT})
@@ -0,0 +1,403 @@
+use std::time::Duration;
+
+use ethers::{
+    types::{transaction::eip2718::TypedTransaction, Address, H256, U64},
+    utils::keccak256,
+};
+use signer_client::{Client, Command, KeyId, KeyInfo, Response};
+use subxt::ext::sp_runtime::traits::Verify;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    time::timeout,
+};
+use tokio_vsock::{VsockAddr, VsockStream};
+
+/// How long to wait for the signer to drop a connection after a malformed frame.
+const MALFORMED_FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Chain ids used for keys that are allowed to sign transactions for any chain.
+const DEFAULT_CHAIN_IDS: [u64; 2] = [1, 1337];
+
+const PAYLOAD: &[u8] = b"signer conformance test";
+
+type CheckResult = Result<(), String>;
+
+#[derive(Default)]
+struct Report {
+    passed: usize,
+    failed: usize,
+}
+
+impl Report {
+    fn check(&mut self, name: &str, result: CheckResult) {
+        match result {
+            Ok(()) => {
+                self.passed += 1;
+                println!("[PASS] {name}");
+            }
+            Err(why) => {
+                self.failed += 1;
+                println!("[FAIL] {name}: {why}");
+            }
+        }
+    }
+}
+
+/// Runs all the checks against the signer and prints their results. Returns whether all of them
+/// passed.
+///
+/// The checks that expect a rejection count as violations in the signer. The signer must not be
+/// run with `--lockdown-after-violations`, otherwise they lock it down and write the lockdown file.
+pub async fn run(client: &Client, cid: u32, port: u32) -> bool {
+    let mut report = Report::default();
+
+    report.check("ping", check_ping(client).await);
+
+    match client.list_keys().await {
+        Ok(keys) if !keys.is_empty() => {
+            report.check("list keys", Ok(()));
+            for key in &keys {
+                check_key(&mut report, client, key).await;
+            }
+        }
+        Ok(_) => report.check("list keys", Err("no keys exposed".to_string())),
+        Err(why) => report.check("list keys", Err(why.to_string())),
+    }
+
+    check_unknown_key(&mut report, client).await;
+    check_malformed_frames(&mut report, cid, port).await;
+
+    report.check(
+        "signer accepts new connections after malformed frames",
+        match Client::new(cid, port).await {
+            Ok(client) => check_ping(&client).await,
+            Err(why) => Err(why.to_string()),
+        },
+    );
+    report.check(
+        "existing connection survives malformed frames on other connections",
+        check_ping(client).await,
+    );
+
+    println!("{} passed, {} failed", report.passed, report.failed);
+
+    report.failed == 0
+}
+
+async fn check_ping(client: &Client) -> CheckResult {
+    match client.call(Command::Ping).await {
+        Ok(Response::Pong) => Ok(()),
+        other => Err(format!("expected Pong, got {other:?}")),
+    }
+}
+
+async fn check_key(report: &mut Report, client: &Client, info: &KeyInfo) {
+    let id = &info.id;
+    let key = Some(id.clone());
+
+    let response = client
+        .call(Command::AccountIdAzero { key: key.clone() })
+        .await;
+    report.check(
+        &format!("[{id}] Azero account id"),
+        match (&info.azero_account_id, response) {
+            (Some(expected), Ok(Response::AccountIdAzero { account_id }))
+                if *expected == account_id =>
+            {
+                Ok(())
+            }
+            (None, Err(signer_client::Error::Rejected(_))) => Ok(()),
+            (expected, got) => Err(format!("expected {expected:?}, got {got:?}")),
+        },
+    );
+
+    let response = client.call(Command::EthAddress { key: key.clone() }).await;
+    report.check(
+        &format!("[{id}] ETH address"),
+        match (&info.eth_address, response) {
+            (Some(expected), Ok(Response::EthAddress { address })) if *expected == address => {
+                Ok(())
+            }
+            (None, Err(signer_client::Error::Rejected(_))) => Ok(()),
+            (expected, got) => Err(format!("expected {expected:?}, got {got:?}")),
+        },
+    );
+
+    report.check(
+        &format!("[{id}] sign Azero payload"),
+        check_sign_azero(client, info).await,
+    );
+    report.check(
+        &format!("[{id}] sign ETH hash"),
+        check_sign_eth_hash(client, info).await,
+    );
+
+    let eth_address = match (&info.eth_address, info.policy.sign_eth_tx) {
+        (Some(address), true) => *address,
+        _ => {
+            let command = eth_tx_command(None, DEFAULT_CHAIN_IDS[0], key);
+            report.check(
+                &format!("[{id}] sign ETH tx is rejected"),
+                expect_rejected(client, command).await,
+            );
+            return;
+        }
+    };
+
+    let allowed_chain_ids: Vec<u64> = match &info.policy.eth_chain_ids {
+        Some(chain_ids) => chain_ids.iter().map(U64::as_u64).collect(),
+        None => DEFAULT_CHAIN_IDS.to_vec(),
+    };
+
+    for &chain_id in &allowed_chain_ids {
+        report.check(
+            &format!("[{id}] sign ETH tx for chain {chain_id}"),
+            check_sign_eth_tx(client, id, eth_address, Some(chain_id), chain_id).await,
+        );
+        report.check(
+            &format!("[{id}] command chain id overrides the tx chain id ({chain_id})"),
+            check_sign_eth_tx(client, id, eth_address, Some(chain_id + 1), chain_id).await,
+        );
+    }
+
+    // `sign_eth_tx` treats transactions without a chain id as mainnet transactions.
+    if allowed_chain_ids.contains(&1) {
+        report.check(
+            &format!("[{id}] sign ETH tx without chain id"),
+            check_sign_eth_tx(client, id, eth_address, None, 1).await,
+        );
+    }
+
+    if info.policy.eth_chain_ids.is_some() {
+        let disallowed = allowed_chain_ids.iter().max().unwrap_or(&0) + 1;
+        let command = eth_tx_command(Some(disallowed), disallowed, key);
+        report.check(
+            &format!("[{id}] sign ETH tx for disallowed chain {disallowed} is rejected"),
+            expect_rejected(client, command).await,
+        );
+    }
+}
+
+async fn check_sign_azero(client: &Client, info: &KeyInfo) -> CheckResult {
+    let command = Command::SignAzero {
+        payload: PAYLOAD.to_vec(),
+        key: Some(info.id.clone()),
+    };
+
+    let account_id = match (&info.azero_account_id, info.policy.sign_azero) {
+        (Some(account_id), true) => account_id,
+        _ => return expect_rejected(client, command).await,
+    };
+
+    match client.call(command).await {
+        Ok(Response::SignedAzero { payload, signature }) => {
+            if payload != PAYLOAD {
+                return Err(format!("payload not echoed, got {payload:?}"));
+            }
+            if !signature.verify(PAYLOAD, account_id) {
+                return Err("signature does not verify".to_string());
+            }
+            Ok(())
+        }
+        other => Err(format!("expected SignedAzero, got {other:?}")),
+    }
+}
+
+async fn check_sign_eth_hash(client: &Client, info: &KeyInfo) -> CheckResult {
+    let hash = H256::from(keccak256(PAYLOAD));
+    let command = Command::SignEthHash {
+        hash,
+        key: Some(info.id.clone()),
+    };
+
+    let address = match (&info.eth_address, info.policy.sign_eth_hash) {
+        (Some(address), true) => *address,
+        _ => return expect_rejected(client, command).await,
+    };
+
+    match client.call(command).await {
+        Ok(Response::SignedEthHash {
+            hash: signed_hash,
+            signature,
+        }) => {
+            if signed_hash != hash {
+                return Err(format!("hash not echoed, got {signed_hash:?}"));
+            }
+            signature
+                .verify(hash, address)
+                .map_err(|why| format!("signature does not verify: {why}"))
+        }
+        other => Err(format!("expected SignedEthHash, got {other:?}")),
+    }
+}
+
+/// Signs a transaction with `tx_chain_id` set on the transaction itself and `chain_id` passed in
+/// the command. The signature should be valid for `chain_id` only.
+async fn check_sign_eth_tx(
+    client: &Client,
+    id: &KeyId,
+    address: Address,
+    tx_chain_id: Option<u64>,
+    chain_id: u64,
+) -> CheckResult {
+    let command = eth_tx_command(tx_chain_id, chain_id, Some(id.clone()));
+
+    let (signed_tx, signature, signed_chain_id) = match client.call(command).await {
+        Ok(Response::SignedEthTx {
+            tx,
+            signature,
+            chain_id,
+        }) => (tx, signature, chain_id),
+        other => return Err(format!("expected SignedEthTx, got {other:?}")),
+    };
+
+    if signed_chain_id != U64::from(chain_id) {
+        return Err(format!(
+            "expected chain id {chain_id}, got {signed_chain_id}"
+        ));
+    }
+
+    let mut expected_tx = unsigned_tx(Some(chain_id));
+    signature
+        .verify(expected_tx.sighash(), address)
+        .map_err(|why| format!("signature does not verify: {why}"))?;
+
+    // The Serialize and Deserialize implementations for TypedTransaction skip the chain id, so it
+    // is only compared after being set on both sides.
+    let mut signed_tx = signed_tx;
+    signed_tx.set_chain_id(chain_id);
+    if signed_tx != expected_tx {
+        return Err(format!("tx not echoed, got {signed_tx:?}"));
+    }
+
+    // The signature must not be replayable on another chain.
+    expected_tx.set_chain_id(chain_id + 1);
+    if signature.verify(expected_tx.sighash(), address).is_ok() {
+        return Err("signature is also valid for another chain".to_string());
+    }
+
+    Ok(())
+}
+
+async fn check_unknown_key(report: &mut Report, client: &Client) {
+    let key = Some("signer-conformance-unknown-key".to_string());
+    let commands = [
+        Command::AccountIdAzero { key: key.clone() },
+        Command::EthAddress { key: key.clone() },
+        Command::SignAzero {
+            payload: PAYLOAD.to_vec(),
+            key: key.clone(),
+        },
+        Command::SignEthHash {
+            hash: H256::from(keccak256(PAYLOAD)),
+            key: key.clone(),
+        },
+        eth_tx_command(Some(1), 1, key),
+    ];
+
+    for command in commands {
+        let name = format!("unknown key is rejected: {command:?}");
+        report.check(&name, expect_rejected(client, command).await);
+    }
+}
+
+async fn check_malformed_frames(report: &mut Report, cid: u32, port: u32) {
+    // Each case also says whether the write side is closed after sending, which is needed for the
+    // signer to notice that a frame is truncated. Complete frames are left open, so that the signer
+    // has to drop them on its own.
+    let cases: [(&str, Vec<u8>, bool); 6] = [
+        (
+            "not JSON",
+            frame(b"\x00\x01\x02 definitely not json"),
+            false,
+        ),
+        ("JSON that isn't a request", frame(br#"{"foo": 1}"#), false),
+        (
+            "request without id",
+            frame(br#"{"command": "Ping"}"#),
+            false,
+        ),
+        (
+            "unknown command",
+            frame(br#"{"id": 0, "command": "SignEverything"}"#),
+            false,
+        ),
+        ("oversized frame", u32::MAX.to_be_bytes().to_vec(), false),
+        (
+            "truncated frame",
+            {
+                let mut frame = frame(br#"{"id": 0, "command": "Ping"}"#);
+                frame.truncate(frame.len() - 4);
+                frame
+            },
+            true,
+        ),
+    ];
+
+    for (name, bytes, half_close) in cases {
+        report.check(
+            &format!("malformed frame is rejected: {name}"),
+            check_malformed_frame(cid, port, &bytes, half_close).await,
+        );
+    }
+}
+
+/// Sends raw bytes to the signer and expects it to close the connection without replying.
+async fn check_malformed_frame(cid: u32, port: u32, bytes: &[u8], half_close: bool) -> CheckResult {
+    let mut stream = VsockStream::connect(VsockAddr::new(cid, port))
+        .await
+        .map_err(|why| format!("failed to connect: {why}"))?;
+    stream
+        .write_all(bytes)
+        .await
+        .map_err(|why| format!("failed to write: {why}"))?;
+    if half_close {
+        stream
+            .shutdown()
+            .await
+            .map_err(|why| format!("failed to shut down: {why}"))?;
+    }
+
+    let mut buf = [0u8; 1024];
+    match timeout(MALFORMED_FRAME_TIMEOUT, stream.read(&mut buf)).await {
+        Ok(Ok(0)) | Ok(Err(_)) => Ok(()),
+        Ok(Ok(n)) => Err(format!("signer replied with {n} bytes")),
+        Err(_) => Err("signer kept the connection open".to_string()),
+    }
+}
+
+async fn expect_rejected(client: &Client, command: Command) -> CheckResult {
+    match client.call(command).await {
+        Err(signer_client::Error::Rejected(_)) => Ok(()),
+        other => Err(format!("expected a rejection, got {other:?}")),
+    }
+}
+
+fn unsigned_tx(chain_id: Option<u64>) -> TypedTransaction {
+    let mut tx = TypedTransaction::Eip1559(Default::default());
+    tx.set_to(Address::repeat_byte(0x42))
+        .set_value(1_000u64)
+        .set_nonce(7u64)
+        .set_data(PAYLOAD.to_vec().into());
+    if let Some(chain_id) = chain_id {
+        tx.set_chain_id(chain_id);
+    }
+
+    tx
+}
+
+fn eth_tx_command(tx_chain_id: Option<u64>, chain_id: u64, key: Option<KeyId>) -> Command {
+    Command::SignEthTx {
+        tx: unsigned_tx(tx_chain_id),
+        chain_id: chain_id.into(),
+        key,
+    }
+}
+
+/// Prefixes the payload with its length, like the `LengthDelimitedCodec` used by the signer.
+fn frame(payload: &[u8]) -> Vec<u8> {
+    let mut frame = (payload.len() as u32).to_be_bytes().to_vec();
+    frame.extend_from_slice(payload);
+
+    frame
+}
@@ -0,0 +1,146 @@
+use std::time::{Duration, Instant};
+
+use clap::{Args, ValueEnum};
+use ethers::types::{transaction::eip2718::TypedTransaction, H256};
+use futures::{stream, StreamExt};
+use signer_client::{Client, Command, KeyId};
+
+#[derive(Args)]
+pub struct LoadArguments {
+    /// Total number of requests to send
+    #[clap(long, default_value = "1000")]
+    requests: usize,
+
+    /// Maximum number of requests in flight at the same time
+    #[clap(long, default_value = "16")]
+    concurrency: usize,
+
+    /// Number of connections the requests are spread over
+    #[clap(long, default_value = "1")]
+    connections: usize,
+
+    #[clap(long, value_enum, default_value = "sign-azero")]
+    command: LoadCommand,
+
+    /// Key to sign with, the signer's default key if not set
+    #[clap(long)]
+    key: Option<KeyId>,
+
+    /// Chain id used for `sign-eth-tx`
+    #[clap(long, default_value = "1")]
+    chain_id: u64,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LoadCommand {
+    Ping,
+    SignAzero,
+    SignEthHash,
+    SignEthTx,
+}
+
+impl LoadArguments {
+    /// Builds the `n`-th command. Payloads differ between requests, so that the signer can't
+    /// cache anything.
+    fn command(&self, n: usize) -> Command {
+        let key = self.key.clone();
+
+        match self.command {
+            LoadCommand::Ping => Command::Ping,
+            LoadCommand::SignAzero => Command::SignAzero {
+                payload: n.to_le_bytes().to_vec(),
+                key,
+            },
+            LoadCommand::SignEthHash => Command::SignEthHash {
+                hash: H256::from_low_u64_be(n as u64),
+                key,
+            },
+            LoadCommand::SignEthTx => {
+                let mut tx = TypedTransaction::Eip1559(Default::default());
+                tx.set_nonce(n as u64).set_chain_id(self.chain_id);
+
+                Command::SignEthTx {
+                    tx,
+                    chain_id: self.chain_id.into(),
+                    key,
+                }
+            }
+        }
+    }
+}
+
+/// Sends `requests` commands to the signer, keeping up to `concurrency` of them in flight, and
+/// prints the throughput and latency distribution.
+pub async fn run(cid: u32, port: u32, args: LoadArguments) {
+    let mut clients = Vec::with_capacity(args.connections);
+    for _ in 0..args.connections.max(1) {
+        clients.push(
+            Client::new(cid, port)
+                .await
+                .expect("Failed to connect to signer"),
+        );
+    }
+
+    let start = Instant::now();
+    let results: Vec<_> = stream::iter(0..args.requests)
+        .map(|n| {
+            let client = &clients[n % clients.len()];
+            let command = args.command(n);
+
+            async move {
+                let sent = Instant::now();
+                client.call(command).await.map(|_| sent.elapsed())
+            }
+        })
+        .buffer_unordered(args.concurrency.max(1))
+        .collect()
+        .await;
+    let elapsed = start.elapsed();
+
+    let mut latencies = Vec::with_capacity(results.len());
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(latency) => latencies.push(latency),
+            Err(why) => errors.push(why),
+        }
+    }
+    latencies.sort();
+
+    println!(
+        "Sent {} requests in {:.2?} over {} connection(s) with concurrency {}",
+        args.requests,
+        elapsed,
+        clients.len(),
+        args.concurrency
+    );
+    println!(
+        "Throughput: {:.1} successful requests/s",
+        latencies.len() as f64 / elapsed.as_secs_f64()
+    );
+    println!("Succeeded: {}, failed: {}", latencies.len(), errors.len());
+    if let Some(first) = errors.first() {
+        println!("First error: {first}");
+    }
+
+    if latencies.is_empty() {
+        return;
+    }
+
+    let mean = latencies.iter().sum::<Duration>() / latencies.len() as u32;
+    println!(
+        "Latency: min {:.2?}, mean {:.2?}, p50 {:.2?}, p90 {:.2?}, p99 {:.2?}, max {:.2?}",
+        latencies[0],
+        mean,
+        percentile(&latencies, 50),
+        percentile(&latencies, 90),
+        percentile(&latencies, 99),
+        latencies[latencies.len() - 1],
+    );
+}
+
+/// Expects `sorted` to be non-empty.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let index = (sorted.len() * percent).div_ceil(100).saturating_sub(1);
+    sorted[index.min(sorted.len() - 1)]
+}
@@ -1,4 +1,10 @@
-use clap::Parser;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use signer_client::Client;
+
+mod conformance;
+mod load;
 
 #[derive(Parser)]
 struct Arguments {
@@ -7,25 +13,58 @@ struct Arguments {
 
     #[clap(short, long, default_value = "1234")]
     port: u32,
+
+    #[clap(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Print every identity exposed by the signer (the default)
+    Identities,
+
+    /// Check that the signer behaves according to the protocol. Some checks expect rejections,
+    /// which a signer run with --lockdown-after-violations counts towards a lockdown, so only run
+    /// it against a signer without that option.
+    Conformance,
+
+    /// Measure signing latency and throughput
+    Load(load::LoadArguments),
 }
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
     let args = Arguments::parse();
-    let mut client = signer_client::Client::new(args.cid, args.port)
+    let client = Client::new(args.cid, args.port)
         .await
         .expect("Failed to connect to signer");
 
-    let azero_account_id = client
-        .azero_account_id()
-        .await
-        .expect("Failed to get Azero account ID");
+    match args.mode.unwrap_or(Mode::Identities) {
+        Mode::Identities => {
+            print_identities(&client).await;
+            ExitCode::SUCCESS
+        }
+        Mode::Conformance => {
+            if conformance::run(&client, args.cid, args.port).await {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Mode::Load(load_args) => {
+            load::run(args.cid, args.port, load_args).await;
+            ExitCode::SUCCESS
+        }
+    }
+}
 
-    let eth_address = client
-        .eth_address()
-        .await
-        .expect("Failed to get ETH address");
+async fn print_identities(client: &Client) {
+    let keys = client.list_keys().await.expect("Failed to list keys");
 
-    println!("Azero account ID: {:?}", azero_account_id);
-    println!("ETH address: {:?}", eth_address);
+    for key in keys {
+        println!("Key {}:", key.id);
+        println!("  Azero account ID: {:?}", key.azero_account_id);
+        println!("  ETH address: {:?}", key.eth_address);
+        println!("  Policy: {:?}", key.policy);
+    }
 }